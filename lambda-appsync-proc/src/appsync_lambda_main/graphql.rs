@@ -1,11 +1,11 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::HashMap};
 
 use graphql_parser::schema::{Definition, Document, TypeDefinition};
 use proc_macro2::Span;
-use quote::{quote, quote_spanned, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 
-use crate::common::{Name, OperationKind};
+use crate::common::{FieldCase, Name, OperationKind};
 
 use super::TypeOverride;
 
@@ -58,6 +58,30 @@ impl TryFrom<&str> for Scalar {
         }
     }
 }
+impl Scalar {
+    fn graphql_name(&self) -> &'static str {
+        match self {
+            Scalar::String => "String",
+            Scalar::ID => "ID",
+            Scalar::Int => "Int",
+            Scalar::Float => "Float",
+            Scalar::Boolean => "Boolean",
+            Scalar::AWSEmail => "AWSEmail",
+            Scalar::AWSPhone => "AWSPhone",
+            Scalar::AWSTimestamp => "AWSTimestamp",
+            Scalar::AWSDate => "AWSDate",
+            Scalar::AWSTime => "AWSTime",
+            Scalar::AWSDateTime => "AWSDateTime",
+            Scalar::AWSJSON => "AWSJSON",
+            Scalar::AWSURL => "AWSURL",
+            Scalar::AWSIPAddress => "AWSIPAddress",
+        }
+    }
+    // Scalars that can be used as a subscription filter value
+    fn is_filter_scalar(&self) -> bool {
+        !matches!(self, Scalar::AWSJSON | Scalar::AWSIPAddress)
+    }
+}
 impl ToTokens for Scalar {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let span = current_span();
@@ -81,11 +105,16 @@ impl ToTokens for Scalar {
 }
 
 enum FieldType {
-    Overriden(syn::Type),
+    // A `type_override`, along with the schema type it replaces
+    Overriden(syn::Type, Box<FieldType>),
     Custom { name: Name },
     Scalar(Scalar),
     List(Box<FieldType>),
     Optionnal(Box<FieldType>),
+    // An optional argument telling absent from null, see `nullable_arg`
+    Nullable(Box<FieldType>),
+    // A nullable list element generated as required, see `lists_non_null`
+    NonNullElement(Box<FieldType>),
 }
 impl FieldType {
     fn from_string(name: String) -> Self {
@@ -97,15 +126,288 @@ impl FieldType {
         }
     }
     fn is_optionnal(&self) -> bool {
-        matches!(self, FieldType::Optionnal(_))
+        matches!(self, FieldType::Optionnal(_) | FieldType::Nullable(_))
+    }
+    fn is_nullable(&self) -> bool {
+        matches!(self, FieldType::Nullable(_))
+    }
+    // Turns an `Optionnal` into its inner type, returns false if the type is not optionnal
+    fn make_required(&mut self) -> bool {
+        match self {
+            FieldType::Optionnal(field_type) => {
+                // The placeholder is dropped right away
+                let field_type =
+                    std::mem::replace(field_type, Box::new(FieldType::Scalar(Scalar::Boolean)));
+                *self = *field_type;
+                true
+            }
+            _ => false,
+        }
+    }
+    // Turns an `Optionnal` into a `Nullable`, returns false if the type is not optionnal
+    fn make_nullable(&mut self) -> bool {
+        match self {
+            FieldType::Optionnal(field_type) => {
+                // The placeholder is dropped right away
+                let field_type =
+                    std::mem::replace(field_type, Box::new(FieldType::Scalar(Scalar::Boolean)));
+                *self = FieldType::Nullable(field_type);
+                true
+            }
+            FieldType::Nullable(_) => true,
+            _ => false,
+        }
+    }
+    // Turns the nullable elements of the lists into `NonNullElement`s, returns true if any was
+    fn make_list_elements_non_null(&mut self) -> bool {
+        match self {
+            FieldType::List(field_type) => {
+                let stripped = if let FieldType::Optionnal(element_type) = &mut **field_type {
+                    // The placeholder is dropped right away
+                    let element_type = std::mem::replace(
+                        element_type,
+                        Box::new(FieldType::Scalar(Scalar::Boolean)),
+                    );
+                    **field_type = FieldType::NonNullElement(element_type);
+                    true
+                } else {
+                    false
+                };
+                field_type.make_list_elements_non_null() || stripped
+            }
+            FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => field_type.make_list_elements_non_null(),
+            FieldType::Overriden(..) | FieldType::Custom { .. } | FieldType::Scalar(_) => false,
+        }
+    }
+    fn non_optionnal(&self) -> &FieldType {
+        match self {
+            FieldType::Optionnal(field_type) => field_type,
+            field_type => field_type,
+        }
+    }
+    // The type parameter of the `TypedFieldPath` of a field of this type, if the field can be filtered on
+    fn filter_type(&self, enums: &[Enum]) -> Option<proc_macro2::TokenStream> {
+        let span = current_span();
+        match self.non_optionnal() {
+            FieldType::Scalar(scalar) if scalar.is_filter_scalar() => {
+                Some(quote_spanned! {span=>#scalar})
+            }
+            FieldType::Custom { name } if enums.iter().any(|e| e.name.orig() == name.orig()) => {
+                let name = name.to_type_ident();
+                Some(quote_spanned! {span=>#name})
+            }
+            FieldType::List(field_type) => match field_type.non_optionnal() {
+                FieldType::List(_) => None,
+                field_type => field_type
+                    .filter_type(enums)
+                    .map(|item_type| quote_spanned! {span=>Vec<#item_type>}),
+            },
+            FieldType::NonNullElement(field_type) => field_type.filter_type(enums),
+            _ => None,
+        }
+    }
+    fn contains_json(&self) -> bool {
+        match self {
+            FieldType::Scalar(scalar) => matches!(scalar, Scalar::AWSJSON),
+            FieldType::List(field_type)
+            | FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => field_type.contains_json(),
+            FieldType::Overriden(..) | FieldType::Custom { .. } => false,
+        }
+    }
+    // An expression generating an arbitrary value of this type from `u`, see `arbitrary`.
+    // `AWSJSON` is a `serde_json::Value`, which does not implement `Arbitrary`
+    fn arbitrary_expr(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        match self {
+            FieldType::Scalar(Scalar::AWSJSON) => quote_spanned! {span=>
+                ::lambda_appsync::arbitrary_json(u)?
+            },
+            // `Some(null)` would be deserialized as `None`
+            FieldType::Optionnal(field_type)
+                if matches!(**field_type, FieldType::Scalar(Scalar::AWSJSON)) =>
+            {
+                quote_spanned! {span=>
+                    ::core::option::Option::Some(::lambda_appsync::arbitrary_json(u)?)
+                        .filter(|value| !value.is_null())
+                }
+            }
+            FieldType::Optionnal(field_type) if field_type.contains_json() => {
+                let item = field_type.arbitrary_expr();
+                quote_spanned! {span=>
+                    if u.arbitrary::<bool>()? { ::core::option::Option::Some(#item) } else { ::core::option::Option::None }
+                }
+            }
+            FieldType::NonNullElement(field_type) => field_type.arbitrary_expr(),
+            FieldType::List(field_type) if field_type.contains_json() => {
+                let item = field_type.arbitrary_expr();
+                quote_spanned! {span=>
+                    {
+                        let mut items = ::std::vec::Vec::new();
+                        while u.arbitrary::<bool>()? {
+                            items.push(#item);
+                        }
+                        items
+                    }
+                }
+            }
+            _ => quote_spanned! {span=>
+                ::lambda_appsync::arbitrary::Arbitrary::arbitrary(u)?
+            },
+        }
+    }
+    // Whether this type refers to a type or input, which may refer back to the type being generated
+    fn contains_structure(&self, enums: &[Enum]) -> bool {
+        match self {
+            FieldType::Custom { name } => !enums.iter().any(|e| e.name.orig() == name.orig()),
+            FieldType::List(field_type)
+            | FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => field_type.contains_structure(enums),
+            FieldType::Overriden(..) | FieldType::Scalar(_) => false,
+        }
+    }
+    // A proptest strategy generating values of this type, see `proptest`. Types and inputs are
+    // generated with the strategies of `strategies_to_tokens`, `depth` being the remaining nesting
+    // of lists of types allowed, as a type can only refer back to itself through a list
+    fn strategy_expr(&self, enums: &[Enum]) -> proc_macro2::TokenStream {
+        let span = current_span();
+        match self {
+            // Reasonable values, which also keeps NaN and infinities (serialized as `null`) out
+            FieldType::Scalar(Scalar::Float) => quote_spanned! {span=>
+                -1e9f64..1e9f64
+            },
+            FieldType::Scalar(Scalar::AWSJSON) => quote_spanned! {span=>
+                ::lambda_appsync::json_strategy()
+            },
+            FieldType::Scalar(scalar) => quote_spanned! {span=>
+                ::lambda_appsync::proptest::arbitrary::any::<#scalar>()
+            },
+            FieldType::Overriden(ty, _) => quote_spanned! {span=>
+                ::lambda_appsync::proptest::arbitrary::any::<#ty>()
+            },
+            FieldType::Custom { name } => {
+                let strategy_fct = name.to_strategy_fct_ident();
+                if enums.iter().any(|e| e.name.orig() == name.orig()) {
+                    quote_spanned! {span=>#strategy_fct()}
+                } else {
+                    let strategy_fct = format_ident!("{strategy_fct}_with_depth");
+                    quote_spanned! {span=>#strategy_fct(depth)}
+                }
+            }
+            // `Some(null)` would be deserialized as `None`
+            FieldType::Optionnal(field_type)
+                if matches!(**field_type, FieldType::Scalar(Scalar::AWSJSON)) =>
+            {
+                quote_spanned! {span=>
+                    ::lambda_appsync::proptest::option::of(::lambda_appsync::json_strategy())
+                        .prop_map(|value| value.filter(|value| !value.is_null()))
+                }
+            }
+            FieldType::Optionnal(field_type) => {
+                let strategy = field_type.strategy_expr(enums);
+                quote_spanned! {span=>
+                    ::lambda_appsync::proptest::option::of(#strategy)
+                }
+            }
+            FieldType::Nullable(field_type) => {
+                let strategy = field_type.strategy_expr(enums);
+                quote_spanned! {span=>
+                    ::lambda_appsync::proptest::option::of(#strategy)
+                        .prop_map(::lambda_appsync::Nullable::from)
+                }
+            }
+            FieldType::NonNullElement(field_type) => field_type.strategy_expr(enums),
+            FieldType::List(field_type) if field_type.contains_structure(enums) => {
+                let strategy = field_type.strategy_expr(enums);
+                quote_spanned! {span=>
+                    if depth == 0 {
+                        ::lambda_appsync::proptest::strategy::Just(::std::vec::Vec::new()).boxed()
+                    } else {
+                        let depth = depth - 1;
+                        ::lambda_appsync::proptest::collection::vec(#strategy, 0..4).boxed()
+                    }
+                }
+            }
+            FieldType::List(field_type) => {
+                let strategy = field_type.strategy_expr(enums);
+                quote_spanned! {span=>
+                    ::lambda_appsync::proptest::collection::vec(#strategy, 0..4)
+                }
+            }
+        }
+    }
+    // The type as written in a GraphQL schema, ignoring the `type_override`s
+    fn to_sdl(&self) -> String {
+        let non_null = match self {
+            FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => {
+                return field_type.to_sdl().trim_end_matches('!').to_owned();
+            }
+            FieldType::Overriden(_, schema_type) => return schema_type.to_sdl(),
+            FieldType::Custom { name } => name.orig().to_owned(),
+            FieldType::Scalar(scalar) => scalar.graphql_name().to_owned(),
+            FieldType::List(field_type) => format!("[{}]", field_type.to_sdl()),
+        };
+        format!("{non_null}!")
+    }
+    // The name of the schema type this type refers to, if any
+    fn custom_name(&self) -> Option<&Name> {
+        match self {
+            FieldType::Custom { name } => Some(name),
+            FieldType::List(field_type)
+            | FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => field_type.custom_name(),
+            FieldType::Overriden(..) | FieldType::Scalar(_) => None,
+        }
+    }
+    // Renames the schema type this type refers to, if it is in `new_names`
+    fn rename_custom(&mut self, new_names: &HashMap<String, String>) {
+        match self {
+            FieldType::Custom { name } => {
+                if let Some(new_name) = new_names.get(name.orig()) {
+                    name.override_name(new_name.clone());
+                }
+            }
+            FieldType::List(field_type)
+            | FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => field_type.rename_custom(new_names),
+            FieldType::Overriden(..) | FieldType::Scalar(_) => (),
+        }
+    }
+    fn set_namespace(&mut self, namespace: &str) {
+        match self {
+            FieldType::Custom { name } => name.set_namespace(namespace),
+            FieldType::List(field_type)
+            | FieldType::Optionnal(field_type)
+            | FieldType::Nullable(field_type)
+            | FieldType::NonNullElement(field_type) => field_type.set_namespace(namespace),
+            FieldType::Overriden(..) | FieldType::Scalar(_) => (),
+        }
     }
     fn override_type(&mut self, type_override: TypeOverride) {
         match self {
-            FieldType::Overriden(_) | FieldType::Custom { .. } | FieldType::Scalar(_) => {
-                *self = FieldType::Overriden(type_override.type_ident())
+            FieldType::Overriden(_, schema_type) => {
+                // The placeholder is dropped right away
+                let schema_type =
+                    std::mem::replace(schema_type, Box::new(FieldType::Scalar(Scalar::Boolean)));
+                *self = FieldType::Overriden(type_override.type_ident(), schema_type)
+            }
+            FieldType::Custom { .. } | FieldType::Scalar(_) => {
+                // The placeholder is dropped right away
+                let schema_type = std::mem::replace(self, FieldType::Scalar(Scalar::Boolean));
+                *self = FieldType::Overriden(type_override.type_ident(), Box::new(schema_type))
             }
             FieldType::List(field_type) => field_type.override_type(type_override),
             FieldType::Optionnal(field_type) => field_type.override_type(type_override),
+            FieldType::Nullable(field_type) => field_type.override_type(type_override),
+            FieldType::NonNullElement(field_type) => field_type.override_type(type_override),
         }
     }
 }
@@ -146,7 +448,11 @@ impl ToTokens for FieldType {
             FieldType::Optionnal(field_type) => {
                 tokens.extend(quote_spanned! {span=>::core::option::Option<#field_type>})
             }
-            FieldType::Overriden(ty) => tokens.extend(quote_spanned! {span=>#ty}),
+            FieldType::Nullable(field_type) => {
+                tokens.extend(quote_spanned! {span=>::lambda_appsync::Nullable<#field_type>})
+            }
+            FieldType::Overriden(ty, _) => tokens.extend(quote_spanned! {span=>#ty}),
+            FieldType::NonNullElement(field_type) => field_type.to_tokens(tokens),
         }
     }
 }
@@ -154,50 +460,131 @@ impl ToTokens for FieldType {
 struct Field {
     name: Name,
     field_type: FieldType,
+    // Nullable in the schema but not optional in Rust, see `required_override`
+    required_override: bool,
+    // Deserialize an absent or null value as the default value, see `required_override`
+    default_if_null: bool,
+    // Deserialized but never serialized, see `skip_serialize`
+    skip_serializing: bool,
+    // The GraphQL description of arguments and input fields, used as doc comment
+    description: Option<String>,
+    // Skip the null elements of the list when deserializing, see `lists_non_null`
+    skip_null_elements: bool,
+}
+impl Field {
+    fn new(name: Name, field_type: FieldType) -> Self {
+        Self {
+            name,
+            field_type,
+            required_override: false,
+            default_if_null: false,
+            skip_serializing: false,
+            description: None,
+            skip_null_elements: false,
+        }
+    }
+    fn make_list_elements_non_null(&mut self, skip_nulls: bool) {
+        if self.field_type.make_list_elements_non_null() {
+            self.skip_null_elements = skip_nulls;
+        }
+    }
 }
 impl From<graphql_parser::schema::Field<'_, String>> for Field {
     fn from(value: graphql_parser::schema::Field<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
         let field_type = FieldType::from(value.field_type);
-        Self { name, field_type }
+        Self::new(name, field_type)
     }
 }
 impl From<graphql_parser::schema::InputValue<'_, String>> for Field {
     fn from(value: graphql_parser::schema::InputValue<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
         let field_type = FieldType::from(value.value_type);
-        Self { name, field_type }
+        let mut field = Self::new(name, field_type);
+        field.description = value.description;
+        field
+    }
+}
+
+// The `#[doc]` attributes of a GraphQL description, one per line so that multi-line descriptions
+// render as written. Block strings are already dedented by `graphql_parser`
+fn description_doc(description: &str) -> proc_macro2::TokenStream {
+    let span = current_span();
+    let lines = description
+        .trim()
+        .lines()
+        .map(|line| format!(" {}", line.trim_end()));
+    quote_spanned! {span=>
+        #(#[doc = #lines])*
     }
 }
 
 struct FieldContext<'a> {
     field: &'a Field,
+    field_case: FieldCase,
+    // The struct has a `rename_all` casing, see `serde_rename_all`
+    rename_all: bool,
 }
 impl<'a> FieldContext<'a> {
-    fn new(field: &'a Field) -> Self {
-        Self { field }
+    fn new(field: &'a Field, field_case: FieldCase, rename_all: bool) -> Self {
+        Self {
+            field,
+            field_case,
+            rename_all,
+        }
     }
 }
+// The `rename` of a field to its GraphQL name, if its Rust name differs. With a `rename_all`
+// casing on the struct, only the fields with a `name_override` keep it
+fn field_rename<'a>(
+    field: &'a Field,
+    name: &proc_macro2::Ident,
+    rename_all: bool,
+) -> Option<&'a str> {
+    let orig_name = field.name.orig();
+    (name != orig_name && (!rename_all || field.name.is_overriden())).then_some(orig_name)
+}
 impl ToTokens for FieldContext<'_> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let field = self.field;
-        let name = field.name.to_var_ident();
-        // If the `name` identifier is different from the original name, we must serde_rename the type
-        let orig_name = field.name.orig();
+        let name = field.name.to_field_ident(self.field_case);
 
         let field_type = &field.field_type;
         let mut serde_options = vec![];
         let span = current_span();
-        if name != orig_name {
+        // If the `name` identifier is different from the original name, we must serde_rename the type
+        if let Some(orig_name) = field_rename(field, &name, self.rename_all) {
             serde_options.push(quote_spanned! {span=>
                 rename = #orig_name
             });
         }
+        if field.skip_serializing {
+            serde_options.push(quote_spanned! {span=>
+                skip_serializing
+            });
+        }
         if field_type.is_optionnal() {
+            let skip_serializing_if = (!field.skip_serializing).then(|| {
+                quote_spanned! {span=>
+                    , skip_serializing_if = "Option::is_none"
+                }
+            });
             serde_options.push(quote_spanned! {span=>
-                default, skip_serializing_if = "Option::is_none"
+                default #skip_serializing_if
             });
         }
+        if field.default_if_null {
+            serde_options.push(quote_spanned! {span=>
+                default, deserialize_with = "::lambda_appsync::null_as_default"
+            });
+        } else if field.skip_null_elements {
+            serde_options.push(quote_spanned! {span=>
+                deserialize_with = "::lambda_appsync::skip_null_elements"
+            });
+        }
+        if let Some(ref description) = field.description {
+            tokens.extend(description_doc(description));
+        }
         if !serde_options.is_empty() {
             tokens.extend(quote_spanned! {span=>
                 #[serde(#(#serde_options),*)]
@@ -209,11 +596,286 @@ impl ToTokens for FieldContext<'_> {
     }
 }
 
+// Turns the calls produced by `TypeOverride::serde_check` into compile-time assertions,
+// so that an override type lacking a serde trait is reported at the override site
+fn serde_checks_to_tokens(serde_checks: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    if serde_checks.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+    quote! {
+        const _: fn() = || {
+            #[diagnostic::on_unimplemented(
+                message = "override type `{Self}` must implement `Serialize` and `Deserialize`",
+                label = "this type is used in a `type_override` of a type or input field"
+            )]
+            trait SerdeOverride {}
+            impl<T: ::lambda_appsync::serde::Serialize + ::lambda_appsync::serde::de::DeserializeOwned> SerdeOverride for T {}
+            #[diagnostic::on_unimplemented(
+                message = "override type `{Self}` must implement `Deserialize`",
+                label = "this type is used in a `type_override` of an operation argument"
+            )]
+            trait DeserializeOverride {}
+            impl<T: ::lambda_appsync::serde::de::DeserializeOwned> DeserializeOverride for T {}
+            #[allow(dead_code)]
+            fn serde_override<T: SerdeOverride>() {}
+            #[allow(dead_code)]
+            fn deserialize_override<T: DeserializeOverride>() {}
+            #(#serde_checks)*
+        };
+    }
+}
+
 struct Structure {
     name: Name,
     fields: Vec<Field>,
+    field_case: FieldCase,
+    // Reject unknown fields when deserializing, see `strict_inputs`
+    deny_unknown_fields: bool,
+    serde_checks: Vec<proc_macro2::TokenStream>,
+    // Indexes of the fields of the `@key` directive, see `federation_keys`
+    key: Vec<usize>,
+    // Also generate the `<Name>Patch` struct, see `generate_patch`
+    patch: bool,
+    // Declared as a GraphQL `input` rather than a `type`
+    input: bool,
+    // Also implement `Arbitrary`, see `arbitrary`
+    arbitrary: bool,
+    // Also generate the `<Name>Field` enum, see `emit_field_enums`
+    field_enum: bool,
+    // The `#[serde(rename_all)]` casing of the fields, see `serde_rename_all`
+    rename_all: Option<&'static str>,
 }
 impl Structure {
+    fn set_key(&mut self, key_fields: &str, span: Span) -> Result<(), syn::Error> {
+        if key_fields.contains(['{', '}']) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "Nested fields are not supported in the `@key` directive of `{}`",
+                    self.name.orig()
+                ),
+            ));
+        }
+        let mut errors = vec![];
+        for key_field in key_fields.split_whitespace() {
+            match self.fields.iter().position(|f| f.name.orig() == key_field) {
+                Some(index) => self.key.push(index),
+                None => errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "No field `{key_field}` in `{}` for its `@key` directive",
+                        self.name.orig()
+                    ),
+                )),
+            }
+        }
+        if self.key.is_empty() && errors.is_empty() {
+            errors.push(syn::Error::new(
+                span,
+                format!(
+                    "The `@key` directive of `{}` has no field",
+                    self.name.orig()
+                ),
+            ));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors
+                .into_iter()
+                .reduce(|mut acc, e| {
+                    acc.combine(e);
+                    acc
+                })
+                .expect("not empty"))
+        }
+    }
+    // The `Arbitrary` implementation, see `arbitrary`
+    fn arbitrary_to_tokens(&self) -> proc_macro2::TokenStream {
+        if !self.arbitrary {
+            return proc_macro2::TokenStream::new();
+        }
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let field_names = self
+            .fields
+            .iter()
+            .map(|field| field.name.to_field_ident(self.field_case));
+        let field_values = self
+            .fields
+            .iter()
+            .map(|field| field.field_type.arbitrary_expr());
+        quote_spanned! {span=>
+            impl<'a> ::lambda_appsync::arbitrary::Arbitrary<'a> for #struct_name {
+                fn arbitrary(
+                    u: &mut ::lambda_appsync::arbitrary::Unstructured<'a>,
+                ) -> ::lambda_appsync::arbitrary::Result<Self> {
+                    ::core::result::Result::Ok(Self {
+                        #(#field_names: #field_values,)*
+                    })
+                }
+            }
+        }
+    }
+    // The `<Name>Field` enum of the fields, see `emit_field_enums`
+    fn field_enum_to_tokens(&self) -> proc_macro2::TokenStream {
+        if !self.field_enum {
+            return proc_macro2::TokenStream::new();
+        }
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let enum_name = format_ident!("{}Field", struct_name);
+        let enum_doc = format!(
+            "The fields of [{struct_name}], [{enum_name}::as_str] giving their GraphQL name"
+        );
+        let variants = self
+            .fields
+            .iter()
+            .map(|field| field.name.to_variant_ident())
+            .collect::<Vec<_>>();
+        let names = self.fields.iter().map(|field| field.name.orig());
+        let names_bis = names.clone();
+        let count = self.fields.len();
+        quote_spanned! {span=>
+            #[doc = #enum_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum #enum_name {
+                #(#variants,)*
+            }
+            impl #enum_name {
+                /// All the fields, in the order of the schema
+                pub const ALL: [Self; #count] = [#(Self::#variants,)*];
+                /// Returns the GraphQL name of the field
+                pub const fn as_str(&self) -> &'static str {
+                    match self {
+                        #(Self::#variants => #names,)*
+                    }
+                }
+                /// Returns the field with the given GraphQL name, if any
+                pub fn from_name(name: &str) -> ::core::option::Option<Self> {
+                    match name {
+                        #(#names_bis => ::core::option::Option::Some(Self::#variants),)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+            impl ::core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        }
+    }
+    // The `<Name>Patch` struct with all the fields optional and the `apply_patch` method
+    fn patch_to_tokens(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        if !self.patch {
+            return proc_macro2::TokenStream::new();
+        }
+        let struct_name = self.name.to_type_ident();
+        let patch_name = format_ident!("{}Patch", struct_name);
+        let patch_doc = format!(
+            "Partial update of [{struct_name}], applied with [{struct_name}::apply_patch]. \
+            Nullable fields use [Nullable](::lambda_appsync::Nullable) to tell \"don't touch\" from \"set to null\""
+        );
+        let patch_fields = self.fields.iter().map(|field| {
+            let name = field.name.to_field_ident(self.field_case);
+            let rename = match field_rename(field, &name, self.rename_all.is_some()) {
+                Some(orig_name) => quote_spanned! {span=>rename = #orig_name,},
+                None => proc_macro2::TokenStream::new(),
+            };
+            match field.field_type {
+                FieldType::Optionnal(ref inner) => quote_spanned! {span=>
+                    #[serde(#rename default, skip_serializing_if = "::lambda_appsync::Nullable::is_absent")]
+                    pub #name: ::lambda_appsync::Nullable<#inner>
+                },
+                ref field_type => quote_spanned! {span=>
+                    #[serde(#rename default, skip_serializing_if = "Option::is_none")]
+                    pub #name: ::core::option::Option<#field_type>
+                },
+            }
+        });
+        let apply_fields = self.fields.iter().map(|field| {
+            let name = field.name.to_field_ident(self.field_case);
+            match field.field_type {
+                FieldType::Optionnal(_) => quote_spanned! {span=>
+                    if let ::core::option::Option::Some(value) = patch.#name.into_patch() {
+                        self.#name = value;
+                    }
+                },
+                _ => quote_spanned! {span=>
+                    if let ::core::option::Option::Some(value) = patch.#name {
+                        self.#name = value;
+                    }
+                },
+            }
+        });
+        let allow_non_snake_case = if self.field_case == FieldCase::Preserve {
+            quote_spanned! {span=>#[allow(non_snake_case)]}
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+        quote_spanned! {span=>
+            #[doc = #patch_doc]
+            #[derive(Debug, Clone, Default, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize)]
+            #[serde(crate = "::lambda_appsync::serde")]
+            #allow_non_snake_case
+            pub struct #patch_name {
+                #(#patch_fields,)*
+            }
+            impl #struct_name {
+                /// Overwrites the fields provided by the patch, leaving the others untouched
+                pub fn apply_patch(&mut self, patch: #patch_name) {
+                    #(#apply_fields)*
+                }
+            }
+        }
+    }
+    fn key_to_tokens(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        if self.key.is_empty() {
+            return proc_macro2::TokenStream::new();
+        }
+        let struct_name = self.name.to_type_ident();
+        let key_fields = self
+            .key
+            .iter()
+            .map(|&i| &self.fields[i])
+            .collect::<Vec<_>>();
+        let names = key_fields
+            .iter()
+            .map(|f| f.name.to_field_ident(self.field_case));
+        let types = key_fields.iter().map(|f| &f.field_type);
+        let doc = format!(
+            "Returns the key of `{}`, from its `@key(fields: \"{}\")` directive",
+            self.name.orig(),
+            key_fields
+                .iter()
+                .map(|f| f.name.orig())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        // A single key field is returned as is, several as a tuple
+        let (key_type, key_value) = if key_fields.len() == 1 {
+            (
+                quote_spanned! {span=>#(&#types)*},
+                quote_spanned! {span=>#(&self.#names)*},
+            )
+        } else {
+            (
+                quote_spanned! {span=>(#(&#types),*)},
+                quote_spanned! {span=>(#(&self.#names),*)},
+            )
+        };
+        quote_spanned! {span=>
+            impl #struct_name {
+                #[doc = #doc]
+                pub fn key(&self) -> #key_type {
+                    #key_value
+                }
+            }
+        }
+    }
     fn apply_type_overrides(
         &mut self,
         mut type_overrides: super::FieldTypeOverrides,
@@ -231,6 +893,8 @@ impl Structure {
                     }));
                 }
                 if let Some(field_override) = field_override {
+                    self.serde_checks
+                        .push(field_override.serde_check("serde_override"));
                     field.field_type.override_type(field_override);
                 }
             }
@@ -305,26 +969,82 @@ impl From<graphql_parser::schema::ObjectType<'_, String>> for Structure {
     fn from(value: graphql_parser::schema::ObjectType<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
         let fields = value.fields.into_iter().map(Field::from).collect();
-        Self { name, fields }
+        Self {
+            name,
+            fields,
+            field_case: FieldCase::default(),
+            deny_unknown_fields: false,
+            serde_checks: vec![],
+            key: vec![],
+            patch: false,
+            input: false,
+            arbitrary: false,
+            field_enum: false,
+            rename_all: None,
+        }
     }
 }
 impl From<graphql_parser::schema::InputObjectType<'_, String>> for Structure {
     fn from(value: graphql_parser::schema::InputObjectType<'_, String>) -> Self {
         let name = Name::from(value.name);
         let fields = value.fields.into_iter().map(Field::from).collect();
-        Self { name, fields }
+        Self {
+            name,
+            fields,
+            field_case: FieldCase::default(),
+            deny_unknown_fields: false,
+            serde_checks: vec![],
+            key: vec![],
+            patch: false,
+            input: true,
+            arbitrary: false,
+            field_enum: false,
+            rename_all: None,
+        }
     }
 }
 impl ToTokens for Structure {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let span = current_span();
         let struct_name = self.name.to_type_ident();
-        let fields = self.fields.iter().map(FieldContext::new);
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| FieldContext::new(field, self.field_case, self.rename_all.is_some()));
+        let serde_checks = serde_checks_to_tokens(&self.serde_checks);
+        // Preserved GraphQL names are usually camelCase
+        let allow_non_snake_case = if self.field_case == FieldCase::Preserve {
+            quote_spanned! {span=>#[allow(non_snake_case)]}
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+        let rename_all = match self.rename_all {
+            Some(rule) => quote_spanned! {span=>#[serde(rename_all = #rule)]},
+            None => proc_macro2::TokenStream::new(),
+        };
+        let deny_unknown_fields = if self.deny_unknown_fields {
+            quote_spanned! {span=>#[serde(deny_unknown_fields)]}
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+        let key = self.key_to_tokens();
+        let patch = self.patch_to_tokens();
+        let arbitrary = self.arbitrary_to_tokens();
+        let field_enum = self.field_enum_to_tokens();
         tokens.extend(quote_spanned! {span=>
             #[derive(Debug, Clone, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize)]
+            #[serde(crate = "::lambda_appsync::serde")]
+            #rename_all
+            #deny_unknown_fields
+            #allow_non_snake_case
             pub struct #struct_name {
                 #(#fields,)*
             }
+            #key
+            #patch
+            #arbitrary
+            #field_enum
+            #serde_checks
         });
     }
 }
@@ -333,6 +1053,10 @@ impl ToTokens for Structure {
 struct Enum {
     name: Name,
     variants: Vec<Name>,
+    // Also generate integer conversions, see `enum_discriminants`
+    discriminants: bool,
+    // Also implement `Arbitrary`, see `arbitrary`
+    arbitrary: bool,
 }
 impl Enum {
     fn apply_name_overrides(
@@ -384,7 +1108,12 @@ impl From<graphql_parser::schema::EnumType<'_, String>> for Enum {
             .into_iter()
             .map(|v| Name::from((v.name, current_span())))
             .collect();
-        Self { name, variants }
+        Self {
+            name,
+            variants,
+            discriminants: false,
+            arbitrary: false,
+        }
     }
 }
 impl ToTokens for Enum {
@@ -399,10 +1128,29 @@ impl ToTokens for Enum {
             .collect::<Vec<_>>();
         let error_message = format!("`{{}}` is an invalid value for enum {}", enum_name);
         let span = current_span();
+        // The discriminants follow the declaration order in the schema
+        let (repr, discriminants) = if self.discriminants {
+            (
+                quote_spanned! {span=>#[repr(i32)]},
+                (0..self.variants.len())
+                    .map(|i| {
+                        let i = proc_macro2::Literal::i32_unsuffixed(i as i32);
+                        quote_spanned! {span=> = #i}
+                    })
+                    .collect(),
+            )
+        } else {
+            (
+                proc_macro2::TokenStream::new(),
+                vec![proc_macro2::TokenStream::new(); self.variants.len()],
+            )
+        };
         tokens.extend(quote_spanned! {span=>
             #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[serde(crate = "::lambda_appsync::serde")]
+            #repr
             pub enum #enum_name {
-                #(#[serde(rename = #variant_orig_iter)]#variants,)*
+                #(#[serde(rename = #variant_orig_iter)]#variants #discriminants,)*
             }
             impl #enum_name {
                 pub const COUNT: usize = #count;
@@ -410,6 +1158,7 @@ impl ToTokens for Enum {
                     [#(Self::#variants,)*]
                 }
             }
+            impl ::lambda_appsync::subscription_filters::FilterScalar for #enum_name {}
             impl ::core::fmt::Display for #enum_name {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                     match self {
@@ -430,7 +1179,65 @@ impl ToTokens for Enum {
                     }
                 }
             }
+            impl ::core::convert::TryFrom<&str> for #enum_name {
+                type Error = ::lambda_appsync::AppsyncError;
+
+                fn try_from(value: &str) -> ::core::result::Result<Self, Self::Error> {
+                    value.parse()
+                }
+            }
+            impl ::core::convert::TryFrom<String> for #enum_name {
+                type Error = ::lambda_appsync::AppsyncError;
+
+                fn try_from(value: String) -> ::core::result::Result<Self, Self::Error> {
+                    value.parse()
+                }
+            }
         });
+        if self.arbitrary {
+            tokens.extend(quote_spanned! {span=>
+                impl<'a> ::lambda_appsync::arbitrary::Arbitrary<'a> for #enum_name {
+                    fn arbitrary(
+                        u: &mut ::lambda_appsync::arbitrary::Unstructured<'a>,
+                    ) -> ::lambda_appsync::arbitrary::Result<Self> {
+                        u.choose(&Self::all()).copied()
+                    }
+                }
+            });
+        }
+        if self.discriminants {
+            let discriminants =
+                (0..self.variants.len()).map(|i| proc_macro2::Literal::i32_unsuffixed(i as i32));
+            let error_message = format!("`{{}}` is an invalid discriminant for enum {}", enum_name);
+            tokens.extend(quote_spanned! {span=>
+                impl #enum_name {
+                    pub fn from_ordinal(ordinal: u32) -> ::core::option::Option<Self> {
+                        Self::all().get(ordinal as usize).copied()
+                    }
+                    pub fn to_ordinal(self) -> u32 {
+                        self as u32
+                    }
+                }
+                impl ::core::convert::From<#enum_name> for i32 {
+                    fn from(value: #enum_name) -> Self {
+                        value as i32
+                    }
+                }
+                impl ::core::convert::TryFrom<i32> for #enum_name {
+                    type Error = ::lambda_appsync::AppsyncError;
+
+                    fn try_from(value: i32) -> ::core::result::Result<Self, Self::Error> {
+                        match value {
+                            #(#discriminants => ::core::result::Result::Ok(Self::#variants),)*
+                            _ => ::core::result::Result::Err(::lambda_appsync::AppsyncError::new(
+                                "InvalidDiscriminant",
+                                format!(#error_message, value),
+                            ))
+                        }
+                    }
+                }
+            });
+        }
     }
 }
 
@@ -438,21 +1245,42 @@ struct Operation {
     name: Name,
     args: Vec<Field>,
     return_type: FieldType,
+    cfg: Option<proc_macro2::TokenStream>,
+    serde_checks: Vec<proc_macro2::TokenStream>,
+    // Span of the `type_override` of the return type, whose values go through `OverrideToJson`
+    return_override: Option<Span>,
 }
 impl Operation {
-    fn variant(&self) -> proc_macro2::Ident {
-        self.name.to_type_ident()
+    fn cfg_attr(&self) -> proc_macro2::TokenStream {
+        match self.cfg {
+            Some(ref predicate) => quote! {#[cfg(#predicate)]},
+            None => proc_macro2::TokenStream::new(),
+        }
     }
-    fn default_op(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+    fn variant(&self) -> proc_macro2::TokenStream {
+        let cfg_attr = self.cfg_attr();
+        let variant = self.name.to_type_ident();
+        quote! {#cfg_attr #variant}
+    }
+    fn default_op(
+        &self,
+        kind: OperationKind,
+        resolvers: Option<&syn::Path>,
+        warn_unimplemented: bool,
+    ) -> proc_macro2::TokenStream {
         let fct_name = self.name.to_prefixed_fct_ident(kind.fct_prefix());
         let span = current_span();
+        if let (Some(resolvers), OperationKind::Query | OperationKind::Mutation) = (resolvers, kind)
+        {
+            return self.resolver_op(kind, resolvers);
+        }
         let return_type = match kind {
             OperationKind::Query | OperationKind::Mutation => {
                 let return_type = &self.return_type;
                 quote_spanned! {span=>#return_type}
             }
             OperationKind::Subscription => quote_spanned! {span=>
-                ::core::option::Option<::lambda_appsync::subscription_filters::FilterGroup>
+                ::core::option::Option<::lambda_appsync::subscription_filters::SubscriptionFilters>
             },
         };
         let default_body = match kind {
@@ -470,27 +1298,198 @@ impl Operation {
                 ::core::result::Result::Ok(None)
             },
         };
+        // The default operation is only called when no `appsync_operation` handler shadows it, so
+        // deprecating it makes rustc warn about exactly the operations left unimplemented
+        let deprecated_attr = match kind {
+            OperationKind::Query | OperationKind::Mutation if warn_unimplemented => {
+                let note = format!(
+                    "{kind} `{}` has no `#[appsync_operation]` handler and always returns an `Unimplemented` error",
+                    self.name.orig()
+                );
+                quote_spanned! {span=>#[deprecated(note = #note)]}
+            }
+            _ => proc_macro2::TokenStream::new(),
+        };
+        let cfg_attr = self.cfg_attr();
         quote_spanned! {span=>
+            #cfg_attr
+            #deprecated_attr
             async fn #fct_name(_event: ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError> {
                 #default_body
             }
         }
     }
+    // Whether the operation is excluded from the concurrent batch processing, shadowed by the
+    // `appsync_operation` handlers marked `no_batch`
+    fn no_batch_default(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let no_batch_fct_name = format_ident!(
+            "{}_no_batch",
+            self.name.to_prefixed_fct_ident(kind.fct_prefix())
+        );
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            fn #no_batch_fct_name() -> bool {
+                false
+            }
+        }
+    }
+    fn no_batch_match_arm(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let operation_enum_name = kind.operation_enum_name(span);
+        let variant = self.name.to_type_ident();
+        let no_batch_fct_name = format_ident!(
+            "{}_no_batch",
+            self.name.to_prefixed_fct_ident(kind.fct_prefix())
+        );
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            #operation_enum_name::#variant => Operation::#no_batch_fct_name()
+        }
+    }
+    // Default operation calling the method of the `QueryResolvers` or `MutationResolvers` trait
+    fn resolver_op(&self, kind: OperationKind, resolvers: &syn::Path) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let fct_name = self.name.to_prefixed_fct_ident(kind.fct_prefix());
+        let method_name = self.name.to_var_ident();
+        let resolvers_trait = kind.resolvers_trait_name(span);
+        let op_module_type = proc_macro2::Ident::new(kind.module_name(), span);
+        let op_module_name = self.name.to_var_ident();
+        let arg_names = self
+            .args
+            .iter()
+            .map(|arg| arg.name.to_var_ident())
+            .collect::<Vec<_>>();
+        let return_type = &self.return_type;
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            async fn #fct_name(mut event: ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError> {
+                let (#(#arg_names,)*) = #op_module_type::#op_module_name::without_event::operation_arguments(&mut event)?;
+                <#resolvers as #resolvers_trait>::#method_name(__resolvers(), #(#arg_names),*).await
+            }
+        }
+    }
+    fn resolver_method(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let method_name = self.name.to_var_ident();
+        let args = self.args.iter().map(|arg| {
+            let name = arg.name.to_var_ident();
+            let field_type = &arg.field_type;
+            quote_spanned! {span=>#name: #field_type}
+        });
+        let return_type = &self.return_type;
+        let doc = format!(
+            "Resolves the `{}` operation, returns an `Unimplemented` error unless overridden",
+            self.name.orig()
+        );
+        let args_doc = self.args_doc();
+        let unimplemented_message = format!("{kind} `{}` is unimplemented", self.name.orig());
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            #[doc = #doc]
+            #args_doc
+            #[allow(unused_variables)]
+            fn #method_name(&self, #(#args),*) -> impl ::core::future::Future<Output = ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError>> + ::core::marker::Send {
+                async {
+                    ::core::result::Result::Err(::lambda_appsync::AppsyncError::new(
+                        "Unimplemented",
+                        #unimplemented_message,
+                    ))
+                }
+            }
+        }
+    }
+    fn lookup_match_arm(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let operation_enum_name = kind.operation_enum_name(span);
+        let kind_variant = proc_macro2::Ident::new(&kind.to_string(), span);
+        let kind_str = kind.to_string();
+        let field_str = self.name.orig();
+        let variant = self.name.to_type_ident();
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            (#kind_str, #field_str) => ::core::option::Option::Some(Operation::#kind_variant(#operation_enum_name::#variant))
+        }
+    }
+    fn as_str_match_arm(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let operation_enum_name = kind.operation_enum_name(span);
+        let variant = self.name.to_type_ident();
+        let name = format!("{kind}.{}", self.name.orig());
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            #operation_enum_name::#variant => #name
+        }
+    }
+    fn registry_entry(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let kind_variant = proc_macro2::Ident::new(&kind.to_string(), span);
+        let name = self.name.orig();
+        let arg_names = self.args.iter().map(|arg| arg.name.orig());
+        let arg_requireds = self.args.iter().map(|arg| !arg.field_type.is_optionnal());
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            ::lambda_appsync::registry::OperationMeta {
+                kind: ::lambda_appsync::registry::OperationKind::#kind_variant,
+                name: #name,
+                args: &[#(::lambda_appsync::registry::ArgMeta {
+                    name: #arg_names,
+                    required: #arg_requireds,
+                }),*],
+            }
+        }
+    }
     fn execute_match_arm(&self, kind: OperationKind) -> proc_macro2::TokenStream {
         let span = current_span();
         let operation_enum_name = kind.operation_enum_name(span);
         let variant = self.name.to_type_ident();
         let fct_name = self.name.to_prefixed_fct_ident(kind.fct_prefix());
+        let cfg_attr = self.cfg_attr();
+        // Bound with the span of the override, `OverrideToJson` reports an override type that is not
+        // `Serialize` there, with a single error
+        if let Some(override_span) = self.return_override {
+            let to_json = quote_spanned! {override_span=>
+                ::lambda_appsync::OverrideToJson::override_to_json
+            };
+            return quote_spanned! {span=>
+                #cfg_attr
+                #operation_enum_name::#variant => {
+                    let to_json = #to_json;
+                    ::core::result::Result::Ok(to_json(Operation::#fct_name(event).await?))
+                }
+            };
+        }
         quote_spanned! {span=>
+            #cfg_attr
             #operation_enum_name::#variant => Operation::#fct_name(event)
             .await
             .map(::lambda_appsync::res_to_json)
         }
     }
-    fn argument_extractor(&self, with_event: bool) -> proc_macro2::TokenStream {
+    fn argument_extractor(
+        &self,
+        kind: OperationKind,
+        with_event: bool,
+    ) -> proc_macro2::TokenStream {
         let span = current_span();
         let params_types = self.args.iter().map(|arg| &arg.field_type);
         let param_strs = self.args.iter().map(|arg| arg.name.orig());
+        let extract_fns = self.args.iter().map(|arg| {
+            if arg.field_type.is_nullable() {
+                quote! {::lambda_appsync::nullable_arg_from_json}
+            } else if arg.skip_null_elements {
+                quote! {::lambda_appsync::list_arg_from_json}
+            } else {
+                quote! {::lambda_appsync::arg_from_json}
+            }
+        });
 
         let return_type = if with_event {
             quote! {
@@ -503,11 +1502,11 @@ impl Operation {
         };
         let returned_tuple = if with_event {
             quote! {
-                (#(::lambda_appsync::arg_from_json(&mut args, #param_strs)?,)* event,)
+                (#(#extract_fns(&mut args, #param_strs)?,)* event,)
             }
         } else {
             quote! {
-                (#(::lambda_appsync::arg_from_json(&mut args, #param_strs)?,)*)
+                (#(#extract_fns(&mut args, #param_strs)?,)*)
             }
         };
 
@@ -520,11 +1519,128 @@ impl Operation {
                 let mut args = event.args.take();
             }
         };
+
+        // Same extraction, gathered in the arguments struct, see `args_struct`
+        let args_struct_path = self.args_struct_path(kind);
+        let field_names = self.args.iter().map(|arg| arg.name.to_var_ident());
+        let param_strs = self.args.iter().map(|arg| arg.name.orig());
+        let extract_fns = self.args.iter().map(|arg| {
+            if arg.field_type.is_nullable() {
+                quote! {::lambda_appsync::nullable_arg_from_json}
+            } else if arg.skip_null_elements {
+                quote! {::lambda_appsync::list_arg_from_json}
+            } else {
+                quote! {::lambda_appsync::arg_from_json}
+            }
+        });
+        let args_struct = quote! {
+            #args_struct_path {
+                #(#field_names: #extract_fns(&mut args, #param_strs)?,)*
+            }
+        };
+        let (args_struct_return_type, returned_args_struct) = if with_event {
+            (
+                quote! {(#args_struct_path, &::lambda_appsync::AppsyncEvent<Operation>,)},
+                quote! {(#args_struct, event,)},
+            )
+        } else {
+            (quote! {(#args_struct_path,)}, quote! {(#args_struct,)})
+        };
+        // With the event, `keep_raw_args` extracts from a clone of the arguments, leaving them in the event
+        let keep_raw_extractors = if with_event {
+            let extract_args = if self.args.is_empty() {
+                proc_macro2::TokenStream::new()
+            } else {
+                quote! {
+                    let mut args = ::core::clone::Clone::clone(&event.args);
+                }
+            };
+            quote_spanned! {span=>
+                #[allow(clippy::type_complexity)]
+                pub(crate) fn operation_arguments_keep_raw(event: &mut ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError> {
+                    #extract_args
+                     Ok(#returned_tuple)
+                }
+                pub(crate) fn operation_args_struct_keep_raw(event: &mut ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#args_struct_return_type, ::lambda_appsync::AppsyncError> {
+                    #extract_args
+                     Ok(#returned_args_struct)
+                }
+            }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
         quote_spanned! {span=>
+            #[allow(clippy::type_complexity)]
             pub(crate) fn operation_arguments(event: &mut ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError> {
                 #extract_args
                  Ok(#returned_tuple)
             }
+            pub(crate) fn operation_args_struct(event: &mut ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#args_struct_return_type, ::lambda_appsync::AppsyncError> {
+                #extract_args
+                 Ok(#returned_args_struct)
+            }
+            #keep_raw_extractors
+        }
+    }
+    fn args_struct_path(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let op_module_type = proc_macro2::Ident::new(kind.module_name(), span);
+        let args_struct_name = format_ident!("{}Args", self.name.to_type_ident());
+        quote_spanned! {span=>
+            operation_args::#op_module_type::#args_struct_name
+        }
+    }
+    // The `# Arguments` section of the doc of the resolver methods, listing the described arguments
+    fn args_doc(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let mut lines = vec![];
+        for arg in &self.args {
+            let Some(ref description) = arg.description else {
+                continue;
+            };
+            // Continuation lines are indented to stay in the list item
+            let mut description_lines = description.trim().lines().map(str::trim_end);
+            let first_line = description_lines.next().unwrap_or_default();
+            lines.push(format!(" * `{}` - {first_line}", arg.name.to_var_ident()));
+            lines.extend(description_lines.map(|line| format!("   {line}")));
+        }
+        if lines.is_empty() {
+            return proc_macro2::TokenStream::new();
+        }
+        quote_spanned! {span=>
+            #[doc = ""]
+            #[doc = " # Arguments"]
+            #(#[doc = #lines])*
+        }
+    }
+    // The `<Operation>Args` struct holding the arguments of the operation, see `args_struct`
+    fn args_struct(&self, kind: OperationKind) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let args_struct_name = format_ident!("{}Args", self.name.to_type_ident());
+        let fields = self.args.iter().map(|arg| {
+            let name = arg.name.to_var_ident();
+            let field_type = &arg.field_type;
+            let doc = match arg.description {
+                Some(ref description) => description_doc(description),
+                None => {
+                    let doc = format!("The `{}` argument", arg.name.orig());
+                    quote_spanned! {span=>#[doc = #doc]}
+                }
+            };
+            quote_spanned! {span=>
+                #doc
+                pub #name: #field_type
+            }
+        });
+        let doc = format!("Arguments of the {kind} `{}`", self.name.orig());
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            #[doc = #doc]
+            #[derive(Debug, Clone)]
+            pub struct #args_struct_name {
+                #(#fields,)*
+            }
         }
     }
     fn operation_module(&self, kind: OperationKind) -> proc_macro2::TokenStream {
@@ -534,46 +1650,156 @@ impl Operation {
             .iter()
             .map(|arg| &arg.field_type)
             .collect::<Vec<_>>();
-        let arument_extractor_without_event = self.argument_extractor(false);
-        let arument_extractor_with_event = self.argument_extractor(true);
-        let return_type = match kind {
+        let arument_extractor_without_event = self.argument_extractor(kind, false);
+        let arument_extractor_with_event = self.argument_extractor(kind, true);
+        // Subscriptions can return any `SubscriptionFilterResult`, inferred from the function signature
+        let (generics, return_type) = match kind {
             OperationKind::Query | OperationKind::Mutation => {
                 let return_type = &self.return_type;
-                quote_spanned! {return_type.span()=>
-                    ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError>
-                }
+                (
+                    proc_macro2::TokenStream::new(),
+                    quote_spanned! {return_type.span()=>
+                        ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError>
+                    },
+                )
             }
-            OperationKind::Subscription => quote_spanned! {current_span()=>
-                ::core::result::Result<::core::option::Option<::lambda_appsync::subscription_filters::FilterGroup>, ::lambda_appsync::AppsyncError>
-            },
+            OperationKind::Subscription => (
+                quote_spanned! {current_span()=>
+                    R: ::lambda_appsync::subscription_filters::SubscriptionFilterResult,
+                },
+                quote_spanned! {current_span()=>
+                    ::core::result::Result<R, ::lambda_appsync::AppsyncError>
+                },
+            ),
+        };
+        let args_struct_path = self.args_struct_path(kind);
+        // Queries and mutations marked `raw_return` may return a raw JSON value instead of the schema
+        // type, those marked `loose_return` any serializable type
+        let (return_checks_without_event, return_checks_with_event) = match kind {
+            OperationKind::Query | OperationKind::Mutation => {
+                let span = current_span();
+                let raw_return_type = quote_spanned! {span=>
+                    ::core::result::Result<::lambda_appsync::serde_json::Value, ::lambda_appsync::AppsyncError>
+                };
+                let loose_return_type = quote_spanned! {span=>
+                    ::core::result::Result<R, ::lambda_appsync::AppsyncError>
+                };
+                let loose_generics = quote_spanned! {span=>
+                    R: ::lambda_appsync::serde::Serialize,
+                };
+                (
+                    quote! {
+                        pub(crate) fn check_raw_signature<F: Fn(#(#params_types),*) -> #raw_return_type>(_f: F) {}
+                        pub(crate) fn check_raw_args_struct_signature<F: Fn(#args_struct_path) -> #raw_return_type>(_f: F) {}
+                        pub(crate) fn check_loose_signature<#loose_generics F: Fn(#(#params_types),*) -> #loose_return_type>(_f: F) {}
+                        pub(crate) fn check_loose_args_struct_signature<#loose_generics F: Fn(#args_struct_path) -> #loose_return_type>(_f: F) {}
+                    },
+                    quote! {
+                        pub(crate) fn check_raw_signature<F: Fn(#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>) -> #raw_return_type>(_f: F) {}
+                        pub(crate) fn check_raw_args_struct_signature<F: Fn(#args_struct_path, &::lambda_appsync::AppsyncEvent<Operation>) -> #raw_return_type>(_f: F) {}
+                        pub(crate) fn check_loose_signature<#loose_generics F: Fn(#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>) -> #loose_return_type>(_f: F) {}
+                        pub(crate) fn check_loose_args_struct_signature<#loose_generics F: Fn(#args_struct_path, &::lambda_appsync::AppsyncEvent<Operation>) -> #loose_return_type>(_f: F) {}
+                    },
+                )
+            }
+            OperationKind::Subscription => (
+                proc_macro2::TokenStream::new(),
+                proc_macro2::TokenStream::new(),
+            ),
         };
 
+        let cfg_attr = self.cfg_attr();
+        let serde_checks = serde_checks_to_tokens(&self.serde_checks);
         quote! {
+            #cfg_attr
             pub(crate) mod #module_name {
                 pub(crate) mod without_event {
                     use super::super::super::*;
-                    pub(crate) fn check_signature<F: Fn(#(#params_types),*) -> #return_type>(_f: F) {}
+                    pub(crate) fn check_signature<#generics F: Fn(#(#params_types),*) -> #return_type>(_f: F) {}
+                    pub(crate) fn check_args_struct_signature<#generics F: Fn(#args_struct_path) -> #return_type>(_f: F) {}
+                    #return_checks_without_event
                     #arument_extractor_without_event
+                    #serde_checks
                 }
                 pub(crate) mod with_event {
                     use super::super::super::*;
-                    pub(crate) fn check_signature<F: Fn(#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>) -> #return_type>(_f: F) {}
+                    pub(crate) fn check_signature<#generics F: Fn(#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>) -> #return_type>(_f: F) {}
+                    pub(crate) fn check_args_struct_signature<#generics F: Fn(#args_struct_path, &::lambda_appsync::AppsyncEvent<Operation>) -> #return_type>(_f: F) {}
+                    #return_checks_with_event
                     #arument_extractor_with_event
                 }
             }
         }
     }
+    fn subscription_fields_module(
+        &self,
+        structures: &[Structure],
+        enums: &[Enum],
+    ) -> proc_macro2::TokenStream {
+        let FieldType::Custom { name: type_name } = self.return_type.non_optionnal() else {
+            return proc_macro2::TokenStream::new();
+        };
+        let Some(structure) = structures
+            .iter()
+            .find(|structure| structure.name.orig() == type_name.orig())
+        else {
+            return proc_macro2::TokenStream::new();
+        };
+        let span = current_span();
+        let field_fns = structure.fields.iter().filter_map(|field| {
+            let filter_type = field.field_type.filter_type(enums)?;
+            let fct_name = field.name.to_var_ident();
+            let path = field.name.orig();
+            let doc = format!("Filter on the `{path}` field of `{}`", type_name.orig());
+            Some(quote_spanned! {span=>
+                #[doc = #doc]
+                pub fn #fct_name() -> ::lambda_appsync::subscription_filters::TypedFieldPath<#filter_type> {
+                    ::lambda_appsync::subscription_filters::TypedFieldPath::new(#path)
+                        .expect("GraphQL field names are valid field paths")
+                }
+            })
+        });
+        let module_name = self.name.to_var_ident();
+        let doc = format!(
+            "Typed field paths to filter the `{}` subscription",
+            self.name.orig()
+        );
+        let payload_type = structure.name.to_type_ident();
+        let payload_doc = format!(
+            "The `{}` type published to the subscribers of `{}`",
+            type_name.orig(),
+            self.name.orig()
+        );
+        let field_names = structure.fields.iter().map(|field| field.name.orig());
+        let cfg_attr = self.cfg_attr();
+        quote_spanned! {span=>
+            #cfg_attr
+            #[doc = #doc]
+            pub mod #module_name {
+                #[allow(unused_imports)]
+                use super::super::*;
+                #[doc = #payload_doc]
+                pub type PayloadType = #payload_type;
+                /// The GraphQL names of the fields of [PayloadType], in schema order
+                pub const FIELDS: &[&str] = &[#(#field_names),*];
+                #(#field_fns)*
+            }
+        }
+    }
     fn apply_type_overrides(
         &mut self,
         (field_type_override, mut arg_type_overrides): super::FieldTypeOverride,
     ) -> Result<(), syn::Error> {
         let mut errors = vec![];
         if let Some(field_type_override) = field_type_override {
+            self.return_override = Some(field_type_override.span());
             self.return_type.override_type(field_type_override);
         }
         for arg in self.args.iter_mut() {
             let arg_name = arg.name.orig();
             if let Some(arg_type_override) = arg_type_overrides.remove(arg_name) {
+                self.serde_checks
+                    .push(arg_type_override.serde_check("deserialize_override"));
                 arg.field_type.override_type(arg_type_override);
             }
         }
@@ -614,6 +1840,9 @@ impl From<graphql_parser::schema::Field<'_, String>> for Operation {
             name,
             args,
             return_type,
+            cfg: None,
+            serde_checks: vec![],
+            return_override: None,
         }
     }
 }
@@ -626,14 +1855,99 @@ impl From<graphql_parser::schema::ObjectType<'_, String>> for Operations {
     }
 }
 impl Operations {
-    fn variants_iter(&self) -> impl Iterator<Item = proc_macro2::Ident> + '_ {
+    // Two operations whose names only differ by their casing (e.g. `getPlayer` and `get_player`)
+    // would generate the same variant and the same `Operation` method
+    fn check_collisions(&self, kind: OperationKind) -> Result<(), syn::Error> {
+        let mut seen = HashMap::new();
+        let mut errors = vec![];
+        for op in self.0.iter() {
+            let fct_name = op.name.to_prefixed_fct_ident(kind.fct_prefix()).to_string();
+            if let Some(other) = seen.insert(fct_name.clone(), op.name.orig()) {
+                errors.push(syn::Error::new(
+                    current_span(),
+                    format!(
+                        "{kind} operations `{other}` and `{}` would both generate the method `Operation::{fct_name}`",
+                        op.name.orig()
+                    ),
+                ));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors
+                .into_iter()
+                .reduce(|mut acc, e| {
+                    acc.combine(e);
+                    acc
+                })
+                .expect("not empty"))
+        }
+    }
+    fn make_list_elements_non_null(&mut self, skip_nulls: bool) {
+        for op in self.0.iter_mut() {
+            op.return_type.make_list_elements_non_null();
+            for arg in op.args.iter_mut() {
+                arg.make_list_elements_non_null(skip_nulls);
+            }
+        }
+    }
+    fn set_namespace(&mut self, namespace: &str) {
+        for op in self.0.iter_mut() {
+            op.return_type.set_namespace(namespace);
+            for arg in op.args.iter_mut() {
+                arg.field_type.set_namespace(namespace);
+            }
+        }
+    }
+    fn variants_iter(&self) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
         self.0.iter().map(Operation::variant)
     }
-    fn default_op_iter(
+    fn default_op_iter<'a>(
+        &'a self,
+        kind: OperationKind,
+        resolvers: Option<&'a syn::Path>,
+        warn_unimplemented: bool,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + 'a {
+        self.0
+            .iter()
+            .map(move |op| op.default_op(kind, resolvers, warn_unimplemented))
+    }
+    fn resolver_method_iter(
+        &self,
+        kind: OperationKind,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.0.iter().map(move |op| op.resolver_method(kind))
+    }
+    fn lookup_match_arm_iter(
+        &self,
+        kind: OperationKind,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.0.iter().map(move |op| op.lookup_match_arm(kind))
+    }
+    fn no_batch_default_iter(
+        &self,
+        kind: OperationKind,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.0.iter().map(move |op| op.no_batch_default(kind))
+    }
+    fn no_batch_match_arm_iter(
+        &self,
+        kind: OperationKind,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.0.iter().map(move |op| op.no_batch_match_arm(kind))
+    }
+    fn as_str_match_arm_iter(
+        &self,
+        kind: OperationKind,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.0.iter().map(move |op| op.as_str_match_arm(kind))
+    }
+    fn registry_entry_iter(
         &self,
         kind: OperationKind,
     ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
-        self.0.iter().map(move |op| op.default_op(kind))
+        self.0.iter().map(move |op| op.registry_entry(kind))
     }
     fn execute_match_arm_iter(
         &self,
@@ -647,6 +1961,33 @@ impl Operations {
     ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
         self.0.iter().map(move |op| op.operation_module(kind))
     }
+    // Operations returning an interface, a union or a custom scalar (without `type_override`) would
+    // refer to a Rust type that is not generated
+    fn unsupported_return_types<'a>(
+        &'a self,
+        kind: OperationKind,
+        unsupported_types: &'a HashMap<String, &'static str>,
+        span: proc_macro2::Span,
+    ) -> impl Iterator<Item = syn::Error> + 'a {
+        self.0.iter().filter_map(move |op| {
+            let type_name = op.return_type.custom_name()?.orig();
+            let type_kind = unsupported_types.get(type_name)?;
+            let op_name = op.name.orig();
+            Some(syn::Error::new(
+                span,
+                format!(
+                    "`{kind}.{op_name}` returns the {type_kind} `{type_name}`, {type_kind}s are not supported yet, \
+                     use `type_override = {kind}.{op_name}: CustomType` to return a custom type"
+                ),
+            ))
+        })
+    }
+    fn args_struct_iter(
+        &self,
+        kind: OperationKind,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.0.iter().map(move |op| op.args_struct(kind))
+    }
     fn apply_type_overrides(
         &mut self,
         mut type_overrides: super::FieldTypeOverrides,
@@ -686,6 +2027,90 @@ impl Operations {
                 .expect("not empty"))
         }
     }
+    fn apply_nullable_args(
+        &mut self,
+        mut nullable_args: super::FieldNullableArgs,
+    ) -> Result<(), syn::Error> {
+        let mut errors = vec![];
+        for op in self.0.iter_mut() {
+            let Some(mut arg_nullable_args) = nullable_args.remove(op.name.orig()) else {
+                continue;
+            };
+            for arg in op.args.iter_mut() {
+                let Some(na) = arg_nullable_args.remove(arg.name.orig()) else {
+                    continue;
+                };
+                if !arg.field_type.make_nullable() {
+                    errors.push(syn::Error::new(
+                        na.arg_name().span(),
+                        format!(
+                            "Argument `{}` of `{}.{}` is required, it can be neither absent nor null",
+                            na.arg_name(),
+                            na.type_name(),
+                            na.field_name(),
+                        ),
+                    ));
+                }
+            }
+            errors.extend(arg_nullable_args.into_values().map(|na| {
+                syn::Error::new(
+                    na.arg_name().span(),
+                    format!(
+                        "No argument `{}` in operation `{}.{}`",
+                        na.arg_name(),
+                        na.type_name(),
+                        na.field_name(),
+                    ),
+                )
+            }));
+        }
+        errors.extend(
+            nullable_args
+                .into_values()
+                .flat_map(|anas| anas.into_values())
+                .map(|na| {
+                    syn::Error::new(
+                        na.field_name().span(),
+                        format!("No operation `{}` in `{}`", na.field_name(), na.type_name()),
+                    )
+                }),
+        );
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors
+                .into_iter()
+                .reduce(|mut acc, e| {
+                    acc.combine(e);
+                    acc
+                })
+                .expect("not empty"))
+        }
+    }
+    fn apply_cfgs(&mut self, mut cfgs: super::FieldOperationCfgs) -> Result<(), syn::Error> {
+        for op in self.0.iter_mut() {
+            if let Some(cfg) = cfgs.remove(op.name.orig()) {
+                op.cfg.replace(cfg.predicate());
+            }
+        }
+        if cfgs.is_empty() {
+            Ok(())
+        } else {
+            Err(cfgs
+                .into_values()
+                .map(|oc| {
+                    syn::Error::new(
+                        oc.field_name().span(),
+                        format!("No operation `{}` in `{}`", oc.field_name(), oc.type_name()),
+                    )
+                })
+                .reduce(|mut acc, e| {
+                    acc.combine(e);
+                    acc
+                })
+                .expect("not empty"))
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -732,25 +2157,237 @@ impl From<graphql_parser::schema::SchemaDefinition<'_, String>> for SchemaDefini
     }
 }
 
+// The field `field_name` of the type or input `type_name`
+fn find_field_mut<'a>(
+    structures: &'a mut [Structure],
+    type_name: &proc_macro2::Ident,
+    field_name: &proc_macro2::Ident,
+) -> Result<&'a mut Field, syn::Error> {
+    let structure = structures
+        .iter_mut()
+        .find(|s| type_name == s.name.orig())
+        .ok_or_else(|| {
+            syn::Error::new(
+                type_name.span(),
+                format!("No type or input named `{type_name}`"),
+            )
+        })?;
+    structure
+        .fields
+        .iter_mut()
+        .find(|f| field_name == f.name.orig())
+        .ok_or_else(|| {
+            syn::Error::new(
+                field_name.span(),
+                format!("No field `{field_name}` in `{type_name}`"),
+            )
+        })
+}
+
+// Makes a nullable field of a type or input non-optional in Rust, see `required_override`
+fn apply_required_override(
+    structures: &mut [Structure],
+    required_override: super::RequiredOverride,
+) -> Result<(), syn::Error> {
+    let type_name = required_override.type_name();
+    let field_name = required_override.field_name();
+    let field = find_field_mut(structures, type_name, field_name)?;
+    if !field.field_type.make_required() {
+        return Err(syn::Error::new(
+            field_name.span(),
+            format!("`{type_name}.{field_name}` is already required in the schema"),
+        ));
+    }
+    field.required_override = true;
+    field.default_if_null = required_override.default_if_null();
+    Ok(())
+}
+
+// Stops serializing a field of a type or input, see `skip_serialize`. A non-null field missing from a
+// response would fail the whole query on the AppSync side, so only optional fields are accepted
+fn apply_skip_serialize(
+    structures: &mut [Structure],
+    skip_serialize: super::SkipSerialize,
+) -> Result<(), syn::Error> {
+    let type_name = skip_serialize.type_name();
+    let field_name = skip_serialize.field_name();
+    let field = find_field_mut(structures, type_name, field_name)?;
+    let is_optionnal = match field.field_type {
+        FieldType::Overriden(_, ref schema_type) => schema_type.is_optionnal(),
+        ref field_type => field_type.is_optionnal(),
+    };
+    if !is_optionnal {
+        return Err(syn::Error::new(
+            field_name.span(),
+            format!(
+                "`skip_serialize` requires an optional field, `{type_name}.{field_name}` is not"
+            ),
+        ));
+    }
+    field.skip_serializing = true;
+    Ok(())
+}
+
+// A type renamed with `name_override` keeps its GraphQL name in the fields and operations using it:
+// give them the new name too. Explicit `type_override`s are not `Custom` anymore and are left untouched
+fn propagate_renamed_types<'a>(
+    renamed_types: &[proc_macro2::Ident],
+    structures: &mut [Structure],
+    enums: &[Enum],
+    operations: impl Iterator<Item = &'a mut Operations>,
+) {
+    let new_names = renamed_types
+        .iter()
+        .filter_map(|type_name| {
+            let new_name = structures
+                .iter()
+                .map(|s| &s.name)
+                .chain(enums.iter().map(|e| &e.name))
+                .find(|name| type_name == name.orig())?
+                .to_type_ident();
+            Some((type_name.to_string(), new_name.to_string()))
+        })
+        .collect::<HashMap<_, _>>();
+    if new_names.is_empty() {
+        return;
+    }
+    let fields = structures
+        .iter_mut()
+        .flat_map(|s| s.fields.iter_mut())
+        .map(|f| &mut f.field_type);
+    let operation_types = operations.flat_map(|ops| {
+        ops.0.iter_mut().flat_map(|op| {
+            std::iter::once(&mut op.return_type)
+                .chain(op.args.iter_mut().map(|arg| &mut arg.field_type))
+        })
+    });
+    for field_type in fields.chain(operation_types) {
+        field_type.rename_custom(&new_names);
+    }
+}
+
+// The `fields` argument of the first `@key` directive of an object type, see `federation_keys`
+fn key_directive_fields(
+    object_type: &graphql_parser::schema::ObjectType<'_, String>,
+    span: Span,
+) -> Result<Option<String>, syn::Error> {
+    let Some(directive) = object_type.directives.iter().find(|d| d.name == "key") else {
+        return Ok(None);
+    };
+    match directive
+        .arguments
+        .iter()
+        .find(|(name, _)| name == "fields")
+    {
+        Some((_, graphql_parser::schema::Value::String(fields))) => Ok(Some(fields.clone())),
+        _ => Err(syn::Error::new(
+            span,
+            format!(
+                "The `@key` directive of `{}` must have a `fields` string argument",
+                object_type.name
+            ),
+        )),
+    }
+}
+
+// Keeps the given types and the types they depend on, see `only_types`
+fn retain_types(
+    structures: &mut Vec<Structure>,
+    enums: &mut Vec<Enum>,
+    only_types: Vec<proc_macro2::Ident>,
+) -> Result<(), syn::Error> {
+    let is_known = |name: &str| {
+        structures.iter().any(|s| s.name.orig() == name)
+            || enums.iter().any(|e| e.name.orig() == name)
+    };
+    let mut to_visit = vec![];
+    let mut errors = vec![];
+    for ident in only_types {
+        let name = ident.to_string();
+        if is_known(&name) {
+            to_visit.push(name);
+        } else {
+            errors.push(syn::Error::new(
+                ident.span(),
+                format!("No type, enum or input named `{name}`"),
+            ));
+        }
+    }
+    if let Some(e) = errors.into_iter().reduce(|mut acc, e| {
+        acc.combine(e);
+        acc
+    }) {
+        return Err(e);
+    }
+
+    let mut selected = std::collections::HashSet::new();
+    while let Some(name) = to_visit.pop() {
+        if let Some(structure) = structures.iter().find(|s| s.name.orig() == name) {
+            to_visit.extend(
+                structure
+                    .fields
+                    .iter()
+                    .filter_map(|field| field.field_type.custom_name())
+                    .map(|name| name.orig().to_owned())
+                    .filter(|name| !selected.contains(name)),
+            );
+        }
+        selected.insert(name);
+    }
+    structures.retain(|s| selected.contains(s.name.orig()));
+    enums.retain(|e| selected.contains(e.name.orig()));
+    Ok(())
+}
+
 pub(crate) struct GraphQLSchema {
     queries: Operations,
     mutations: Operations,
     subscriptions: Operations,
     structures: Vec<Structure>,
     enums: Vec<Enum>,
+    on_error: Option<proc_macro2::Ident>,
+    middleware: Option<proc_macro2::Ident>,
+    operation_timeout_ms: Option<u64>,
+    resolvers: Option<syn::Path>,
+    warn_unimplemented: bool,
 }
 impl GraphQLSchema {
     pub(crate) fn new(
         mut doc: Document<'_, String>,
         span: proc_macro2::Span,
-        mut tos: super::TypeOverrides,
-        mut nos: super::NameOverrides,
+        options: &mut super::OptionalParameters,
     ) -> Result<Self, syn::Error> {
+        let mut tos = std::mem::take(&mut options.tos);
+        let mut nos = std::mem::take(&mut options.nos);
+        let mut ocs = std::mem::take(&mut options.ocs);
+        let mut nas = std::mem::take(&mut options.nas);
+        let field_case = options.field_case;
+        let namespace = options.namespace.take();
+        let on_error = options.on_error.take();
+        let middleware = options.middleware.take();
+        let operation_timeout_ms = options.operation_timeout_ms;
+        let strict_inputs = options.strict_inputs;
+        let only_types = options.only_types.take();
+        let resolvers = options.resolvers.take();
+        let warn_unimplemented = options.warn_unimplemented;
+        let federation_keys = options.federation_keys;
+        let generate_patch = std::mem::take(&mut options.generate_patch);
+        let required_overrides = std::mem::take(&mut options.required_overrides);
+        let skip_serializes = std::mem::take(&mut options.skip_serializes);
+        let serde_rename_alls = std::mem::take(&mut options.serde_rename_alls);
+        let enum_discriminants = options.enum_discriminants;
+        let arbitrary = options.arbitrary;
+        let emit_field_enums = options.emit_field_enums;
+        let lists_non_null = options.lists_non_null;
         let mut queries = None;
+        // The `Type` of the `name_override = Type: NewName` options
+        let mut renamed_types = vec![];
         let mut mutations = None;
         let mut subscriptions = None;
         let mut structures = vec![];
         let mut enums = vec![];
+        // The interfaces, unions and custom scalars, mapped to their kind, see `unsupported_return_types`
+        let mut unsupported_types = HashMap::new();
 
         CURRENT_SPAN.replace(span);
 
@@ -775,6 +2412,7 @@ impl GraphQLSchema {
                         TypeDefinition::Object(object_type) => {
                             if let Some(sdt) = sd.schema_definition(&object_type.name) {
                                 let type_overrides = tos.remove(&object_type.name);
+                                let object_type_name = object_type.name.clone();
                                 let mut ops = Operations::from(object_type);
                                 if let Some(type_overrides) = type_overrides {
                                     match ops.apply_type_overrides(type_overrides) {
@@ -782,6 +2420,22 @@ impl GraphQLSchema {
                                         Err(e) => errors.push(e),
                                     };
                                 }
+                                match ops.check_collisions(sdt) {
+                                    Ok(_) => (),
+                                    Err(e) => errors.push(e),
+                                };
+                                if let Some(cfgs) = ocs.remove(&object_type_name) {
+                                    match ops.apply_cfgs(cfgs) {
+                                        Ok(_) => (),
+                                        Err(e) => errors.push(e),
+                                    };
+                                }
+                                if let Some(nullable_args) = nas.remove(&object_type_name) {
+                                    match ops.apply_nullable_args(nullable_args) {
+                                        Ok(_) => (),
+                                        Err(e) => errors.push(e),
+                                    };
+                                }
                                 match sdt {
                                     OperationKind::Query => {
                                         queries.replace(ops);
@@ -794,7 +2448,21 @@ impl GraphQLSchema {
                                     }
                                 }
                             } else {
+                                let key_fields = if federation_keys {
+                                    key_directive_fields(&object_type, span)
+                                } else {
+                                    Ok(None)
+                                };
                                 let mut structure = Structure::from(object_type);
+                                match key_fields {
+                                    Ok(Some(key_fields)) => {
+                                        if let Err(e) = structure.set_key(&key_fields, span) {
+                                            errors.push(e);
+                                        }
+                                    }
+                                    Ok(None) => (),
+                                    Err(e) => errors.push(e),
+                                }
                                 if let Some(type_overrides) = tos.remove(structure.name.orig()) {
                                     match structure.apply_type_overrides(type_overrides) {
                                         Ok(_) => (),
@@ -802,17 +2470,25 @@ impl GraphQLSchema {
                                     };
                                 }
                                 if let Some(name_overrides) = nos.remove(structure.name.orig()) {
+                                    if let Some(ref type_override) = name_overrides.0 {
+                                        renamed_types.push(type_override.type_name().clone());
+                                    }
                                     match structure.apply_name_overrides(name_overrides) {
                                         Ok(_) => (),
                                         Err(e) => errors.push(e),
                                     };
                                 }
+                                structure.field_case = field_case;
                                 structures.push(structure);
                             }
                         }
                         TypeDefinition::Enum(enum_type) => {
                             let mut r_enum = Enum::from(enum_type);
+                            r_enum.discriminants = enum_discriminants;
                             if let Some(name_overrides) = nos.remove(r_enum.name.orig()) {
+                                if let Some(ref type_override) = name_overrides.0 {
+                                    renamed_types.push(type_override.type_name().clone());
+                                }
                                 match r_enum.apply_name_overrides(name_overrides) {
                                     Ok(_) => (),
                                     Err(e) => errors.push(e),
@@ -829,17 +2505,28 @@ impl GraphQLSchema {
                                 };
                             }
                             if let Some(name_overrides) = nos.remove(structure.name.orig()) {
+                                if let Some(ref type_override) = name_overrides.0 {
+                                    renamed_types.push(type_override.type_name().clone());
+                                }
                                 match structure.apply_name_overrides(name_overrides) {
                                     Ok(_) => (),
                                     Err(e) => errors.push(e),
                                 };
                             }
+                            structure.field_case = field_case;
+                            structure.deny_unknown_fields = strict_inputs;
                             structures.push(structure);
                         }
-                        // Not yet implemented, ignored for now
-                        TypeDefinition::Scalar(_) => {}
-                        TypeDefinition::Interface(_) => (),
-                        TypeDefinition::Union(_) => (),
+                        // Not yet implemented, operations cannot return them for now
+                        TypeDefinition::Scalar(scalar_type) => {
+                            unsupported_types.insert(scalar_type.name, "custom scalar");
+                        }
+                        TypeDefinition::Interface(interface_type) => {
+                            unsupported_types.insert(interface_type.name, "interface");
+                        }
+                        TypeDefinition::Union(union_type) => {
+                            unsupported_types.insert(union_type.name, "union");
+                        }
                     }
                 }
                 // Already processed
@@ -880,13 +2567,128 @@ impl GraphQLSchema {
                     }),
             );
         }
+        if !ocs.is_empty() {
+            errors.extend(
+                ocs.into_values()
+                    .flat_map(|cfgs| cfgs.into_values())
+                    .map(|oc| {
+                        syn::Error::new(
+                            oc.type_name().span(),
+                            format!("No operation type named `{}`", oc.type_name()),
+                        )
+                    }),
+            );
+        }
+        if !nas.is_empty() {
+            errors.extend(
+                nas.into_values()
+                    .flat_map(|fnas| fnas.into_values())
+                    .flat_map(|anas| anas.into_values())
+                    .map(|na| {
+                        syn::Error::new(
+                            na.type_name().span(),
+                            format!("No operation type named `{}`", na.type_name()),
+                        )
+                    }),
+            );
+        }
+        for (kind, ops) in [
+            (OperationKind::Query, &queries),
+            (OperationKind::Mutation, &mutations),
+        ] {
+            if let Some(ops) = ops {
+                errors.extend(ops.unsupported_return_types(kind, &unsupported_types, span));
+            }
+        }
+        propagate_renamed_types(
+            &renamed_types,
+            &mut structures,
+            &enums,
+            [&mut queries, &mut mutations, &mut subscriptions]
+                .into_iter()
+                .flatten(),
+        );
+        for required_override in required_overrides {
+            if let Err(e) = apply_required_override(&mut structures, required_override) {
+                errors.push(e);
+            }
+        }
+        for skip_serialize in skip_serializes {
+            if let Err(e) = apply_skip_serialize(&mut structures, skip_serialize) {
+                errors.push(e);
+            }
+        }
+        if let Some(skip_nulls) = lists_non_null {
+            for ops in [&mut queries, &mut mutations, &mut subscriptions]
+                .into_iter()
+                .flatten()
+            {
+                ops.make_list_elements_non_null(skip_nulls);
+            }
+            for field in structures.iter_mut().flat_map(|s| s.fields.iter_mut()) {
+                field.make_list_elements_non_null(skip_nulls);
+            }
+        }
+        for structure in structures.iter_mut() {
+            structure.arbitrary = arbitrary;
+            structure.field_enum = emit_field_enums;
+        }
+        for r_enum in enums.iter_mut() {
+            r_enum.arbitrary = arbitrary;
+        }
+        for patch_type in generate_patch {
+            match structures.iter_mut().find(|s| patch_type == s.name.orig()) {
+                Some(structure) => structure.patch = true,
+                None => errors.push(syn::Error::new(
+                    patch_type.span(),
+                    format!("No type or input named `{patch_type}`"),
+                )),
+            }
+        }
+        for serde_rename_all in serde_rename_alls {
+            let type_name = serde_rename_all.type_name();
+            match structures.iter_mut().find(|s| type_name == s.name.orig()) {
+                Some(structure) => structure.rename_all = Some(serde_rename_all.rule()),
+                None => errors.push(syn::Error::new(
+                    type_name.span(),
+                    format!("No type or input named `{type_name}`"),
+                )),
+            }
+        }
+        if let Some(only_types) = only_types {
+            if let Err(e) = retain_types(&mut structures, &mut enums, only_types) {
+                errors.push(e);
+            }
+        }
         if errors.is_empty() {
+            let mut queries = queries.unwrap_or_default();
+            let mut mutations = mutations.unwrap_or_default();
+            let mut subscriptions = subscriptions.unwrap_or_default();
+            if let Some(namespace) = namespace {
+                queries.set_namespace(&namespace);
+                mutations.set_namespace(&namespace);
+                subscriptions.set_namespace(&namespace);
+                for structure in structures.iter_mut() {
+                    structure.name.set_namespace(&namespace);
+                    for field in structure.fields.iter_mut() {
+                        field.field_type.set_namespace(&namespace);
+                    }
+                }
+                for r_enum in enums.iter_mut() {
+                    r_enum.name.set_namespace(&namespace);
+                }
+            }
             Ok(Self {
-                queries: queries.unwrap_or_default(),
-                mutations: mutations.unwrap_or_default(),
-                subscriptions: subscriptions.unwrap_or_default(),
+                queries,
+                mutations,
+                subscriptions,
                 structures,
                 enums,
+                on_error,
+                middleware,
+                operation_timeout_ms,
+                resolvers,
+                warn_unimplemented,
             })
         } else {
             Err(errors
@@ -898,6 +2700,69 @@ impl GraphQLSchema {
                 .expect("not empty"))
         }
     }
+    /// Reconstructs the GraphQL schema from what the macro parsed, see `emit_sdl`
+    ///
+    /// Only the operations, types, inputs and enums used by the generated code are written, with their
+    /// GraphQL names and types. Anything else of the input schema (directives, descriptions, unions,
+    /// interfaces, scalars declarations...) is left out.
+    pub(crate) fn to_sdl(&self) -> String {
+        let mut sdl = String::from(
+            "# Generated by lambda-appsync from the parsed GraphQL schema, do not edit manually.\n",
+        );
+        for (kind, operations) in [
+            (OperationKind::Query, &self.queries),
+            (OperationKind::Mutation, &self.mutations),
+            (OperationKind::Subscription, &self.subscriptions),
+        ] {
+            if operations.0.is_empty() {
+                continue;
+            }
+            sdl.push_str(&format!("\ntype {kind} {{\n"));
+            for op in operations.0.iter() {
+                let args = if op.args.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "({})",
+                        op.args
+                            .iter()
+                            .map(|arg| format!("{}: {}", arg.name.orig(), arg.field_type.to_sdl()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+                sdl.push_str(&format!(
+                    "  {}{args}: {}\n",
+                    op.name.orig(),
+                    op.return_type.to_sdl()
+                ));
+            }
+            sdl.push_str("}\n");
+        }
+        for structure in self.structures.iter() {
+            let keyword = if structure.input { "input" } else { "type" };
+            sdl.push_str(&format!("\n{keyword} {} {{\n", structure.name.orig()));
+            for field in structure.fields.iter() {
+                let field_sdl = field.field_type.to_sdl();
+                // Still nullable in the schema
+                let field_sdl = if field.required_override {
+                    field_sdl.trim_end_matches('!')
+                } else {
+                    &field_sdl
+                };
+                sdl.push_str(&format!("  {}: {field_sdl}\n", field.name.orig()));
+            }
+            sdl.push_str("}\n");
+        }
+        for r_enum in self.enums.iter() {
+            sdl.push_str(&format!("\nenum {} {{\n", r_enum.name.orig()));
+            for variant in r_enum.variants.iter() {
+                sdl.push_str(&format!("  {}\n", variant.orig()));
+            }
+            sdl.push_str("}\n");
+        }
+        sdl
+    }
     fn enums_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let enums = self.enums.iter();
         let span = current_span();
@@ -920,44 +2785,193 @@ impl GraphQLSchema {
         let mutation_field_variants = self.mutations.variants_iter();
         let subscription_field_name = OperationKind::Subscription.operation_enum_name(span);
         let subscription_field_variants = self.subscriptions.variants_iter();
+        let query_lookup_match_arms = self.queries.lookup_match_arm_iter(OperationKind::Query);
+        let mutation_lookup_match_arms = self
+            .mutations
+            .lookup_match_arm_iter(OperationKind::Mutation);
+        let subscription_lookup_match_arms = self
+            .subscriptions
+            .lookup_match_arm_iter(OperationKind::Subscription);
+        let query_as_str_match_arms = self.queries.as_str_match_arm_iter(OperationKind::Query);
+        let mutation_as_str_match_arms = self
+            .mutations
+            .as_str_match_arm_iter(OperationKind::Mutation);
+        let subscription_as_str_match_arms = self
+            .subscriptions
+            .as_str_match_arm_iter(OperationKind::Subscription);
         tokens.extend(quote_spanned! {span=>
             #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
+            #[serde(crate = "::lambda_appsync::serde", rename_all = "camelCase")]
             pub enum #query_field_name {
                 #(#query_field_variants,)*
             }
             #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
+            #[serde(crate = "::lambda_appsync::serde", rename_all = "camelCase")]
             pub enum #mutation_field_name {
                 #(#mutation_field_variants,)*
             }
             #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
+            #[serde(crate = "::lambda_appsync::serde", rename_all = "camelCase")]
             pub enum #subscription_field_name {
                 #(#subscription_field_variants,)*
             }
-            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(tag = "parentTypeName", content = "fieldName")]
+            #[derive(Debug, Clone, Copy)]
             pub enum Operation {
                 Query(#query_field_name),
                 Mutation(#mutation_field_name),
                 Subscription(#subscription_field_name),
             }
+            impl Operation {
+                /// Returns the operation corresponding to the `parentTypeName` and `fieldName`
+                /// sent by AWS AppSync, if any
+                pub fn from_names(parent_type_name: &str, field_name: &str) -> ::core::option::Option<Self> {
+                    match (parent_type_name, field_name) {
+                        #(#query_lookup_match_arms,)*
+                        #(#mutation_lookup_match_arms,)*
+                        #(#subscription_lookup_match_arms,)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+                /// Returns the `parentTypeName.fieldName` of the operation with the names of the
+                /// schema, e.g. `"Mutation.createPlayer"`
+                pub const fn as_str(&self) -> &'static str {
+                    match *self {
+                        Operation::Query(query_field) => match query_field {
+                            #(#query_as_str_match_arms,)*
+                        },
+                        Operation::Mutation(mutation_field) => match mutation_field {
+                            #(#mutation_as_str_match_arms,)*
+                        },
+                        Operation::Subscription(subscription_field) => match subscription_field {
+                            #(#subscription_as_str_match_arms,)*
+                        },
+                    }
+                }
+                /// Returns `true` if the operation is a query
+                pub fn is_query(&self) -> bool {
+                    ::core::matches!(self, Self::Query(_))
+                }
+                /// Returns `true` if the operation is a mutation
+                pub fn is_mutation(&self) -> bool {
+                    ::core::matches!(self, Self::Mutation(_))
+                }
+                /// Returns `true` if the operation is a subscription
+                pub fn is_subscription(&self) -> bool {
+                    ::core::matches!(self, Self::Subscription(_))
+                }
+            }
+            impl ::core::convert::From<Operation> for &'static str {
+                fn from(operation: Operation) -> Self {
+                    operation.as_str()
+                }
+            }
+            // Hand-written instead of `#[serde(tag = "parentTypeName", content = "fieldName")]`:
+            // the derived version buffers the content and then matches the strings twice
+            // (once for the tag and once for the field enum), this one borrows the two names
+            // and goes straight to the variant in a single `match`. The variants are the integer
+            // ids of the operations: a separate name to id table would compare the same strings
+            // before the same dispatch, see the `hot_path` benchmark
+            impl<'de> ::lambda_appsync::serde::Deserialize<'de> for Operation {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                where
+                    D: ::lambda_appsync::serde::Deserializer<'de>,
+                {
+                    use ::lambda_appsync::serde::de::{Error, IgnoredAny, MapAccess, Visitor};
+
+                    const FIELDS: &[&str] = &["parentTypeName", "fieldName"];
+
+                    #[derive(::lambda_appsync::serde::Deserialize)]
+                    #[serde(crate = "::lambda_appsync::serde")]
+                    enum Key {
+                        #[serde(rename = "parentTypeName")]
+                        ParentTypeName,
+                        #[serde(rename = "fieldName")]
+                        FieldName,
+                        #[serde(other)]
+                        Other,
+                    }
+
+                    struct OperationVisitor;
+                    impl<'de> Visitor<'de> for OperationVisitor {
+                        type Value = Operation;
+
+                        fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                            f.write_str("an AppSync operation with `parentTypeName` and `fieldName`")
+                        }
+
+                        fn visit_map<A>(self, mut map: A) -> ::core::result::Result<Operation, A::Error>
+                        where
+                            A: MapAccess<'de>,
+                        {
+                            let mut parent_type_name: ::core::option::Option<::lambda_appsync::OperationName<'de>> = None;
+                            let mut field_name: ::core::option::Option<::lambda_appsync::OperationName<'de>> = None;
+                            while let ::core::option::Option::Some(key) = map.next_key::<Key>()? {
+                                match key {
+                                    Key::ParentTypeName => parent_type_name = Some(map.next_value()?),
+                                    Key::FieldName => field_name = Some(map.next_value()?),
+                                    Key::Other => {
+                                        map.next_value::<IgnoredAny>()?;
+                                    }
+                                }
+                            }
+                            let parent_type_name = parent_type_name.ok_or_else(|| A::Error::missing_field("parentTypeName"))?;
+                            let field_name = field_name.ok_or_else(|| A::Error::missing_field("fieldName"))?;
+                            let (parent_type_name, field_name) = (parent_type_name.as_str(), field_name.as_str());
+                            Operation::from_names(parent_type_name, field_name).ok_or_else(|| {
+                                A::Error::custom(format!("unknown operation `{parent_type_name}.{field_name}`"))
+                            })
+                        }
+                    }
+
+                    deserializer.deserialize_struct("Operation", FIELDS, OperationVisitor)
+                }
+            }
             use __operations::DefaultOperations;
             impl DefaultOperations for Operation {}
         });
     }
+    // rustc type checks each generated item on its own, so this layout is kept for incremental builds
     fn default_operations_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let query_field_default_ops = self.queries.default_op_iter(OperationKind::Query);
-        let mutation_field_default_ops = self.mutations.default_op_iter(OperationKind::Mutation);
-        let subscription_field_default_ops = self
+        let resolvers = self.resolvers.as_ref();
+        let query_field_default_ops =
+            self.queries
+                .default_op_iter(OperationKind::Query, resolvers, self.warn_unimplemented);
+        let mutation_field_default_ops = self.mutations.default_op_iter(
+            OperationKind::Mutation,
+            resolvers,
+            self.warn_unimplemented,
+        );
+        let subscription_field_default_ops = self.subscriptions.default_op_iter(
+            OperationKind::Subscription,
+            resolvers,
+            self.warn_unimplemented,
+        );
+        if let Some(resolvers) = resolvers {
+            tokens.extend(quote_spanned! {current_span()=>
+                // Built on first use, shared by all the invocations of the Lambda function. Lives in
+                // `__operations` with a reserved name so it cannot clash with the user items
+                fn __resolvers() -> &'static #resolvers {
+                    static RESOLVERS: ::std::sync::OnceLock<#resolvers> = ::std::sync::OnceLock::new();
+                    RESOLVERS.get_or_init(::core::default::Default::default)
+                }
+            });
+        }
+        let query_field_no_batch_defaults =
+            self.queries.no_batch_default_iter(OperationKind::Query);
+        let mutation_field_no_batch_defaults = self
+            .mutations
+            .no_batch_default_iter(OperationKind::Mutation);
+        let subscription_field_no_batch_defaults = self
             .subscriptions
-            .default_op_iter(OperationKind::Subscription);
+            .no_batch_default_iter(OperationKind::Subscription);
         tokens.extend(quote_spanned! {current_span()=>
             pub(super) trait DefaultOperations {
                 #(#query_field_default_ops)*
                 #(#mutation_field_default_ops)*
                 #(#subscription_field_default_ops)*
+                #(#query_field_no_batch_defaults)*
+                #(#mutation_field_no_batch_defaults)*
+                #(#subscription_field_no_batch_defaults)*
             }
         });
     }
@@ -971,6 +2985,14 @@ impl GraphQLSchema {
         let subscription_field_execute_match_arm = self
             .subscriptions
             .execute_match_arm_iter(OperationKind::Subscription);
+        let query_field_no_batch_match_arm =
+            self.queries.no_batch_match_arm_iter(OperationKind::Query);
+        let mutation_field_no_batch_match_arm = self
+            .mutations
+            .no_batch_match_arm_iter(OperationKind::Mutation);
+        let subscription_field_no_batch_match_arm = self
+            .subscriptions
+            .no_batch_match_arm_iter(OperationKind::Subscription);
 
         let span = current_span();
 
@@ -981,15 +3003,103 @@ impl GraphQLSchema {
             ::lambda_appsync::log::error!("{e}");
         });
 
+        // The bound of `call_on_error` checks the signature of the observer at the `on_error = ...` option
+        let call_on_error = match self.on_error {
+            Some(ref on_error) => quote_spanned! {on_error.span()=>
+                mod _check_sig {
+                    use super::Operation;
+                    use ::lambda_appsync::AppsyncError;
+                    #[inline(always)]
+                    pub(super) fn call_on_error<F: Fn(&AppsyncError, &Operation)>(on_error: F, e: &AppsyncError, operation: &Operation) {
+                        on_error(e, operation)
+                    }
+                }
+                _check_sig::call_on_error(#on_error, &e, &self);
+            },
+            None => proc_macro2::TokenStream::new(),
+        };
+
+        let execute = match self.operation_timeout_ms {
+            Some(timeout_ms) => {
+                let timeout_ms = proc_macro2::Literal::u64_suffixed(timeout_ms);
+                quote_spanned! {span=>
+                    match ::lambda_appsync::tokio::time::timeout(
+                        ::core::time::Duration::from_millis(#timeout_ms),
+                        self._execute(event),
+                    )
+                    .await
+                    {
+                        ::core::result::Result::Ok(result) => result,
+                        ::core::result::Result::Err(_) => ::core::result::Result::Err(
+                            ::lambda_appsync::AppsyncError::new(
+                                "Timeout",
+                                ::std::format!("`{}` timed out after {} ms", self.as_str(), #timeout_ms),
+                            ),
+                        ),
+                    }
+                }
+            }
+            None => quote_spanned! {span=>
+                self._execute(event).await
+            },
+        };
+
+        // With a middleware, `execute` calls it with the continuation executing the operation
+        let (execute_fct_name, call_middleware) = match self.middleware {
+            Some(ref middleware) => (
+                format_ident!("_execute_response"),
+                quote_spanned! {middleware.span()=>
+                    async fn execute(self,
+                        event: ::lambda_appsync::AppsyncEvent<Self>
+                    ) -> ::lambda_appsync::AppsyncResponse {
+                        mod _check_sig {
+                            use super::Operation;
+                            use ::lambda_appsync::{middleware::Next, AppsyncEvent, AppsyncResponse};
+                            use ::core::future::Future;
+                            #[inline(always)]
+                            pub(super) fn call_middleware<Fut, F>(middleware: F, event: AppsyncEvent<Operation>, next: Next<Operation>) -> Fut
+                            where
+                                Fut: Future<Output = AppsyncResponse>,
+                                F: Fn(AppsyncEvent<Operation>, Next<Operation>) -> Fut {
+                                middleware(event, next)
+                            }
+                        }
+                        let next = ::lambda_appsync::middleware::Next::new(|event: ::lambda_appsync::AppsyncEvent<Self>| {
+                            ::std::boxed::Box::pin(event.info.operation._execute_response(event))
+                        });
+                        _check_sig::call_middleware(#middleware, event, next).await
+                    }
+                },
+            ),
+            None => (format_ident!("execute"), proc_macro2::TokenStream::new()),
+        };
+
         tokens.extend(quote_spanned! {span=>
             impl Operation {
-                async fn execute(self,
+                /// Returns `true` if the `appsync_operation` handler of the operation is marked
+                /// `no_batch`, i.e. its events are not processed concurrently in a batch
+                pub fn is_no_batch(&self) -> bool {
+                    match *self {
+                        Operation::Query(query_field) => match query_field {
+                            #(#query_field_no_batch_match_arm,)*
+                        },
+                        Operation::Mutation(mutation_field) => match mutation_field {
+                            #(#mutation_field_no_batch_match_arm,)*
+                        },
+                        Operation::Subscription(subscription_field) => match subscription_field {
+                            #(#subscription_field_no_batch_match_arm,)*
+                        },
+                    }
+                }
+                #call_middleware
+                async fn #execute_fct_name(self,
                     event: ::lambda_appsync::AppsyncEvent<Self>
                 ) -> ::lambda_appsync::AppsyncResponse {
-                    match self._execute(event).await {
+                    match #execute {
                         ::core::result::Result::Ok(v) => v.into(),
                         ::core::result::Result::Err(e) => {
                             #log_lines
+                            #call_on_error
                             e.into()
                         }
                     }
@@ -1038,13 +3148,180 @@ impl GraphQLSchema {
                     #(#subscription_operation_module_iter)*
                 }
             }
+            // What the `appsync_operation` handlers use, named after the macro so that the error of
+            // a handler without `appsync_lambda_main!` tells what is missing
+            #[doc(hidden)]
+            mod __appsync_lambda_main {
+                pub(crate) use super::Operation;
+                pub(crate) use super::__operations::{mutations, queries, subscriptions};
+            }
+        });
+    }
+    fn operation_args_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let query_args_struct_iter = self.queries.args_struct_iter(OperationKind::Query);
+        let mutation_args_struct_iter = self.mutations.args_struct_iter(OperationKind::Mutation);
+        let subscription_args_struct_iter = self
+            .subscriptions
+            .args_struct_iter(OperationKind::Subscription);
+        tokens.extend(quote_spanned! {current_span()=>
+            /// The arguments of each operation gathered in a struct, for the operations using `args_struct`
+            pub mod operation_args {
+                /// Arguments of the queries
+                pub mod queries {
+                    #[allow(unused_imports)]
+                    use super::super::*;
+                    #(#query_args_struct_iter)*
+                }
+                /// Arguments of the mutations
+                pub mod mutations {
+                    #[allow(unused_imports)]
+                    use super::super::*;
+                    #(#mutation_args_struct_iter)*
+                }
+                /// Arguments of the subscriptions
+                pub mod subscriptions {
+                    #[allow(unused_imports)]
+                    use super::super::*;
+                    #(#subscription_args_struct_iter)*
+                }
+            }
+        });
+    }
+    pub(crate) fn operation_registry_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let query_registry_entries = self.queries.registry_entry_iter(OperationKind::Query);
+        let mutation_registry_entries = self.mutations.registry_entry_iter(OperationKind::Mutation);
+        let subscription_registry_entries = self
+            .subscriptions
+            .registry_entry_iter(OperationKind::Subscription);
+        tokens.extend(quote_spanned! {current_span()=>
+            /// Returns the description of every operation of the GraphQL schema
+            pub fn operation_registry() -> &'static [::lambda_appsync::registry::OperationMeta] {
+                static OPERATION_REGISTRY: &[::lambda_appsync::registry::OperationMeta] = &[
+                    #(#query_registry_entries,)*
+                    #(#mutation_registry_entries,)*
+                    #(#subscription_registry_entries,)*
+                ];
+                OPERATION_REGISTRY
+            }
+        });
+    }
+    pub(crate) fn type_name_map_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let names = self
+            .structures
+            .iter()
+            .map(|s| &s.name)
+            .chain(self.enums.iter().map(|e| &e.name));
+        let orig_names = names.clone().map(|name| name.orig());
+        let rust_names = names.map(|name| name.to_type_ident().to_string());
+        tokens.extend(quote_spanned! {current_span()=>
+            /// Maps the GraphQL name of each type, input and enum of the schema to the name of the generated Rust type
+            pub const TYPE_NAME_MAP: &[(&str, &str)] = &[#((#orig_names, #rust_names)),*];
+        });
+    }
+    // A `<type>_strategy()` function per enum, type and input, see `proptest`
+    pub(crate) fn strategies_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        for r_enum in self.enums.iter() {
+            let enum_name = r_enum.name.to_type_ident();
+            let strategy_fct = r_enum.name.to_strategy_fct_ident();
+            let doc = format!("Strategy generating the values of [{enum_name}]");
+            tokens.extend(quote_spanned! {span=>
+                #[doc = #doc]
+                pub fn #strategy_fct() -> ::lambda_appsync::proptest::strategy::BoxedStrategy<#enum_name> {
+                    use ::lambda_appsync::proptest::strategy::Strategy;
+                    ::lambda_appsync::proptest::sample::select(#enum_name::all().to_vec()).boxed()
+                }
+            });
+        }
+        for structure in self.structures.iter() {
+            let struct_name = structure.name.to_type_ident();
+            let strategy_fct = structure.name.to_strategy_fct_ident();
+            let strategy_with_depth_fct = format_ident!("{strategy_fct}_with_depth");
+            let field_names = structure
+                .fields
+                .iter()
+                .map(|field| field.name.to_field_ident(structure.field_case))
+                .collect::<Vec<_>>();
+            // Nested pairs, as tuples of strategies are only strategies up to 12 elements
+            let strategies = structure.fields.iter().rev().fold(
+                quote_spanned! {span=>::lambda_appsync::proptest::strategy::Just(())},
+                |strategies, field| {
+                    let strategy = field.field_type.strategy_expr(&self.enums);
+                    quote_spanned! {span=>(#strategy, #strategies)}
+                },
+            );
+            let pattern =
+                field_names
+                    .iter()
+                    .rev()
+                    .fold(quote_spanned! {span=>()}, |pattern, field_name| {
+                        quote_spanned! {span=>(#field_name, #pattern)}
+                    });
+            let doc = format!("Strategy generating the values of [{struct_name}]");
+            let doc_with_depth = format!(
+                "Strategy generating the values of [{struct_name}], with lists of types nested at most `depth` times"
+            );
+            tokens.extend(quote_spanned! {span=>
+                #[doc = #doc]
+                pub fn #strategy_fct() -> ::lambda_appsync::proptest::strategy::BoxedStrategy<#struct_name> {
+                    #strategy_with_depth_fct(2)
+                }
+                #[doc = #doc_with_depth]
+                #[allow(unused_variables)]
+                pub fn #strategy_with_depth_fct(
+                    depth: u32,
+                ) -> ::lambda_appsync::proptest::strategy::BoxedStrategy<#struct_name> {
+                    use ::lambda_appsync::proptest::strategy::Strategy;
+                    #strategies
+                        .prop_map(|#pattern| #struct_name { #(#field_names,)* })
+                        .boxed()
+                }
+            });
+        }
+    }
+    pub(crate) fn subscription_fields_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let subscription_field_modules = self
+            .subscriptions
+            .0
+            .iter()
+            .map(|op| op.subscription_fields_module(&self.structures, &self.enums));
+        tokens.extend(quote_spanned! {current_span()=>
+            /// Typed field paths of the subscription return types, to build
+            /// [FieldFilter](::lambda_appsync::subscription_filters::FieldFilter)s checked at compile-time
+            pub mod subscription_fields {
+                #(#subscription_field_modules)*
+            }
         });
     }
     pub(crate) fn appsync_types_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         self.enums_to_tokens(tokens);
         self.structs_to_tokens(tokens);
     }
+    fn resolver_traits_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Some(ref resolvers) = self.resolvers else {
+            return;
+        };
+        let span = current_span();
+        let query_resolvers = OperationKind::Query.resolvers_trait_name(span);
+        let query_methods = self.queries.resolver_method_iter(OperationKind::Query);
+        let mutation_resolvers = OperationKind::Mutation.resolvers_trait_name(span);
+        let mutation_methods = self.mutations.resolver_method_iter(OperationKind::Mutation);
+        let query_doc = format!("Resolvers of the queries, the queries without `appsync_operation` are dispatched to `{}`", resolvers.to_token_stream());
+        let mutation_doc = format!("Resolvers of the mutations, the mutations without `appsync_operation` are dispatched to `{}`", resolvers.to_token_stream());
+        tokens.extend(quote_spanned! {span=>
+            #[doc = #query_doc]
+            pub trait #query_resolvers {
+                #(#query_methods)*
+            }
+            #[doc = #mutation_doc]
+            pub trait #mutation_resolvers {
+                #(#mutation_methods)*
+            }
+        });
+    }
     pub(crate) fn appsync_operations_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.resolver_traits_to_tokens(tokens);
+        self.operation_args_to_tokens(tokens);
         self.operations_module_to_tokens(tokens);
         self.operation_to_tokens(tokens);
         self.impl_operation_to_tokens(tokens);
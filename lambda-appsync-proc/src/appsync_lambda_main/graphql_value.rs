@@ -0,0 +1,152 @@
+use graphql_parser::schema::Value;
+use quote::quote;
+
+/// Converts a GraphQL value of the schema, such as the default value of an argument or an input
+/// field, into an expression building the equivalent `serde_json::Value`
+///
+/// Enum values become strings, as serialized by the generated enums. Variables and non-finite floats,
+/// which the schema parser rejects anyway, expand to a `compile_error!`.
+// Only called by the tests until the default values of arguments and input fields are wired into the
+// code generation, which will remove this `allow`
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) fn graphql_value_to_tokens(value: &Value<'_, String>) -> proc_macro2::TokenStream {
+    match value {
+        Value::Variable(name) => {
+            let message = format!("Variables are not allowed in the schema, found `${name}`");
+            quote! {::core::compile_error!(#message)}
+        }
+        Value::Int(number) => {
+            let number = number.as_i64().expect("graphql_parser numbers are i64");
+            quote! {::lambda_appsync::serde_json::Value::from(#number)}
+        }
+        Value::Float(float) if float.is_finite() => {
+            quote! {::lambda_appsync::serde_json::Value::from(#float)}
+        }
+        Value::Float(float) => {
+            let message = format!("The float `{float}` cannot be represented in JSON");
+            quote! {::core::compile_error!(#message)}
+        }
+        Value::String(string) | Value::Enum(string) => {
+            quote! {::lambda_appsync::serde_json::Value::from(#string)}
+        }
+        Value::Boolean(boolean) => quote! {::lambda_appsync::serde_json::Value::Bool(#boolean)},
+        Value::Null => quote! {::lambda_appsync::serde_json::Value::Null},
+        Value::List(values) => {
+            let values = values.iter().map(graphql_value_to_tokens);
+            quote! {
+                ::lambda_appsync::serde_json::Value::Array(::std::vec![#(#values),*])
+            }
+        }
+        Value::Object(fields) => {
+            let names = fields.keys();
+            let values = fields.values().map(graphql_value_to_tokens);
+            quote! {
+                ::lambda_appsync::serde_json::Value::Object(
+                    [#((::std::string::String::from(#names), #values)),*].into_iter().collect()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphql_parser::schema::{parse_schema, Definition, TypeDefinition};
+
+    // The default value of the `value` argument of `Query.test(value: ... = <value>)`
+    fn default_value(value: &str) -> Value<'static, String> {
+        let schema = format!("type Query {{ test(value: Int = {value}): Int }}");
+        let document = parse_schema::<String>(&schema).unwrap().into_static();
+        let Some(Definition::TypeDefinition(TypeDefinition::Object(query))) =
+            document.definitions.into_iter().next()
+        else {
+            panic!("the schema has a single type");
+        };
+        query.fields[0].arguments[0]
+            .default_value
+            .clone()
+            .expect("the argument has a default value")
+    }
+
+    fn tokens(value: &str) -> String {
+        graphql_value_to_tokens(&default_value(value)).to_string()
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(
+            tokens("42"),
+            quote! {::lambda_appsync::serde_json::Value::from(42i64)}.to_string()
+        );
+        assert_eq!(
+            tokens("-1.5"),
+            quote! {::lambda_appsync::serde_json::Value::from(-1.5f64)}.to_string()
+        );
+        assert_eq!(
+            tokens(r#""Ferris""#),
+            quote! {::lambda_appsync::serde_json::Value::from("Ferris")}.to_string()
+        );
+        assert_eq!(
+            tokens("true"),
+            quote! {::lambda_appsync::serde_json::Value::Bool(true)}.to_string()
+        );
+        assert_eq!(
+            tokens("null"),
+            quote! {::lambda_appsync::serde_json::Value::Null}.to_string()
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        assert_eq!(
+            tokens("RUST"),
+            quote! {::lambda_appsync::serde_json::Value::from("RUST")}.to_string()
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        assert_eq!(
+            tokens("[1, null]"),
+            quote! {
+                ::lambda_appsync::serde_json::Value::Array(::std::vec![
+                    ::lambda_appsync::serde_json::Value::from(1i64),
+                    ::lambda_appsync::serde_json::Value::Null
+                ])
+            }
+            .to_string()
+        );
+        assert_eq!(
+            tokens("[]"),
+            quote! {::lambda_appsync::serde_json::Value::Array(::std::vec![])}.to_string()
+        );
+    }
+
+    #[test]
+    fn test_object() {
+        assert_eq!(
+            tokens(r#"{name: "Ferris", team: RUST}"#),
+            quote! {
+                ::lambda_appsync::serde_json::Value::Object(
+                    [
+                        (::std::string::String::from("name"), ::lambda_appsync::serde_json::Value::from("Ferris")),
+                        (::std::string::String::from("team"), ::lambda_appsync::serde_json::Value::from("RUST"))
+                    ]
+                    .into_iter()
+                    .collect()
+                )
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        // Rejected by the schema parser, but part of the `Value` type
+        let variable = graphql_value_to_tokens(&Value::Variable("value".to_owned()));
+        assert!(variable.to_string().contains("compile_error"));
+        let infinity = graphql_value_to_tokens(&Value::Float(f64::INFINITY));
+        assert!(infinity.to_string().contains("compile_error"));
+    }
+}
@@ -1,14 +1,22 @@
 mod graphql;
+mod graphql_value;
 mod overrides;
+mod typescript;
 
 use std::collections::HashMap;
 
+use crate::common::{FieldCase, Name};
 use graphql::GraphQLSchema;
-use overrides::{NameOverride, TypeOverride};
+use overrides::{
+    NameOverride, NullableArg, OperationCfg, RequiredOverride, SerdeRenameAll, SkipSerialize,
+    TypeOverride,
+};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
-use syn::{parenthesized, parse::Parse, parse_macro_input, LitBool, LitStr, Token, Type};
+use syn::{
+    bracketed, parenthesized, parse::Parse, parse_macro_input, LitBool, LitStr, Token, Type,
+};
 
 struct AWSClient {
     fct_identifier: Ident,
@@ -19,8 +27,30 @@ impl Parse for AWSClient {
         // Expected example:
         // dynamodb() -> aws_sdk_dynamodb::Client
         let fct_identifier = input.parse::<Ident>()?;
-        let _empty;
-        _ = parenthesized!(_empty in input);
+        if !input.peek(syn::token::Paren) {
+            return Err(syn::Error::new(
+                input.span(),
+                format!(
+                    "Expected `()` after the AWS client getter name, e.g. `{fct_identifier}() -> aws_sdk_dynamodb::Client`"
+                ),
+            ));
+        }
+        let args;
+        _ = parenthesized!(args in input);
+        if !args.is_empty() {
+            return Err(syn::Error::new(
+                args.span(),
+                format!("AWS client getter `{fct_identifier}` cannot take arguments"),
+            ));
+        }
+        if !input.peek(Token![->]) {
+            return Err(syn::Error::new(
+                input.span(),
+                format!(
+                    "Expected `->` followed by the AWS SDK client type, e.g. `{fct_identifier}() -> aws_sdk_dynamodb::Client`"
+                ),
+            ));
+        }
         _ = input.parse::<Token![->]>()?;
         let client_type = input.parse::<syn::Type>()?;
         Ok(Self {
@@ -30,14 +60,25 @@ impl Parse for AWSClient {
     }
 }
 impl AWSClient {
+    // An identifier followed by parentheses or an arrow can only be an AWS client declaration,
+    // the parser then reports precisely what is malformed
     fn is_next(input: syn::parse::ParseStream) -> bool {
-        Self::parse(&input.fork()).is_ok()
+        input.peek(syn::Ident) && (input.peek2(syn::token::Paren) || input.peek2(Token![->]))
     }
     fn aws_config_getter() -> TokenStream2 {
         quote! {
             static AWS_SDK_CONFIG: ::std::sync::OnceLock<::lambda_appsync::aws_config::SdkConfig> = ::std::sync::OnceLock::new();
+            /// Returns the AWS SDK configuration loaded once when the Lambda starts
+            ///
+            /// Build any additional AWS SDK client from this configuration, and cache it, rather than
+            /// loading a new configuration in a resolver.
+            ///
+            /// # Panics
+            /// Panics if called before the Lambda runtime `main` initialized the configuration
             pub fn aws_sdk_config() -> &'static ::lambda_appsync::aws_config::SdkConfig {
-                AWS_SDK_CONFIG.get().unwrap()
+                AWS_SDK_CONFIG
+                    .get()
+                    .expect("aws_sdk_config() called before the AWS SDK configuration was loaded in main")
             }
         }
     }
@@ -51,7 +92,12 @@ impl AWSClient {
             fct_identifier,
             client_type,
         } = self;
+        let doc = format!(
+            "Returns the cached `{}`, built once from [aws_sdk_config]",
+            client_type.to_token_stream().to_string().replace(' ', "")
+        );
         quote! {
+            #[doc = #doc]
             pub fn #fct_identifier() -> #client_type {
                 static CLIENT: ::std::sync::OnceLock<#client_type> = ::std::sync::OnceLock::new();
                 CLIENT.get_or_init(||<#client_type>::new(aws_sdk_config())).clone()
@@ -63,18 +109,61 @@ impl AWSClient {
 // I suppose this is acceptable for a proc-macro
 enum OptionalParameter {
     Batch(bool),
+    Streaming(LitBool),
     ExcludeLambdaHandler(bool),
     OnlyLambdaHandler(bool),
     ExcludeAppsyncTypes(bool),
     OnlyAppsyncTypes(bool),
     ExcludeAppsyncOperations(bool),
     OnlyAppsyncOperations(bool),
+    OnlyTypes(Vec<Ident>),
+    ValidateSchema(bool),
     Hook(Ident),
+    PostHook(Ident),
+    OnError(Ident),
+    Middleware(Ident),
+    SerializeResponse(Ident),
+    EventAdapter(Ident),
+    HealthCheckOp(LitStr),
+    CorrelationHeader(LitStr),
+    Resolvers(syn::Path),
+    WarnUnimplemented(bool),
+    FederationKeys(bool),
+    GeneratePatch(Ident),
+    EnumDiscriminants(bool),
+    #[cfg(feature = "arbitrary")]
+    Arbitrary(bool),
+    #[cfg(feature = "proptest")]
+    Proptest(bool),
     LogInit(Ident),
+    LogFilter(LitStr),
+    LogColor(&'static str),
     #[cfg(feature = "log")]
     EventLogging(bool),
-    TypeOverride(TypeOverride),
+    EmitRegistry(bool),
+    TypedFilters(bool),
+    EmitTypeNameMap(bool),
+    EmitFieldEnums(bool),
+    ListsNonNull(Option<bool>),
+    EmitTypescript(LitStr),
+    EmitSdl(LitStr),
+    FromOutDir(bool),
+    OperationTimeoutMs(u64),
+    StrictInputs(bool),
+    CurrentThreadRuntime(bool),
+    CaptureRawEvent(bool),
+    EmfMetrics(bool),
+    ArgsSource(Option<&'static str>),
+    ArgsMayBeString(bool),
+    FieldCase(FieldCase),
+    Namespace(Ident),
+    TypeOverride(Box<TypeOverride>),
     NameOverride(NameOverride),
+    OperationCfg(OperationCfg),
+    NullableArg(NullableArg),
+    RequiredOverride(RequiredOverride),
+    SkipSerialize(SkipSerialize),
+    SerdeRenameAll(SerdeRenameAll),
 }
 impl Parse for OptionalParameter {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -82,6 +171,7 @@ impl Parse for OptionalParameter {
         _ = input.parse::<Token![=]>()?;
         match ident.to_string().as_str() {
             "batch" => Ok(Self::Batch(input.parse::<LitBool>()?.value())),
+            "streaming" => Ok(Self::Streaming(input.parse()?)),
             "exclude_lambda_handler" => Ok(Self::ExcludeLambdaHandler(
                 input.parse::<LitBool>()?.value(),
             )),
@@ -96,14 +186,130 @@ impl Parse for OptionalParameter {
             "only_appsync_operations" => Ok(Self::OnlyAppsyncOperations(
                 input.parse::<LitBool>()?.value(),
             )),
+            "only_types" => {
+                let types_input;
+                _ = bracketed!(types_input in input);
+                Ok(Self::OnlyTypes(
+                    types_input
+                        .parse_terminated(Ident::parse, Token![,])?
+                        .into_iter()
+                        .collect(),
+                ))
+            }
+            "validate_schema" => Ok(Self::ValidateSchema(input.parse::<LitBool>()?.value())),
             "hook" => Ok(Self::Hook(input.parse()?)),
+            "post_hook" => Ok(Self::PostHook(input.parse()?)),
+            "on_error" => Ok(Self::OnError(input.parse()?)),
+            "middleware" => Ok(Self::Middleware(input.parse()?)),
+            "serialize_response" => Ok(Self::SerializeResponse(input.parse()?)),
+            "event_adapter" => Ok(Self::EventAdapter(input.parse()?)),
+            "health_check_op" => Ok(Self::HealthCheckOp(input.parse()?)),
+            "correlation_header" => Ok(Self::CorrelationHeader(input.parse()?)),
+            "resolvers" => Ok(Self::Resolvers(input.parse()?)),
+            "warn_unimplemented" => Ok(Self::WarnUnimplemented(input.parse::<LitBool>()?.value())),
+            "federation_keys" => Ok(Self::FederationKeys(input.parse::<LitBool>()?.value())),
+            "generate_patch" => Ok(Self::GeneratePatch(input.parse()?)),
+            "enum_discriminants" => Ok(Self::EnumDiscriminants(input.parse::<LitBool>()?.value())),
+            #[cfg(feature = "arbitrary")]
+            "arbitrary" => Ok(Self::Arbitrary(input.parse::<LitBool>()?.value())),
+            #[cfg(feature = "proptest")]
+            "proptest" => Ok(Self::Proptest(input.parse::<LitBool>()?.value())),
             "log_init" => Ok(Self::LogInit(input.parse()?)),
+            "log_filter" => Ok(Self::LogFilter(input.parse()?)),
+            "log_color" => {
+                let color = input.parse::<Ident>()?;
+                match color.to_string().as_str() {
+                    "auto" => Ok(Self::LogColor("auto")),
+                    "always" => Ok(Self::LogColor("always")),
+                    "never" => Ok(Self::LogColor("never")),
+                    _ => Err(syn::Error::new(
+                        color.span(),
+                        format!("Expected one of `auto`, `always` or `never`, got `{color}`"),
+                    )),
+                }
+            }
             #[cfg(feature = "log")]
             "event_logging" => Ok(Self::EventLogging(input.parse::<LitBool>()?.value())),
-            "type_override" => Ok(Self::TypeOverride(input.parse()?)),
+            "field_case" => {
+                let case = input.parse::<Ident>()?;
+                match case.to_string().as_str() {
+                    "snake" => Ok(Self::FieldCase(FieldCase::Snake)),
+                    "preserve" => Ok(Self::FieldCase(FieldCase::Preserve)),
+                    _ => Err(syn::Error::new(
+                        case.span(),
+                        format!("Expected one of `snake` or `preserve`, got `{case}`"),
+                    )),
+                }
+            }
+            "runtime_flavor" => {
+                let flavor = input.parse::<Ident>()?;
+                match flavor.to_string().as_str() {
+                    "multi_thread" => Ok(Self::CurrentThreadRuntime(false)),
+                    "current_thread" => Ok(Self::CurrentThreadRuntime(true)),
+                    _ => Err(syn::Error::new(
+                        flavor.span(),
+                        format!(
+                            "Expected one of `multi_thread` or `current_thread`, got `{flavor}`"
+                        ),
+                    )),
+                }
+            }
+            "namespace" => Ok(Self::Namespace(input.parse()?)),
+            "capture_raw_event" => Ok(Self::CaptureRawEvent(input.parse::<LitBool>()?.value())),
+            "emf_metrics" => Ok(Self::EmfMetrics(input.parse::<LitBool>()?.value())),
+            "args_may_be_string" => Ok(Self::ArgsMayBeString(input.parse::<LitBool>()?.value())),
+            "args_source" => {
+                // The JSON pointer of the arguments in the event, `None` for the standard `arguments`
+                let source = input.parse::<Ident>()?;
+                match source.to_string().as_str() {
+                    "arguments" => Ok(Self::ArgsSource(None)),
+                    "stash" => Ok(Self::ArgsSource(Some("/stash"))),
+                    "prev_result" => Ok(Self::ArgsSource(Some("/prev/result"))),
+                    _ => Err(syn::Error::new(
+                        source.span(),
+                        format!(
+                            "Expected one of `arguments`, `stash` or `prev_result`, got `{source}`"
+                        ),
+                    )),
+                }
+            }
+            "emit_registry" => Ok(Self::EmitRegistry(input.parse::<LitBool>()?.value())),
+            "typed_filters" => Ok(Self::TypedFilters(input.parse::<LitBool>()?.value())),
+            "emit_type_name_map" => Ok(Self::EmitTypeNameMap(input.parse::<LitBool>()?.value())),
+            "emit_field_enums" => Ok(Self::EmitFieldEnums(input.parse::<LitBool>()?.value())),
+            "lists_non_null" => {
+                // `Some(skip_nulls)` when enabled, the null elements being rejected with `strict`
+                if input.peek(LitBool) {
+                    Ok(Self::ListsNonNull(
+                        input.parse::<LitBool>()?.value().then_some(true),
+                    ))
+                } else {
+                    let mode = input.parse::<Ident>()?;
+                    match mode.to_string().as_str() {
+                        "strict" => Ok(Self::ListsNonNull(Some(false))),
+                        _ => Err(syn::Error::new(
+                            mode.span(),
+                            format!("Expected `true`, `false` or `strict`, got `{mode}`"),
+                        )),
+                    }
+                }
+            }
+            "emit_typescript" => Ok(Self::EmitTypescript(input.parse()?)),
+            "emit_sdl" => Ok(Self::EmitSdl(input.parse()?)),
+            "from_out_dir" => Ok(Self::FromOutDir(input.parse::<LitBool>()?.value())),
+            "strict_inputs" => Ok(Self::StrictInputs(input.parse::<LitBool>()?.value())),
+            "operation_timeout_ms" => Ok(Self::OperationTimeoutMs(
+                input.parse::<syn::LitInt>()?.base10_parse()?,
+            )),
+            "type_override" => Ok(Self::TypeOverride(Box::new(input.parse()?))),
             "name_override" => Ok(Self::NameOverride(input.parse()?)),
+            "operation_cfg" => Ok(Self::OperationCfg(input.parse()?)),
+            "nullable_arg" => Ok(Self::NullableArg(input.parse()?)),
+            "required_override" => Ok(Self::RequiredOverride(input.parse()?)),
+            "skip_serialize" => Ok(Self::SkipSerialize(input.parse()?)),
+            "serde_rename_all" => Ok(Self::SerdeRenameAll(input.parse()?)),
             // Deprecated options
-            "field_type_override" => Ok(Self::TypeOverride(input.parse()?)),
+            "field_type_override" => Ok(Self::TypeOverride(Box::new(input.parse()?))),
             // Unknown option
             _ => Err(syn::Error::new(
                 ident.span(),
@@ -143,6 +349,23 @@ type TypeNameOverride = (Option<NameOverride>, FieldNameOverrides);
 // Maps field names to their name overrides for a field
 type FieldNameOverrides = HashMap<FieldName, NameOverride>;
 
+// Captures operation_cfg = OpType.operation: predicate options
+// using a HashMap hierarchy of TypeName -> FieldName -> cfg predicate
+type OperationCfgs = HashMap<TypeName, FieldOperationCfgs>;
+
+// For each operation type, maps operation names to their cfg predicate
+type FieldOperationCfgs = HashMap<FieldName, OperationCfg>;
+
+// Captures nullable_arg = OpType.operation.arg options
+// using a HashMap hierarchy of TypeName -> FieldName -> ArgName -> NullableArg
+type NullableArgs = HashMap<TypeName, FieldNullableArgs>;
+
+// For each operation type, maps operation names to their nullable arguments
+type FieldNullableArgs = HashMap<FieldName, ArgNullableArgs>;
+
+// For each operation, maps argument names to their nullable_arg option
+type ArgNullableArgs = HashMap<ArgName, NullableArg>;
+
 // [Type|Field|Arg]Name are just String
 type TypeName = String;
 type FieldName = String;
@@ -150,29 +373,111 @@ type ArgName = String;
 
 struct OptionalParameters {
     batch: bool,
+    // The span of the `true` literal, if streaming is enabled
+    streaming: Option<proc_macro2::Span>,
     appsync_types: bool,
     appsync_operations: bool,
     lambda_handler: bool,
+    only_types: Option<Vec<Ident>>,
     hook: Option<Ident>,
+    post_hook: Option<Ident>,
+    on_error: Option<Ident>,
+    middleware: Option<Ident>,
+    serialize_response: Option<Ident>,
+    event_adapter: Option<Ident>,
+    health_check_op: Option<LitStr>,
+    correlation_header: Option<LitStr>,
+    resolvers: Option<syn::Path>,
+    warn_unimplemented: bool,
+    federation_keys: bool,
+    generate_patch: Vec<Ident>,
+    enum_discriminants: bool,
+    arbitrary: bool,
+    proptest: bool,
     log_init: Option<Ident>,
+    log_filter: Option<LitStr>,
+    // The `env_logger` write style of the default log initialization, see `log_color`
+    log_color: &'static str,
     #[cfg(feature = "log")]
     event_logging: bool,
+    emit_registry: bool,
+    typed_filters: bool,
+    emit_type_name_map: bool,
+    emit_field_enums: bool,
+    lists_non_null: Option<bool>,
+    emit_typescript: Option<LitStr>,
+    emit_sdl: Option<LitStr>,
+    from_out_dir: bool,
+    operation_timeout_ms: Option<u64>,
+    strict_inputs: bool,
+    current_thread_runtime: bool,
+    capture_raw_event: bool,
+    emf_metrics: bool,
+    args_source: Option<&'static str>,
+    args_may_be_string: bool,
+    field_case: FieldCase,
+    namespace: Option<String>,
     tos: TypeOverrides,
     nos: NameOverrides,
+    ocs: OperationCfgs,
+    nas: NullableArgs,
+    required_overrides: Vec<RequiredOverride>,
+    skip_serializes: Vec<SkipSerialize>,
+    serde_rename_alls: Vec<SerdeRenameAll>,
 }
 impl Default for OptionalParameters {
     fn default() -> Self {
         Self {
             batch: true,
+            streaming: None,
             appsync_types: true,
             appsync_operations: true,
             lambda_handler: true,
+            only_types: None,
             hook: None,
+            post_hook: None,
+            on_error: None,
+            middleware: None,
+            serialize_response: None,
+            event_adapter: None,
+            health_check_op: None,
+            correlation_header: None,
+            resolvers: None,
+            warn_unimplemented: false,
+            federation_keys: false,
+            generate_patch: vec![],
+            enum_discriminants: false,
+            arbitrary: false,
+            proptest: false,
             log_init: None,
+            log_filter: None,
+            log_color: "never",
             #[cfg(feature = "log")]
             event_logging: false,
+            emit_registry: false,
+            typed_filters: false,
+            emit_type_name_map: false,
+            emit_field_enums: false,
+            lists_non_null: None,
+            emit_typescript: None,
+            emit_sdl: None,
+            from_out_dir: false,
+            operation_timeout_ms: None,
+            strict_inputs: false,
+            current_thread_runtime: false,
+            capture_raw_event: false,
+            emf_metrics: false,
+            args_source: None,
+            args_may_be_string: false,
+            field_case: FieldCase::default(),
+            namespace: None,
             tos: TypeOverrides::new(),
             nos: NameOverrides::new(),
+            ocs: OperationCfgs::new(),
+            nas: NullableArgs::new(),
+            required_overrides: vec![],
+            skip_serializes: vec![],
+            serde_rename_alls: vec![],
         }
     }
 }
@@ -180,6 +485,9 @@ impl OptionalParameters {
     fn set(&mut self, p: OptionalParameter) {
         match p {
             OptionalParameter::Batch(batch) => self.batch = batch,
+            OptionalParameter::Streaming(streaming) => {
+                self.streaming = streaming.value().then_some(streaming.span());
+            }
             OptionalParameter::ExcludeLambdaHandler(b) if b => self.lambda_handler = false,
             OptionalParameter::OnlyLambdaHandler(b) if b => {
                 self.lambda_handler = true;
@@ -198,16 +506,125 @@ impl OptionalParameters {
                 self.appsync_types = false;
                 self.appsync_operations = true;
             }
+            OptionalParameter::OnlyTypes(only_types) => {
+                self.lambda_handler = false;
+                self.appsync_types = true;
+                self.appsync_operations = false;
+                self.only_types.replace(only_types);
+            }
+            // The schema is still parsed and checked, but nothing is generated
+            OptionalParameter::ValidateSchema(b) if b => {
+                self.lambda_handler = false;
+                self.appsync_types = false;
+                self.appsync_operations = false;
+            }
             OptionalParameter::Hook(ident) => {
                 self.hook.replace(ident);
             }
+            OptionalParameter::PostHook(ident) => {
+                self.post_hook.replace(ident);
+            }
+            OptionalParameter::OnError(ident) => {
+                self.on_error.replace(ident);
+            }
+            OptionalParameter::Middleware(ident) => {
+                self.middleware.replace(ident);
+            }
+            OptionalParameter::SerializeResponse(ident) => {
+                self.serialize_response.replace(ident);
+            }
+            OptionalParameter::EventAdapter(ident) => {
+                self.event_adapter.replace(ident);
+            }
+            OptionalParameter::HealthCheckOp(op) => {
+                self.health_check_op.replace(op);
+            }
+            OptionalParameter::CorrelationHeader(header) => {
+                self.correlation_header.replace(header);
+            }
+            OptionalParameter::Resolvers(path) => {
+                self.resolvers.replace(path);
+            }
+            OptionalParameter::WarnUnimplemented(b) => {
+                self.warn_unimplemented = b;
+            }
+            OptionalParameter::FederationKeys(b) => {
+                self.federation_keys = b;
+            }
+            OptionalParameter::GeneratePatch(ident) => {
+                self.generate_patch.push(ident);
+            }
+            OptionalParameter::EnumDiscriminants(b) => {
+                self.enum_discriminants = b;
+            }
+            #[cfg(feature = "arbitrary")]
+            OptionalParameter::Arbitrary(b) => {
+                self.arbitrary = b;
+            }
+            #[cfg(feature = "proptest")]
+            OptionalParameter::Proptest(b) => {
+                self.proptest = b;
+            }
             OptionalParameter::LogInit(ident) => {
                 self.log_init.replace(ident);
             }
+            OptionalParameter::LogFilter(filter) => {
+                self.log_filter.replace(filter);
+            }
+            OptionalParameter::LogColor(color) => {
+                self.log_color = color;
+            }
             #[cfg(feature = "log")]
             OptionalParameter::EventLogging(b) => {
                 self.event_logging = b;
             }
+            OptionalParameter::FieldCase(field_case) => {
+                self.field_case = field_case;
+            }
+            OptionalParameter::Namespace(ident) => {
+                // `admin` => `Admin`, so that types are named `AdminPlayer`
+                let namespace = Name::from(ident.to_string()).to_type_ident().to_string();
+                self.namespace.replace(namespace);
+            }
+            OptionalParameter::CaptureRawEvent(b) => {
+                self.capture_raw_event = b;
+            }
+            OptionalParameter::EmfMetrics(b) => {
+                self.emf_metrics = b;
+            }
+            OptionalParameter::ArgsSource(pointer) => {
+                self.args_source = pointer;
+            }
+            OptionalParameter::ArgsMayBeString(b) => {
+                self.args_may_be_string = b;
+            }
+            OptionalParameter::EmitRegistry(b) => {
+                self.emit_registry = b;
+            }
+            OptionalParameter::TypedFilters(b) => {
+                self.typed_filters = b;
+            }
+            OptionalParameter::EmitTypeNameMap(b) => {
+                self.emit_type_name_map = b;
+            }
+            OptionalParameter::EmitFieldEnums(b) => {
+                self.emit_field_enums = b;
+            }
+            OptionalParameter::ListsNonNull(skip_nulls) => {
+                self.lists_non_null = skip_nulls;
+            }
+            OptionalParameter::EmitTypescript(path) => {
+                self.emit_typescript.replace(path);
+            }
+            OptionalParameter::EmitSdl(path) => {
+                self.emit_sdl.replace(path);
+            }
+            OptionalParameter::FromOutDir(b) => self.from_out_dir = b,
+            OptionalParameter::StrictInputs(b) => self.strict_inputs = b,
+            OptionalParameter::CurrentThreadRuntime(b) => self.current_thread_runtime = b,
+            OptionalParameter::OperationTimeoutMs(ms) => {
+                self.operation_timeout_ms.replace(ms);
+            }
             OptionalParameter::TypeOverride(to) => {
                 // Retrieve the entry corresponding to `Type.field`
                 let to_field_entry = self
@@ -219,11 +636,11 @@ impl OptionalParameters {
                 if let Some(arg_name) = to.arg_name() {
                     // There is a `.param`
                     // This is a parameter override
-                    to_field_entry.1.insert(arg_name.to_string(), to);
+                    to_field_entry.1.insert(arg_name.to_string(), *to);
                 } else {
                     // no `.param`
                     // This is just a field override
-                    to_field_entry.0.replace(to);
+                    to_field_entry.0.replace(*to);
                 }
             }
             OptionalParameter::NameOverride(no) => {
@@ -239,12 +656,30 @@ impl OptionalParameters {
                     no_type_entry.0.replace(no);
                 }
             }
+            OptionalParameter::OperationCfg(oc) => {
+                self.ocs
+                    .entry(oc.type_name().to_string())
+                    .or_default()
+                    .insert(oc.field_name().to_string(), oc);
+            }
+            OptionalParameter::NullableArg(na) => {
+                self.nas
+                    .entry(na.type_name().to_string())
+                    .or_default()
+                    .entry(na.field_name().to_string())
+                    .or_default()
+                    .insert(na.arg_name().to_string(), na);
+            }
+            OptionalParameter::RequiredOverride(ro) => self.required_overrides.push(ro),
+            OptionalParameter::SkipSerialize(ss) => self.skip_serializes.push(ss),
+            OptionalParameter::SerdeRenameAll(sra) => self.serde_rename_alls.push(sra),
             OptionalParameter::ExcludeLambdaHandler(_)
             | OptionalParameter::OnlyLambdaHandler(_)
             | OptionalParameter::ExcludeAppsyncTypes(_)
             | OptionalParameter::OnlyAppsyncTypes(_)
             | OptionalParameter::ExcludeAppsyncOperations(_)
-            | OptionalParameter::OnlyAppsyncOperations(_) => (),
+            | OptionalParameter::OnlyAppsyncOperations(_)
+            | OptionalParameter::ValidateSchema(_) => (),
         }
     }
 }
@@ -253,41 +688,13 @@ struct AppsyncLambdaMain {
     graphql_schema: GraphQLSchema,
     aws_clients: Vec<AWSClient>,
     options: OptionalParameters,
+    // Hash of the parsed schema, returned by the health check, see `health_check_op`
+    schema_hash: Option<String>,
 }
 
 impl Parse for AppsyncLambdaMain {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let graphql_schema_path = input.parse::<LitStr>()?;
-        let path_value = graphql_schema_path.value();
-        let full_path = if std::path::Path::new(&path_value).is_relative() {
-            std::env::current_dir()
-                .map_err(|e| {
-                    syn::Error::new(
-                        graphql_schema_path.span(),
-                        format!("Could not get current directory: {e}"),
-                    )
-                })?
-                .join(&path_value)
-        } else {
-            std::path::PathBuf::from(path_value)
-        };
-        let schema_str = std::fs::read_to_string(&full_path).map_err(|e| {
-            syn::Error::new(
-                graphql_schema_path.span(),
-                format!(
-                    "Could not open GraphQL schema file at '{}' ({e})",
-                    full_path.display()
-                ),
-            )
-        })?;
-        let schema = graphql_parser::parse_schema(&schema_str)
-            .map_err(|e| {
-                syn::Error::new(
-                    graphql_schema_path.span(),
-                    format!("Could not parse GraphQL schema file ({e})",),
-                )
-            })?
-            .into_static();
 
         let mut options = OptionalParameters::default();
         let mut aws_clients = vec![];
@@ -303,26 +710,227 @@ impl Parse for AppsyncLambdaMain {
             } else if AWSClient::is_next(input) {
                 aws_clients.push(input.parse::<AWSClient>()?);
             } else {
-                return Err(syn::Error::new(input.span(), "Unknown argument"));
+                return Err(syn::Error::new(
+                    input.span(),
+                    "Unknown argument, expected an option (`name = value`) or an AWS client declaration (`name() -> ClientType`)",
+                ));
             }
         }
 
-        let graphql_schema = GraphQLSchema::new(
-            schema,
-            graphql_schema_path.span(),
-            std::mem::take(&mut options.tos),
-            std::mem::take(&mut options.nos),
-        )?;
+        let full_path = resolve_path(&graphql_schema_path, options.from_out_dir)?;
+        let schema = load_schema(&full_path).map_err(|e| {
+            let hint = if options.from_out_dir {
+                ", make sure the build script writes it before the crate is compiled"
+            } else {
+                ""
+            };
+            syn::Error::new(graphql_schema_path.span(), format!("{e}{hint}"))
+        })?;
+
+        if let Some(span) = options.streaming {
+            if options.batch {
+                return Err(syn::Error::new(
+                    span,
+                    "`streaming = true` only supports single responses, it requires `batch = false`",
+                ));
+            }
+        }
+
+        if let Some(ref ts_path) = options.emit_typescript {
+            write_emitted_file(
+                ts_path,
+                typescript::schema_to_typescript(&schema),
+                "TypeScript declarations",
+            )?;
+        }
+
+        let schema_hash = options
+            .health_check_op
+            .as_ref()
+            .map(|_| schema_hash(&schema));
+
+        let graphql_schema = GraphQLSchema::new(schema, graphql_schema_path.span(), &mut options)?;
+
+        if let Some(ref sdl_path) = options.emit_sdl {
+            write_emitted_file(sdl_path, graphql_schema.to_sdl(), "GraphQL schema")?;
+        }
 
         Ok(Self {
             graphql_schema,
             aws_clients,
             options,
+            schema_hash,
         })
     }
 }
 
+// Resolves a path given to the macro: relative paths are resolved from the current directory (the
+// workspace root when building with cargo) or, with `from_out_dir`, from the `OUT_DIR` of the build script
+fn resolve_path(path: &LitStr, from_out_dir: bool) -> syn::Result<std::path::PathBuf> {
+    let path_value = path.value();
+    if std::path::Path::new(&path_value).is_absolute() {
+        return Ok(std::path::PathBuf::from(path_value));
+    }
+    let base = if from_out_dir {
+        std::env::var_os("OUT_DIR")
+            .map(std::path::PathBuf::from)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    path.span(),
+                    "`from_out_dir = true` requires a build script, `OUT_DIR` is not set",
+                )
+            })?
+    } else {
+        std::env::current_dir().map_err(|e| {
+            syn::Error::new(path.span(), format!("Could not get current directory: {e}"))
+        })?
+    };
+    Ok(base.join(path_value))
+}
+
+type SchemaDocument = graphql_parser::schema::Document<'static, String>;
+
+// Parsed schemas, keyed by absolute path and checked against the file modification time and size,
+// so that the macro invocations of a crate (or of a long-running rust-analyzer) parse a schema once
+static SCHEMA_CACHE: std::sync::Mutex<
+    std::collections::BTreeMap<std::path::PathBuf, (std::time::SystemTime, u64, SchemaDocument)>,
+> = std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+// Reads and parses the schema at `full_path`, reusing the cached document if the file did not change
+fn load_schema(full_path: &std::path::Path) -> Result<SchemaDocument, String> {
+    let open_error = |e| {
+        format!(
+            "Could not open GraphQL schema file at '{}' ({e})",
+            full_path.display()
+        )
+    };
+    let abs_path = full_path.canonicalize().map_err(open_error)?;
+    let metadata = std::fs::metadata(&abs_path).map_err(open_error)?;
+    let modified = metadata.modified().map_err(open_error)?;
+    let len = metadata.len();
+
+    let mut cache = SCHEMA_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some((cached_modified, cached_len, schema)) = cache.get(&abs_path) {
+        if *cached_modified == modified && *cached_len == len {
+            return Ok(schema.clone());
+        }
+    }
+
+    let schema_str = std::fs::read_to_string(&abs_path).map_err(open_error)?;
+    // Schemas saved on Windows may start with a UTF-8 BOM and use CRLF line endings
+    let schema_str = schema_str
+        .strip_prefix('\u{feff}')
+        .unwrap_or(&schema_str)
+        .replace("\r\n", "\n");
+    let schema = graphql_parser::parse_schema(&schema_str)
+        .map_err(|e| format!("Could not parse GraphQL schema file ({e})"))?
+        .into_static();
+    cache.insert(abs_path, (modified, len, schema.clone()));
+    Ok(schema)
+}
+
+// A FNV-1a hash of the parsed schema, stable across compilers unlike the `Hash` of std
+fn schema_hash(schema: &SchemaDocument) -> String {
+    let hash = schema
+        .to_string()
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{hash:016x}")
+}
+
+// Writes a file emitted by the macro (`emit_typescript`, `emit_sdl`), relative paths are resolved like
+// the schema path. The file is only written when its content changes, to avoid triggering file watchers
+// needlessly.
+fn write_emitted_file(path: &LitStr, content: String, description: &str) -> syn::Result<()> {
+    let full_path = resolve_path(path, false)?;
+    if std::fs::read_to_string(&full_path).is_ok_and(|current| current == content) {
+        return Ok(());
+    }
+    std::fs::write(&full_path, content).map_err(|e| {
+        syn::Error::new(
+            path.span(),
+            format!(
+                "Could not write {description} at '{}' ({e})",
+                full_path.display()
+            ),
+        )
+    })
+}
+
 impl AppsyncLambdaMain {
+    // The bounds of `call_hook` check the signature of the hook, errors point at the `hook = ...` option
+    fn hook_check_sig_module(hook: &Ident) -> TokenStream2 {
+        quote_spanned! {hook.span()=>
+            mod _check_sig {
+                use super::Operation;
+                use ::lambda_appsync::{AppsyncEvent, AppsyncResponse};
+                use ::core::future::Future;
+                #[inline(always)]
+                #[allow(dead_code)]
+                pub(super) async fn call_hook<'a, Fut, H>(hook: H, event: &'a AppsyncEvent<Operation>) -> Option<AppsyncResponse>
+                where
+                    Fut: Future<Output = Option<AppsyncResponse>>,
+                    H: Fn(&'a AppsyncEvent<Operation>) -> Fut {
+                    hook(event).await
+                }
+                #[allow(dead_code)]
+                pub(super) fn check_hook_signature<'a, Fut, H>(_hook: H)
+                where
+                    Fut: Future<Output = Option<AppsyncResponse>>,
+                    H: Fn(&'a AppsyncEvent<Operation>) -> Fut {}
+            }
+        }
+    }
+    // The bound of `call_post_hook` checks the signature of the post hook, errors point at the
+    // `post_hook = ...` option
+    fn post_hook_check_sig_module(post_hook: &Ident) -> TokenStream2 {
+        quote_spanned! {post_hook.span()=>
+            mod _check_sig {
+                use super::Operation;
+                use ::lambda_appsync::{AppsyncEventInfo, AppsyncIdentity, AppsyncResponse};
+                #[inline(always)]
+                #[allow(dead_code)]
+                pub(super) fn call_post_hook<F: Fn(&AppsyncEventInfo<Operation>, &AppsyncIdentity, AppsyncResponse) -> AppsyncResponse>(
+                    post_hook: F,
+                    info: &AppsyncEventInfo<Operation>,
+                    identity: &AppsyncIdentity,
+                    response: AppsyncResponse,
+                ) -> AppsyncResponse {
+                    post_hook(info, identity, response)
+                }
+                #[allow(dead_code)]
+                pub(super) fn check_post_hook_signature<F: Fn(&AppsyncEventInfo<Operation>, &AppsyncIdentity, AppsyncResponse) -> AppsyncResponse>(_post_hook: F) {}
+            }
+        }
+    }
+    // When the Lambda handler is not generated, the hooks are never called but their signatures are
+    // still checked, so that a mistake does not go unnoticed until the handler is generated elsewhere
+    fn hook_signature_check(&self, tokens: &mut TokenStream2) {
+        if let Some(ref hook) = self.options.hook {
+            let check_sig_module = Self::hook_check_sig_module(hook);
+            tokens.extend(quote_spanned! {hook.span()=>
+                const _: fn() = || {
+                    // Compile-time assertion only – never calls the hook.
+                    #check_sig_module
+                    _check_sig::check_hook_signature(#hook);
+                };
+            });
+        }
+        if let Some(ref post_hook) = self.options.post_hook {
+            let check_sig_module = Self::post_hook_check_sig_module(post_hook);
+            tokens.extend(quote_spanned! {post_hook.span()=>
+                const _: fn() = || {
+                    // Compile-time assertion only – never calls the post hook.
+                    #check_sig_module
+                    _check_sig::check_post_hook_signature(#post_hook);
+                };
+            });
+        }
+    }
     fn appsync_event_handler(&self, tokens: &mut TokenStream2) {
         #[allow(unused_mut)]
         let mut log_lines = proc_macro2::TokenStream::new();
@@ -338,19 +946,9 @@ impl AppsyncLambdaMain {
         });
 
         let call_hook = if let Some(ref hook) = self.options.hook {
+            let check_sig_module = Self::hook_check_sig_module(hook);
             quote_spanned! {hook.span()=>
-                mod _check_sig {
-                    use super::Operation;
-                    use ::lambda_appsync::{AppsyncEvent, AppsyncResponse};
-                    use ::core::future::Future;
-                    #[inline(always)]
-                    pub(super) async fn call_hook<'a, Fut, H>(hook: H, event: &'a AppsyncEvent<Operation>) -> Option<AppsyncResponse>
-                    where
-                        Fut: Future<Output = Option<AppsyncResponse>>,
-                        H: Fn(&'a AppsyncEvent<Operation>) -> Fut {
-                        hook(event).await
-                    }
-                }
+                #check_sig_module
                 if let Some(resp) = _check_sig::call_hook(#hook, &event).await{
                     return resp;
                 }
@@ -360,34 +958,126 @@ impl AppsyncLambdaMain {
         };
 
         #[cfg(feature = "tracing")]
-        tokens.extend(quote! {
-            #[::lambda_appsync::tracing::instrument(skip(event), fields(operation = ?event.info.operation))]
-        });
+        if let Some(ref header) = self.options.correlation_header {
+            tokens.extend(quote! {
+                #[::lambda_appsync::tracing::instrument(
+                    skip(event),
+                    fields(operation = ?event.info.operation, correlation_id = event.header(#header))
+                )]
+            });
+        } else {
+            tokens.extend(quote! {
+                #[::lambda_appsync::tracing::instrument(skip(event), fields(operation = ?event.info.operation))]
+            });
+        }
 
-        tokens.extend(quote! {
-            async fn appsync_handler(event: ::lambda_appsync::AppsyncEvent<Operation>) -> ::lambda_appsync::AppsyncResponse {
-                #log_lines
+        let handle_event = quote! {
+            #log_lines
+
+            #call_hook
+
+            event.info.operation.execute(event).await
+        };
+        // The operation consumes the event, the post hook gets a copy of its info and identity. The
+        // response of the hook goes through the post hook too, hence the `async` block for its early return
+        let handle_event = if let Some(ref post_hook) = self.options.post_hook {
+            let check_sig_module = Self::post_hook_check_sig_module(post_hook);
+            quote_spanned! {post_hook.span()=>
+                let post_hook_info = event.info.clone();
+                let post_hook_identity = event.identity.clone();
+                let response = async move {
+                    #handle_event
+                }
+                .await;
+                {
+                    #check_sig_module
+                    _check_sig::call_post_hook(#post_hook, &post_hook_info, &post_hook_identity, response)
+                }
+            }
+        } else {
+            handle_event
+        };
+        // Handles the event in the scope of its correlation id, see `lambda_appsync::correlation`
+        let handle_event = if let Some(ref header) = self.options.correlation_header {
+            quote! {
+                let correlation_id = event.header(#header).map(::std::borrow::ToOwned::to_owned);
+                ::lambda_appsync::correlation::scope(correlation_id, async move {
+                    #handle_event
+                })
+                .await
+            }
+        } else {
+            handle_event
+        };
+        // Prints the EMF record of the operation once handled, hook included, see `lambda_appsync::emf_metrics_record`
+        let handle_event = if self.options.emf_metrics {
+            quote! {
+                let emf_operation = event.info.operation;
+                let emf_start = ::std::time::Instant::now();
+                let response = async move {
+                    #handle_event
+                }
+                .await;
+                ::std::println!(
+                    "{}",
+                    ::lambda_appsync::emf_metrics_record(
+                        emf_operation.as_str(),
+                        response.error_type(),
+                        emf_start.elapsed(),
+                    )
+                );
+                response
+            }
+        } else {
+            handle_event
+        };
 
-                #call_hook
+        // Public with the `testing` feature, so that tests can call the handlers with typed events
+        #[cfg(feature = "testing")]
+        let vis = quote! {pub};
+        #[cfg(not(feature = "testing"))]
+        let vis = TokenStream2::new();
 
-                event.info.operation.execute(event).await
+        tokens.extend(quote! {
+            /// Handles an AppSync event, returning the response of its operation
+            #vis async fn appsync_handler(event: ::lambda_appsync::AppsyncEvent<Operation>) -> ::lambda_appsync::AppsyncResponse {
+                #handle_event
             }
         });
         if self.options.batch {
             tokens.extend(quote! {
-                async fn appsync_batch_handler(
+                /// Handles a batch of AppSync events concurrently, returning their responses in the same order
+                ///
+                /// The events of the operations marked `no_batch` are handled one after the other, while the
+                /// other events of the batch are handled concurrently
+                #vis async fn appsync_batch_handler(
                     events: Vec<::lambda_appsync::AppsyncEvent<Operation>>,
                 ) -> Vec<::lambda_appsync::AppsyncResponse> {
+                    let mut results = (0..events.len()).map(|_| None).collect::<Vec<_>>();
+                    let mut sequential = vec![];
                     let handles = events
                         .into_iter()
-                        .map(|e| ::lambda_appsync::tokio::spawn(appsync_handler(e)))
+                        .enumerate()
+                        .filter_map(|(index, e)| {
+                            if e.info.operation.is_no_batch() {
+                                sequential.push((index, e));
+                                None
+                            } else {
+                                Some((index, ::lambda_appsync::tokio::spawn(appsync_handler(e))))
+                            }
+                        })
                         .collect::<Vec<_>>();
 
-                    let mut results = vec![];
-                    for h in handles {
-                        results.push(h.await.unwrap())
+                    for (index, e) in sequential {
+                        results[index] = Some(appsync_handler(e).await);
+                    }
+                    for (index, h) in handles {
+                        results[index] = Some(h.await.unwrap());
                     }
                     results
+                        .into_iter()
+                        .map(|r| r.expect("every event of the batch is handled"))
+                        .collect()
                 }
 
             });
@@ -395,39 +1085,224 @@ impl AppsyncLambdaMain {
     }
 
     #[allow(dead_code)]
-    fn default_env_logger_init() -> TokenStream2 {
+    fn default_env_logger_init(
+        log_filter: Option<&LitStr>,
+        log_color: &str,
+        correlation: bool,
+    ) -> TokenStream2 {
+        let log_filter = match log_filter {
+            Some(log_filter) => quote! {#log_filter},
+            None => quote! {"info,tracing::span=warn"},
+        };
+        // The default format, with the correlation id of the request (if any) before the message
+        let format = if correlation {
+            quote! {
+                .format(|buf, record| {
+                    use ::std::io::Write;
+                    match ::lambda_appsync::correlation::correlation_id() {
+                        Some(correlation_id) => writeln!(
+                            buf,
+                            "[{} {:<5} {}] correlation_id={} {}",
+                            buf.timestamp_micros(),
+                            record.level(),
+                            record.target(),
+                            correlation_id,
+                            record.args()
+                        ),
+                        None => writeln!(
+                            buf,
+                            "[{} {:<5} {}] {}",
+                            buf.timestamp_micros(),
+                            record.level(),
+                            record.target(),
+                            record.args()
+                        ),
+                    }
+                })
+            }
+        } else {
+            quote! {.format_timestamp_micros()}
+        };
         quote! {
             ::lambda_appsync::env_logger::Builder::from_env(
                 ::lambda_appsync::env_logger::Env::default()
-                    .default_filter_or("info,tracing::span=warn")
-                    .default_write_style_or("never"),
+                    .default_filter_or(#log_filter)
+                    .default_write_style_or(#log_color),
             )
-            .format_timestamp_micros()
+            #format
             .init();
         }
     }
 
     #[allow(dead_code)]
-    fn default_tracing_init() -> TokenStream2 {
+    fn default_tracing_init(log_filter: Option<&LitStr>, log_color: &str) -> TokenStream2 {
+        let env_filter = match log_filter {
+            // `RUST_LOG` still takes precedence over the filter of the macro invocation
+            Some(log_filter) => quote! {
+                ::lambda_appsync::tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| ::lambda_appsync::tracing_subscriber::EnvFilter::new(#log_filter))
+            },
+            None => quote! {
+                ::lambda_appsync::tracing_subscriber::EnvFilter::from_default_env()
+                    .add_directive(tracing::Level::INFO.into())
+            },
+        };
+        // ANSI color codes show up in a confusing manner in CloudWatch logs, hence `never` by default
+        let ansi = match log_color {
+            "always" => quote! {true},
+            "auto" => quote! {::std::io::IsTerminal::is_terminal(&::std::io::stdout())},
+            _ => quote! {false},
+        };
         quote! {
             ::lambda_appsync::tracing_subscriber::fmt()
                     .json()
-                    .with_env_filter(
-                        ::lambda_appsync::tracing_subscriber::EnvFilter::from_default_env()
-                            .add_directive(tracing::Level::INFO.into()),
-                    )
+                    .with_env_filter(#env_filter)
                     // this needs to be set to remove duplicated information in the log.
                     .with_current_span(false)
-                    // this needs to be set to false, otherwise ANSI color codes will
-                    // show up in a confusing manner in CloudWatch logs.
-                    .with_ansi(false)
+                    .with_ansi(#ansi)
                     // remove the name of the function from every log entry
                     .with_target(false)
                     .init();
         }
     }
 
+    // Deserialize `event.payload` into `appsync_events`, either a `Vec<AppsyncEvent<Operation>>` or an
+    // `AppsyncEvent<Operation>`, after passing it through the `event_adapter` if any, moving the
+    // `args_source` into the arguments and parsing them if `args_may_be_string` is set, keeping a copy of the raw JSON if `capture_raw_event` is set and
+    // attaching the Lambda context to each event
+    fn deserialize_appsync_events(&self, batch: bool) -> TokenStream2 {
+        // In its own block, as the handler may have other `_check_sig` modules
+        let adapt = match self.options.event_adapter {
+            Some(ref event_adapter) => quote_spanned! {event_adapter.span()=>
+                let mut event = event;
+                event.payload = {
+                    mod _check_sig {
+                        #[inline(always)]
+                        pub(super) fn call_event_adapter<F: Fn(::lambda_appsync::serde_json::Value) -> ::core::result::Result<::lambda_appsync::serde_json::Value, ::lambda_appsync::lambda_runtime::Error>>(
+                            f: F,
+                            payload: ::lambda_appsync::serde_json::Value,
+                        ) -> ::core::result::Result<::lambda_appsync::serde_json::Value, ::lambda_appsync::lambda_runtime::Error> {
+                            f(payload)
+                        }
+                    }
+                    _check_sig::call_event_adapter(#event_adapter, event.payload)?
+                };
+            },
+            None => TokenStream2::new(),
+        };
+        let mut args_source = match self.options.args_source {
+            Some(pointer) => quote! {
+                let mut event = event;
+                ::lambda_appsync::args_from_source(&mut event.payload, #pointer);
+            },
+            None => TokenStream2::new(),
+        };
+        if self.options.args_may_be_string {
+            args_source.extend(quote! {
+                let mut event = event;
+                ::lambda_appsync::parse_string_args(&mut event.payload);
+            });
+        }
+        let deserialize = match (self.options.capture_raw_event, batch) {
+            (false, true) => quote! {
+                let mut appsync_events: Vec<::lambda_appsync::AppsyncEvent<Operation>> = ::lambda_appsync::serde_json::from_value(event.payload)?;
+            },
+            (false, false) => quote! {
+                let mut appsync_events: ::lambda_appsync::AppsyncEvent<Operation> = ::lambda_appsync::serde_json::from_value(event.payload)?;
+            },
+            (true, true) => quote! {
+                let raw_events = match event.payload {
+                    ::lambda_appsync::serde_json::Value::Array(ref raw_events) => raw_events.clone(),
+                    _ => vec![],
+                };
+                let mut appsync_events: Vec<::lambda_appsync::AppsyncEvent<Operation>> = ::lambda_appsync::serde_json::from_value(event.payload)?;
+                for (appsync_event, raw_event) in appsync_events.iter_mut().zip(raw_events) {
+                    appsync_event.raw = Some(raw_event);
+                }
+            },
+            (true, false) => quote! {
+                let raw_event = event.payload.clone();
+                let mut appsync_events: ::lambda_appsync::AppsyncEvent<Operation> = ::lambda_appsync::serde_json::from_value(event.payload)?;
+                appsync_events.raw = Some(raw_event);
+            },
+        };
+        let attach_context = if batch {
+            quote! {
+                for appsync_event in appsync_events.iter_mut() {
+                    appsync_event.context = Some(event.context.clone());
+                }
+            }
+        } else {
+            quote! {
+                appsync_events.context = Some(event.context);
+            }
+        };
+        quote! {
+            #adapt
+            #args_source
+            #deserialize
+            #attach_context
+        }
+    }
+
+    // Answers the health check payload of `health_check_op` without dispatching it, `respond` returns
+    // the `response: HandlerResponse` variable from the handler. The probe is an event object or a list
+    // holding a single one, answered with the same shape. Tracks the cold start of the Lambda instance
+    fn health_check(&self, respond: TokenStream2) -> TokenStream2 {
+        let (Some(op), Some(schema_hash)) = (&self.options.health_check_op, &self.schema_hash)
+        else {
+            return TokenStream2::new();
+        };
+        quote! {
+            static COLD_START: ::core::sync::atomic::AtomicBool = ::core::sync::atomic::AtomicBool::new(true);
+            let cold_start = COLD_START.swap(false, ::core::sync::atomic::Ordering::Relaxed);
+            let (probe, in_list) = match &event.payload {
+                ::lambda_appsync::serde_json::Value::Array(events) if events.len() == 1 => (&events[0], true),
+                payload => (payload, false),
+            };
+            if probe.get("fieldName").and_then(::lambda_appsync::serde_json::Value::as_str) == Some(#op) {
+                let response = ::lambda_appsync::AppsyncResponse::from(::lambda_appsync::serde_json::json!({
+                    "status": "healthy",
+                    "schemaHash": #schema_hash,
+                    "coldStart": cold_start,
+                }));
+                let response = if in_list {
+                    ::lambda_appsync::HandlerResponse::Batch(vec![response])
+                } else {
+                    ::lambda_appsync::HandlerResponse::Single(response)
+                };
+                #respond
+            }
+        }
+    }
+
+    // Serializes `response` into `let body: Vec<u8>`, either with the `serialize_response` function or
+    // `serde_json`
+    fn serialize_response(&self) -> TokenStream2 {
+        match self.options.serialize_response {
+            Some(ref serialize_response) => quote_spanned! {serialize_response.span()=>
+                mod _check_sig {
+                    #[inline(always)]
+                    pub(super) fn call_serialize_response<R, F: Fn(&R) -> ::core::result::Result<Vec<u8>, ::lambda_appsync::lambda_runtime::Error>>(
+                        f: F,
+                        response: &R,
+                    ) -> ::core::result::Result<Vec<u8>, ::lambda_appsync::lambda_runtime::Error> {
+                        f(response)
+                    }
+                }
+                let body = _check_sig::call_serialize_response(#serialize_response, &response)?;
+            },
+            None => quote! {
+                let body = ::lambda_appsync::serde_json::to_vec(&response)?;
+            },
+        }
+    }
+
     fn lambda_function_handler(&self, tokens: &mut TokenStream2) {
+        if self.options.streaming.is_some() {
+            self.lambda_streaming_function_handler(tokens);
+            return;
+        }
         let (appsync_handler, ret_type) = if self.options.batch {
             (
                 format_ident!("appsync_batch_handler"),
@@ -454,12 +1329,89 @@ impl AppsyncLambdaMain {
             });
         }
 
+        let deserialize_appsync_events = self.deserialize_appsync_events(self.options.batch);
+
+        // `lambda_runtime` serializes the returned value with `serde_json`, a `RawValue` is written as is.
+        // `RawValue` can only be built by checking its JSON, done in a single pass over the bytes that also
+        // checks their UTF-8 encoding
+        let (ret_type, return_response) = if self.options.serialize_response.is_some() {
+            let serialize_response = self.serialize_response();
+            (
+                quote! {::std::boxed::Box<::lambda_appsync::serde_json::value::RawValue>},
+                quote! {
+                    #serialize_response
+                    Ok(::lambda_appsync::serde_json::from_slice::<::std::boxed::Box<::lambda_appsync::serde_json::value::RawValue>>(&body)?)
+                },
+            )
+        } else if self.options.health_check_op.is_some() {
+            (
+                quote! {::lambda_appsync::HandlerResponse},
+                quote! {Ok(response.into())},
+            )
+        } else {
+            (ret_type, quote! {Ok(response)})
+        };
+        // The health check response is not passed to `serialize_response`, which expects the shape of the mode
+        let health_check = self.health_check(if self.options.serialize_response.is_some() {
+            quote! {
+                return Ok(::lambda_appsync::serde_json::value::to_raw_value(&response)?);
+            }
+        } else {
+            quote! {
+                return Ok(response);
+            }
+        });
+
         tokens.extend(quote! {
             async fn function_handler(
                 event: ::lambda_appsync::lambda_runtime::LambdaEvent<::lambda_appsync::serde_json::Value>,
             ) -> ::core::result::Result<#ret_type, ::lambda_appsync::lambda_runtime::Error> {
                 #log_lines
-                Ok(#appsync_handler(::lambda_appsync::serde_json::from_value(event.payload)?).await)
+                #health_check
+                #deserialize_appsync_events
+                let response = #appsync_handler(appsync_events).await;
+                #return_response
+            }
+        });
+    }
+
+    fn lambda_streaming_function_handler(&self, tokens: &mut TokenStream2) {
+        #[cfg(feature = "tracing")]
+        tokens.extend(quote! {
+            #[::lambda_appsync::tracing::instrument(skip(event), fields(req_id = %event.context.request_id))]
+        });
+
+        #[allow(unused_mut)]
+        let mut log_lines = proc_macro2::TokenStream::new();
+        #[cfg(feature = "log")]
+        if self.options.event_logging {
+            log_lines.extend(quote! {
+                ::lambda_appsync::log::debug!("{}", ::lambda_appsync::serde_json::json!(event.payload));
+            });
+        }
+
+        let deserialize_appsync_events = self.deserialize_appsync_events(false);
+        let serialize_response = self.serialize_response();
+        let health_check = self.health_check(quote! {
+            let body = ::lambda_appsync::serde_json::to_vec(&response)?;
+            let body = ::lambda_appsync::lambda_runtime::streaming::Body::from(body);
+            return Ok(::lambda_appsync::lambda_runtime::streaming::Response::from(body));
+        });
+
+        tokens.extend(quote! {
+            async fn function_handler(
+                event: ::lambda_appsync::lambda_runtime::LambdaEvent<::lambda_appsync::serde_json::Value>,
+            ) -> ::core::result::Result<
+                ::lambda_appsync::lambda_runtime::streaming::Response<::lambda_appsync::lambda_runtime::streaming::Body>,
+                ::lambda_appsync::lambda_runtime::Error,
+            > {
+                #log_lines
+                #health_check
+                #deserialize_appsync_events
+                let response = appsync_handler(appsync_events).await;
+                #serialize_response
+                let body = ::lambda_appsync::lambda_runtime::streaming::Body::from(body);
+                Ok(::lambda_appsync::lambda_runtime::streaming::Response::from(body))
             }
         });
     }
@@ -484,10 +1436,17 @@ impl AppsyncLambdaMain {
             #[allow(unused_mut)]
             let mut default_log_init = proc_macro2::TokenStream::new();
             #[cfg(feature = "env_logger")]
-            default_log_init.extend(Self::default_env_logger_init());
+            default_log_init.extend(Self::default_env_logger_init(
+                self.options.log_filter.as_ref(),
+                self.options.log_color,
+                self.options.correlation_header.is_some(),
+            ));
             // The code initializing tracing fails if the env_logger initialization already happened
             #[cfg(all(feature = "tracing", not(any(feature = "env_logger"))))]
-            default_log_init.extend(Self::default_tracing_init());
+            default_log_init.extend(Self::default_tracing_init(
+                self.options.log_filter.as_ref(),
+                self.options.log_color,
+            ));
             // Future default inits can be inserted here like that for feature "fastrace" (for example):
             // #[cfg(all(feature = "fastrace", not(any(feature = "env_logger", feature = "tracing"))))]
             // default_log_init.extend(Self::default_fastrace_init());
@@ -504,6 +1463,12 @@ impl AppsyncLambdaMain {
             use ::lambda_appsync::tracing;
         });
 
+        let tokio_main = if self.options.current_thread_runtime {
+            quote! {#[tokio::main(flavor = "current_thread")]}
+        } else {
+            quote! {#[tokio::main]}
+        };
+
         tokens.extend(quote! {
 
             #config_getter
@@ -512,7 +1477,7 @@ impl AppsyncLambdaMain {
 
             #bring_in_scope
 
-            #[tokio::main]
+            #tokio_main
             async fn main() -> ::core::result::Result<(), ::lambda_appsync::lambda_runtime::Error> {
                 #log_init
 
@@ -528,9 +1493,24 @@ impl ToTokens for AppsyncLambdaMain {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         if self.options.appsync_types {
             self.graphql_schema.appsync_types_to_tokens(tokens);
+            if self.options.typed_filters {
+                self.graphql_schema.subscription_fields_to_tokens(tokens);
+            }
+            if self.options.emit_type_name_map {
+                self.graphql_schema.type_name_map_to_tokens(tokens);
+            }
+            if self.options.proptest {
+                self.graphql_schema.strategies_to_tokens(tokens);
+            }
         }
         if self.options.appsync_operations {
             self.graphql_schema.appsync_operations_to_tokens(tokens);
+            if self.options.emit_registry {
+                self.graphql_schema.operation_registry_to_tokens(tokens);
+            }
+            if !self.options.lambda_handler {
+                self.hook_signature_check(tokens);
+            }
         }
         if self.options.lambda_handler {
             self.appsync_event_handler(tokens);
@@ -19,6 +19,18 @@ impl TypeOverride {
     pub(super) fn type_ident(self) -> syn::Type {
         self.type_ident
     }
+    pub(super) fn span(&self) -> proc_macro2::Span {
+        syn::spanned::Spanned::span(&self.type_ident)
+    }
+    // Returns the call asserting the overriden type implements the serde traits
+    // required by `check`, one of the functions of `serde_checks_to_tokens`
+    pub(super) fn serde_check(&self, check: &str) -> proc_macro2::TokenStream {
+        let check = proc_macro2::Ident::new(check, proc_macro2::Span::call_site());
+        let ty = &self.type_ident;
+        quote::quote_spanned! {syn::spanned::Spanned::span(ty)=>
+            #check::<#ty>();
+        }
+    }
 }
 impl syn::parse::Parse for TypeOverride {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -79,3 +91,190 @@ impl syn::parse::Parse for NameOverride {
         })
     }
 }
+
+pub(super) struct OperationCfg {
+    type_name: syn::Ident,
+    field_name: syn::Ident,
+    predicate: proc_macro2::TokenStream,
+}
+impl OperationCfg {
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn field_name(&self) -> &syn::Ident {
+        &self.field_name
+    }
+    pub(super) fn predicate(self) -> proc_macro2::TokenStream {
+        self.predicate
+    }
+}
+impl syn::parse::Parse for OperationCfg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![.]>()?;
+        let field_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![:]>()?;
+        // The predicate is everything up to the next top-level `,`
+        // e.g. `feature = "admin"` or `any(feature = "admin", test)`
+        let mut predicate = proc_macro2::TokenStream::new();
+        while !input.is_empty() && !input.peek(syn::Token![,]) {
+            predicate.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+        if predicate.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "Expected a cfg predicate (e.g. `feature = \"admin\"`)",
+            ));
+        }
+        Ok(Self {
+            type_name,
+            field_name,
+            predicate,
+        })
+    }
+}
+
+pub(super) struct RequiredOverride {
+    type_name: syn::Ident,
+    field_name: syn::Ident,
+    default_if_null: bool,
+}
+impl RequiredOverride {
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn field_name(&self) -> &syn::Ident {
+        &self.field_name
+    }
+    pub(super) fn default_if_null(&self) -> bool {
+        self.default_if_null
+    }
+}
+impl syn::parse::Parse for RequiredOverride {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![.]>()?;
+        let field_name = input.call(syn::Ident::parse_any)?;
+        let default_if_null = if input.peek(syn::Token![:]) {
+            _ = input.parse::<syn::Token![:]>()?;
+            let mode = input.call(syn::Ident::parse_any)?;
+            if mode != "default" {
+                return Err(syn::Error::new(
+                    mode.span(),
+                    "Expected `default`, to deserialize an absent or null value as the default value",
+                ));
+            }
+            true
+        } else {
+            false
+        };
+        Ok(Self {
+            type_name,
+            field_name,
+            default_if_null,
+        })
+    }
+}
+
+pub(super) struct SkipSerialize {
+    type_name: syn::Ident,
+    field_name: syn::Ident,
+}
+impl SkipSerialize {
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn field_name(&self) -> &syn::Ident {
+        &self.field_name
+    }
+}
+impl syn::parse::Parse for SkipSerialize {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![.]>()?;
+        let field_name = input.call(syn::Ident::parse_any)?;
+        Ok(Self {
+            type_name,
+            field_name,
+        })
+    }
+}
+
+pub(super) struct SerdeRenameAll {
+    type_name: syn::Ident,
+    rule: &'static str,
+}
+impl SerdeRenameAll {
+    // The casings accepted by `#[serde(rename_all = "...")]`
+    const RULES: [&'static str; 8] = [
+        "lowercase",
+        "UPPERCASE",
+        "PascalCase",
+        "camelCase",
+        "snake_case",
+        "SCREAMING_SNAKE_CASE",
+        "kebab-case",
+        "SCREAMING-KEBAB-CASE",
+    ];
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn rule(&self) -> &'static str {
+        self.rule
+    }
+}
+impl syn::parse::Parse for SerdeRenameAll {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![:]>()?;
+        // The kebab casings are not identifiers and must be given as strings
+        let (rule, span) = if input.peek(syn::LitStr) {
+            let rule = input.parse::<syn::LitStr>()?;
+            (rule.value(), rule.span())
+        } else {
+            let rule = input.call(syn::Ident::parse_any)?;
+            (rule.to_string(), rule.span())
+        };
+        let Some(rule) = Self::RULES.into_iter().find(|r| *r == rule) else {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "Expected one of `{}`, got `{rule}`",
+                    Self::RULES.join("`, `")
+                ),
+            ));
+        };
+        Ok(Self { type_name, rule })
+    }
+}
+
+pub(super) struct NullableArg {
+    type_name: syn::Ident,
+    field_name: syn::Ident,
+    arg_name: syn::Ident,
+}
+impl NullableArg {
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn field_name(&self) -> &syn::Ident {
+        &self.field_name
+    }
+    pub(super) fn arg_name(&self) -> &syn::Ident {
+        &self.arg_name
+    }
+}
+impl syn::parse::Parse for NullableArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![.]>()?;
+        let field_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![.]>()?;
+        let arg_name = input.call(syn::Ident::parse_any)?;
+        Ok(Self {
+            type_name,
+            field_name,
+            arg_name,
+        })
+    }
+}
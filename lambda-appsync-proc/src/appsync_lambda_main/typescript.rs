@@ -0,0 +1,196 @@
+use std::collections::BTreeSet;
+
+use graphql_parser::schema::{Definition, Document, Field, Type, TypeDefinition};
+
+// TypeScript type of the AWS scalars, as serialized by AppSync
+fn aws_scalar_to_ts(name: &str) -> Option<&'static str> {
+    match name {
+        "AWSEmail" | "AWSPhone" | "AWSDate" | "AWSTime" | "AWSDateTime" | "AWSJSON" | "AWSURL"
+        | "AWSIPAddress" => Some("string"),
+        "AWSTimestamp" => Some("number"),
+        _ => None,
+    }
+}
+
+// TypeScript type of a built-in scalar or of a named type. The AWS scalars keep their name, they are
+// declared as aliases once the schema is walked, see `schema_to_typescript`
+fn named_type_to_ts<'a>(name: &'a str, aws_scalars: &mut BTreeSet<&'a str>) -> &'a str {
+    match name {
+        "String" | "ID" => "string",
+        "Int" | "Float" => "number",
+        "Boolean" => "boolean",
+        _ => {
+            if aws_scalar_to_ts(name).is_some() {
+                aws_scalars.insert(name);
+            }
+            name
+        }
+    }
+}
+
+fn non_null_type_to_ts<'a>(
+    field_type: &'a Type<'_, String>,
+    aws_scalars: &mut BTreeSet<&'a str>,
+) -> String {
+    match field_type {
+        Type::NamedType(name) => named_type_to_ts(name, aws_scalars).to_owned(),
+        Type::ListType(inner) => format!("Array<{}>", type_to_ts(inner, aws_scalars)),
+        Type::NonNullType(inner) => non_null_type_to_ts(inner, aws_scalars),
+    }
+}
+
+fn type_to_ts<'a>(field_type: &'a Type<'_, String>, aws_scalars: &mut BTreeSet<&'a str>) -> String {
+    match field_type {
+        Type::NonNullType(inner) => non_null_type_to_ts(inner, aws_scalars),
+        _ => format!("{} | null", non_null_type_to_ts(field_type, aws_scalars)),
+    }
+}
+
+fn push_description(ts: &mut String, description: Option<&String>, indent: &str) {
+    if let Some(description) = description {
+        ts.push_str(&format!(
+            "{indent}/** {} */\n",
+            description.trim().replace("*/", "*\\/").replace('\n', " ")
+        ));
+    }
+}
+
+// The fields of a type or an interface
+fn push_fields<'a>(
+    ts: &mut String,
+    fields: &'a [Field<'_, String>],
+    aws_scalars: &mut BTreeSet<&'a str>,
+) {
+    for field in fields.iter() {
+        push_description(ts, field.description.as_ref(), "  ");
+        ts.push_str(&format!(
+            "  {}: {};\n",
+            field.name,
+            type_to_ts(&field.field_type, aws_scalars)
+        ));
+    }
+}
+
+// A union of string literals or of type names, `never` if empty
+fn union_to_ts(members: Vec<String>) -> String {
+    if members.is_empty() {
+        "never".to_owned()
+    } else {
+        members.join(" | ")
+    }
+}
+
+/// Generates TypeScript declarations (`.d.ts`) for the scalars, types, interfaces, unions, inputs and
+/// enums of the schema
+///
+/// Names are the GraphQL names, i.e. the JSON representation sent and received by AppSync,
+/// regardless of the Rust-side name and type overrides.
+pub(crate) fn schema_to_typescript(doc: &Document<'_, String>) -> String {
+    let mut root_types = vec!["Query", "Mutation", "Subscription"];
+    for def in doc.definitions.iter() {
+        if let Definition::SchemaDefinition(sd) = def {
+            root_types = [&sd.query, &sd.mutation, &sd.subscription]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+        }
+    }
+
+    let mut aws_scalars = BTreeSet::new();
+    let mut ts = String::new();
+    for def in doc.definitions.iter() {
+        let Definition::TypeDefinition(type_definition) = def else {
+            continue;
+        };
+        match type_definition {
+            TypeDefinition::Scalar(scalar_type) => {
+                // The JSON representation of a custom scalar is not known
+                ts.push('\n');
+                push_description(&mut ts, scalar_type.description.as_ref(), "");
+                ts.push_str(&format!("export type {} = unknown;\n", scalar_type.name));
+            }
+            TypeDefinition::Object(object_type) => {
+                if root_types.contains(&object_type.name.as_str()) {
+                    continue;
+                }
+                ts.push('\n');
+                push_description(&mut ts, object_type.description.as_ref(), "");
+                let extends = if object_type.implements_interfaces.is_empty() {
+                    String::new()
+                } else {
+                    format!(" extends {}", object_type.implements_interfaces.join(", "))
+                };
+                ts.push_str(&format!(
+                    "export interface {}{extends} {{\n",
+                    object_type.name
+                ));
+                push_fields(&mut ts, &object_type.fields, &mut aws_scalars);
+                ts.push_str("}\n");
+            }
+            TypeDefinition::Interface(interface_type) => {
+                ts.push('\n');
+                push_description(&mut ts, interface_type.description.as_ref(), "");
+                ts.push_str(&format!("export interface {} {{\n", interface_type.name));
+                push_fields(&mut ts, &interface_type.fields, &mut aws_scalars);
+                ts.push_str("}\n");
+            }
+            TypeDefinition::Union(union_type) => {
+                ts.push('\n');
+                push_description(&mut ts, union_type.description.as_ref(), "");
+                ts.push_str(&format!(
+                    "export type {} = {};\n",
+                    union_type.name,
+                    union_to_ts(union_type.types.clone())
+                ));
+            }
+            TypeDefinition::InputObject(input_object_type) => {
+                ts.push('\n');
+                push_description(&mut ts, input_object_type.description.as_ref(), "");
+                ts.push_str(&format!("export interface {} {{\n", input_object_type.name));
+                for field in input_object_type.fields.iter() {
+                    push_description(&mut ts, field.description.as_ref(), "  ");
+                    // Optional input fields may be omitted
+                    let optional = if matches!(field.value_type, Type::NonNullType(_)) {
+                        ""
+                    } else {
+                        "?"
+                    };
+                    ts.push_str(&format!(
+                        "  {}{optional}: {};\n",
+                        field.name,
+                        type_to_ts(&field.value_type, &mut aws_scalars)
+                    ));
+                }
+                ts.push_str("}\n");
+            }
+            TypeDefinition::Enum(enum_type) => {
+                ts.push('\n');
+                push_description(&mut ts, enum_type.description.as_ref(), "");
+                let variants = enum_type
+                    .values
+                    .iter()
+                    .map(|value| format!("\"{}\"", value.name))
+                    .collect();
+                ts.push_str(&format!(
+                    "export type {} = {};\n",
+                    enum_type.name,
+                    union_to_ts(variants)
+                ));
+            }
+        }
+    }
+
+    // The AWS scalars used by the declarations above
+    let mut header = String::from(
+        "// Generated by lambda-appsync from the GraphQL schema, do not edit manually.\n",
+    );
+    if !aws_scalars.is_empty() {
+        header.push('\n');
+        for aws_scalar in aws_scalars {
+            let ts_type = aws_scalar_to_ts(aws_scalar).expect("only AWS scalars are collected");
+            header.push_str(&format!("export type {aws_scalar} = {ts_type};\n"));
+        }
+    }
+    header + &ts
+}
@@ -1,13 +1,107 @@
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, quote_spanned, ToTokens};
-use syn::{braced, parenthesized, parse::Parse, parse_macro_input, Ident, Token, Type, Visibility};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    braced, parenthesized, parse::Parse, parse_macro_input, spanned::Spanned, Ident, Token, Type,
+    Visibility,
+};
 
 use crate::common::{Name, OperationKind};
 
+// Parses `{ max: 3, base_ms: 50, retryable: path::to::predicate }`
+struct RetryConfig {
+    max: u32,
+    base_ms: u64,
+    retryable: Option<syn::Path>,
+}
+impl Parse for RetryConfig {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let config_input;
+        let brace = braced!(config_input in input);
+        let mut max = None;
+        let mut base_ms = None;
+        let mut retryable = None;
+        while !config_input.is_empty() {
+            let key = config_input.parse::<Ident>()?;
+            _ = config_input.parse::<Token![:]>()?;
+            match key.to_string().as_str() {
+                "max" => {
+                    max.replace(config_input.parse::<syn::LitInt>()?);
+                }
+                "base_ms" => {
+                    base_ms.replace(config_input.parse::<syn::LitInt>()?);
+                }
+                "retryable" => {
+                    retryable.replace(config_input.parse::<syn::Path>()?);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("Unknown retry parameter `{key}`, expected one of `max`, `base_ms` or `retryable`"),
+                    ))
+                }
+            }
+            if !config_input.is_empty() {
+                _ = config_input.parse::<Token![,]>()?;
+            }
+        }
+        let max = max
+            .ok_or_else(|| syn::Error::new(brace.span.join(), "Missing retry parameter `max`"))?
+            .base10_parse()?;
+        let base_ms = match base_ms {
+            Some(base_ms) => base_ms.base10_parse()?,
+            None => 50,
+        };
+        Ok(Self {
+            max,
+            base_ms,
+            retryable,
+        })
+    }
+}
+
+// Parses `(name = path::to::validator, other = validator)`
+struct Validators(Vec<(Ident, syn::Path)>);
+impl Parse for Validators {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let validators_input;
+        _ = parenthesized!(validators_input in input);
+        let mut validators = vec![];
+        while !validators_input.is_empty() {
+            let arg_name = validators_input.parse::<Ident>()?;
+            _ = validators_input.parse::<Token![=]>()?;
+            validators.push((arg_name, validators_input.parse::<syn::Path>()?));
+            if !validators_input.is_empty() {
+                _ = validators_input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Self(validators))
+    }
+}
+
+// Parses `(id, other)`
+struct CacheKeyArgs(Vec<Ident>);
+impl Parse for CacheKeyArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let cache_key_input;
+        _ = parenthesized!(cache_key_input in input);
+        let arg_names = cache_key_input.parse_terminated(Ident::parse, Token![,])?;
+        Ok(Self(arg_names.into_iter().collect()))
+    }
+}
+
 enum ArgsOption {
     KeepOriginalFunctionName,
     WithAppsyncEvent,
+    KeepRawArgs(Span),
+    NoBatch,
+    RawReturn(Span),
+    LooseReturn(Span),
+    ArgsStruct(Span),
+    Retry(RetryConfig),
+    TimeoutMs(u64),
+    Validate(Validators),
+    CacheKey(CacheKeyArgs),
 }
 impl Parse for ArgsOption {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -15,6 +109,23 @@ impl Parse for ArgsOption {
         match ident.to_string().as_str() {
             "keep_original_function_name" => Ok(Self::KeepOriginalFunctionName),
             "with_appsync_event" => Ok(Self::WithAppsyncEvent),
+            "keep_raw_args" => Ok(Self::KeepRawArgs(ident.span())),
+            "no_batch" => Ok(Self::NoBatch),
+            "raw_return" => Ok(Self::RawReturn(ident.span())),
+            "loose_return" => Ok(Self::LooseReturn(ident.span())),
+            "args_struct" => Ok(Self::ArgsStruct(ident.span())),
+            "retry" => {
+                _ = input.parse::<Token![=]>()?;
+                Ok(Self::Retry(input.parse()?))
+            }
+            "timeout_ms" => {
+                _ = input.parse::<Token![=]>()?;
+                Ok(Self::TimeoutMs(
+                    input.parse::<syn::LitInt>()?.base10_parse()?,
+                ))
+            }
+            "validate" => Ok(Self::Validate(input.parse()?)),
+            "cache_key" => Ok(Self::CacheKey(input.parse()?)),
             _ => Err(syn::Error::new(
                 ident.span(),
                 format!("Unknown option `{ident}`",),
@@ -28,6 +139,15 @@ struct Args {
     op_name: Name,
     keep_original_function_name: bool,
     with_appsync_event: bool,
+    keep_raw_args: Option<Span>,
+    no_batch: bool,
+    raw_return: bool,
+    loose_return: bool,
+    args_struct: Option<Span>,
+    retry: Option<RetryConfig>,
+    timeout_ms: Option<u64>,
+    validators: Vec<(Ident, syn::Path)>,
+    cache_key: Option<Vec<Ident>>,
 }
 impl Parse for Args {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -56,6 +176,15 @@ impl Parse for Args {
             op_name,
             keep_original_function_name: false,
             with_appsync_event: false,
+            keep_raw_args: None,
+            no_batch: false,
+            raw_return: false,
+            loose_return: false,
+            args_struct: None,
+            retry: None,
+            timeout_ms: None,
+            validators: vec![],
+            cache_key: None,
         };
 
         while input.peek(Token![,]) {
@@ -68,6 +197,55 @@ impl Parse for Args {
             match option {
                 ArgsOption::KeepOriginalFunctionName => args.keep_original_function_name = true,
                 ArgsOption::WithAppsyncEvent => args.with_appsync_event = true,
+                ArgsOption::KeepRawArgs(span) => {
+                    args.keep_raw_args.replace(span);
+                }
+                ArgsOption::NoBatch => args.no_batch = true,
+                ArgsOption::RawReturn(span) => {
+                    if let OperationKind::Subscription = args.op_kind {
+                        return Err(syn::Error::new(
+                            span,
+                            "`raw_return` is not supported for subscriptions",
+                        ));
+                    }
+                    if args.loose_return {
+                        return Err(syn::Error::new(
+                            span,
+                            "`raw_return` cannot be combined with `loose_return`",
+                        ));
+                    }
+                    args.raw_return = true;
+                }
+                ArgsOption::LooseReturn(span) => {
+                    if let OperationKind::Subscription = args.op_kind {
+                        return Err(syn::Error::new(
+                            span,
+                            "`loose_return` is not supported for subscriptions",
+                        ));
+                    }
+                    if args.raw_return {
+                        return Err(syn::Error::new(
+                            span,
+                            "`loose_return` cannot be combined with `raw_return`",
+                        ));
+                    }
+                    args.loose_return = true;
+                }
+                ArgsOption::ArgsStruct(span) => {
+                    args.args_struct.replace(span);
+                }
+                ArgsOption::Retry(retry) => {
+                    args.retry.replace(retry);
+                }
+                ArgsOption::TimeoutMs(timeout_ms) => {
+                    args.timeout_ms.replace(timeout_ms);
+                }
+                ArgsOption::Validate(Validators(validators)) => {
+                    args.validators.extend(validators);
+                }
+                ArgsOption::CacheKey(CacheKeyArgs(arg_names)) => {
+                    args.cache_key.replace(arg_names);
+                }
             }
         }
         Ok(args)
@@ -107,6 +285,7 @@ impl ToTokens for FctArg {
 }
 struct Fct {
     vis: Option<Visibility>,
+    is_async: bool,
     fct_name: Ident,
     args: Vec<FctArg>,
     return_type: Type,
@@ -133,13 +312,17 @@ impl Parse for Fct {
             None
         };
 
-        if !(input.peek(Token![async]) && input.peek2(Token![fn])) {
+        let is_async = if input.peek(Token![async]) && input.peek2(Token![fn]) {
+            _ = input.parse::<Token![async]>()?;
+            true
+        } else if input.peek(Token![fn]) {
+            false
+        } else {
             return Err(syn::Error::new(
                 input.span(),
-                "appsync_operation macro must be use on an async function",
+                "appsync_operation macro must be used on a function",
             ));
-        }
-        _ = input.parse::<Token![async]>()?;
+        };
         _ = input.parse::<Token![fn]>()?;
         let fct_name = input.parse()?;
 
@@ -163,6 +346,7 @@ impl Parse for Fct {
 
         Ok(Self {
             vis,
+            is_async,
             fct_name,
             args,
             return_type,
@@ -178,13 +362,19 @@ impl ToTokens for Fct {
             TokenStream2::new()
         };
 
+        let asyncness = if self.is_async {
+            quote! {async}
+        } else {
+            TokenStream2::new()
+        };
+
         let fct_name = &self.fct_name;
         let args = self.args.iter();
         let orig_fct_body = &self.body;
         let return_type = &self.return_type;
 
         tokens.extend(quote! {
-            #vis async fn #fct_name(
+            #vis #asyncness fn #fct_name(
                 #(#args),*
             ) -> #return_type {
                 #orig_fct_body
@@ -208,7 +398,7 @@ impl AppsyncOperation {
             Ident::new("without_event", span)
         };
         quote_spanned! {span=>
-            crate::__operations::#op_type_module::#op_module_name::#op_submodule_name
+            __appsync_lambda_main::#op_type_module::#op_module_name::#op_submodule_name
         }
     }
     fn check_signature_to_tokens(&self) -> TokenStream2 {
@@ -216,15 +406,200 @@ impl AppsyncOperation {
 
         let fct_name = &self.fct.fct_name;
         let dymmy_fct = self.fct.dummy_function();
+        let return_kind = if self.args.raw_return {
+            "raw_"
+        } else if self.args.loose_return {
+            "loose_"
+        } else {
+            ""
+        };
+        let args_kind = if self.args.args_struct.is_some() {
+            "args_struct_"
+        } else {
+            ""
+        };
+        let check_signature = format_ident!("check_{return_kind}{args_kind}signature");
         quote! {
             const _: fn() = || {
                 // Compile-time assertion only – never calls the user fn.
                 #dymmy_fct
-                #op_module_path::check_signature(#fct_name);
+                #op_module_path::#check_signature(#fct_name);
             };
         }
     }
 
+    // Runs the operation body in a nested function, called again with clones of the arguments
+    // while it fails with a retryable error, waiting `base_ms * 2^attempt` between attempts
+    fn retry_body(&self, retry: &RetryConfig, operation_body: &TokenStream2) -> TokenStream2 {
+        let args = self.fct.args.iter();
+        let arg_names = self.fct.args.iter().map(|a| &a.name);
+        let return_type = &self.fct.return_type;
+        let max = proc_macro2::Literal::u32_suffixed(retry.max);
+        let base_ms = proc_macro2::Literal::u64_suffixed(retry.base_ms);
+        let retryable = match retry.retryable {
+            Some(ref retryable) => quote! {#retryable},
+            None => quote! {::lambda_appsync::AppsyncError::is_retryable},
+        };
+        quote! {
+            #[allow(clippy::too_many_arguments)]
+            async fn __operation_attempt(#(#args),*) -> #return_type {
+                #operation_body
+            }
+            let mut attempt: u32 = 0;
+            loop {
+                match __operation_attempt(#(::core::clone::Clone::clone(&#arg_names)),*).await {
+                    ::core::result::Result::Err(ref e) if attempt < #max && #retryable(e) => {
+                        let delay_ms = #base_ms.saturating_mul(1u64 << attempt.min(32));
+                        ::lambda_appsync::tokio::time::sleep(
+                            ::core::time::Duration::from_millis(delay_ms)
+                        ).await;
+                        attempt += 1;
+                    }
+                    result => break result,
+                }
+            }
+        }
+    }
+
+    // Runs the operation body, retries included, in a nested function bounded by `tokio::time::timeout`
+    fn timeout_body(&self, timeout_ms: u64, operation_body: &TokenStream2) -> TokenStream2 {
+        let args = self.fct.args.iter();
+        let arg_names = self.fct.args.iter().map(|a| &a.name);
+        let return_type = &self.fct.return_type;
+        let message = format!(
+            "`{}.{}` timed out after {timeout_ms} ms",
+            self.args.op_kind,
+            self.args.op_name.orig()
+        );
+        let timeout_ms = proc_macro2::Literal::u64_suffixed(timeout_ms);
+        quote! {
+            #[allow(clippy::too_many_arguments)]
+            async fn __operation_timed(#(#args),*) -> #return_type {
+                #operation_body
+            }
+            match ::lambda_appsync::tokio::time::timeout(
+                ::core::time::Duration::from_millis(#timeout_ms),
+                __operation_timed(#(#arg_names),*),
+            )
+            .await
+            {
+                ::core::result::Result::Ok(result) => result,
+                ::core::result::Result::Err(_) => ::core::result::Result::Err(
+                    ::lambda_appsync::AppsyncError::new("Timeout", #message),
+                ),
+            }
+        }
+    }
+
+    // The parameter holding the arguments struct, see `args_struct`
+    fn args_struct_param(&self) -> Option<&FctArg> {
+        self.args.args_struct.and_then(|_| self.fct.args.first())
+    }
+
+    // An argument named by `validate` or `cache_key`, a field of the arguments struct with `args_struct`
+    fn named_arg(&self, arg_name: &Ident) -> TokenStream2 {
+        match self.args_struct_param() {
+            Some(args_struct) => {
+                let args_struct_name = &args_struct.name;
+                quote_spanned! {arg_name.span()=> #args_struct_name.#arg_name}
+            }
+            None => quote! {#arg_name},
+        }
+    }
+
+    // Calls the validators of the arguments, failing with a `BadRequest` error before the body runs
+    fn validation_to_tokens(&self) -> TokenStream2 {
+        let validations = self.args.validators.iter().map(|(arg_name, validator)| {
+            let arg = self.named_arg(arg_name);
+            quote_spanned! {validator.span()=>
+                if let ::core::result::Result::Err(message) = #validator(&#arg) {
+                    return ::core::result::Result::Err(
+                        ::lambda_appsync::AppsyncError::new("BadRequest", message),
+                    );
+                }
+            }
+        });
+        quote! {
+            #(#validations)*
+        }
+    }
+
+    // Shadows the default of `DefaultOperations`, see `no_batch`
+    fn no_batch_to_tokens(&self) -> TokenStream2 {
+        if !self.args.no_batch {
+            return TokenStream2::new();
+        }
+        let op_fct_name = self
+            .args
+            .op_name
+            .to_prefixed_fct_ident(self.args.op_kind.fct_prefix());
+        let no_batch_fct_name = Ident::new(&format!("{op_fct_name}_no_batch"), op_fct_name.span());
+        quote! {
+            impl __appsync_lambda_main::Operation {
+                fn #no_batch_fct_name() -> bool {
+                    true
+                }
+            }
+        }
+    }
+    // The `<op_fct_name>_cache_key` function, see `cache_key`
+    fn cache_key_to_tokens(&self) -> TokenStream2 {
+        let Some(ref cache_key) = self.args.cache_key else {
+            return TokenStream2::new();
+        };
+        let vis = if let Some(ref vis) = self.fct.vis {
+            vis.into_token_stream()
+        } else {
+            TokenStream2::new()
+        };
+        let op_fct_name = self
+            .args
+            .op_name
+            .to_prefixed_fct_ident(self.args.op_kind.fct_prefix());
+        let cache_key_fct_name =
+            Ident::new(&format!("{op_fct_name}_cache_key"), op_fct_name.span());
+        // With `args_struct`, the function takes the arguments struct and reads the named fields
+        let key_args = match self.args_struct_param() {
+            Some(args_struct) => vec![args_struct],
+            None => cache_key
+                .iter()
+                .filter_map(|arg_name| self.fct.args.iter().find(|arg| arg.name == *arg_name))
+                .collect::<Vec<_>>(),
+        };
+        let params = key_args.iter().map(|arg| {
+            let (name, ty) = (&arg.name, &arg.ty);
+            quote! {#name: &#ty}
+        });
+        let key_parts = cache_key.iter().map(|arg_name| {
+            let arg = self.named_arg(arg_name);
+            quote! {&#arg}
+        });
+        let arg_names = cache_key.iter();
+        // The parent type keeps a query and a mutation with the same field name apart
+        let prefix = format!("{}.{}", self.args.op_kind, self.args.op_name.orig());
+        quote! {
+            impl __appsync_lambda_main::Operation {
+                #vis fn #cache_key_fct_name(
+                    #(#params),*
+                ) -> ::core::result::Result<::std::string::String, ::lambda_appsync::AppsyncError> {
+                    let mut key = ::std::string::String::from(#prefix);
+                    #(
+                        key.push(':');
+                        key.push_str(
+                            &::lambda_appsync::cache_key_part(#key_parts).map_err(|e| {
+                                ::lambda_appsync::AppsyncError::new(
+                                    "InvalidCacheKey",
+                                    ::std::format!("Cannot format `{}`: {e}", ::core::stringify!(#arg_names)),
+                                )
+                            })?,
+                        );
+                    )*
+                    ::core::result::Result::Ok(key)
+                }
+            }
+        }
+    }
+
     fn impl_operation_to_tokens(&self) -> TokenStream2 {
         let vis = if let Some(ref vis) = self.fct.vis {
             vis.into_token_stream()
@@ -236,8 +611,14 @@ impl AppsyncOperation {
             // Call the original fct
             let fct_name = &self.fct.fct_name;
             let arg_names = self.fct.args.iter().map(|a| &a.name);
-            &quote! {
-                #fct_name(#(#arg_names),*).await
+            if self.fct.is_async {
+                &quote! {
+                    #fct_name(#(#arg_names),*).await
+                }
+            } else {
+                &quote! {
+                    #fct_name(#(#arg_names),*)
+                }
             }
         } else {
             // Inline the original fct body
@@ -252,12 +633,31 @@ impl AppsyncOperation {
             .to_prefixed_fct_ident(self.args.op_kind.fct_prefix());
         let arg_names = self.fct.args.iter().map(|a| &a.name);
         let return_type = &self.fct.return_type;
+        let operation_body = match self.args.retry {
+            Some(ref retry) => self.retry_body(retry, operation_body),
+            None => operation_body.clone(),
+        };
+        let operation_body = match self.args.timeout_ms {
+            Some(timeout_ms) => self.timeout_body(timeout_ms, &operation_body),
+            None => operation_body,
+        };
+        let validation = self.validation_to_tokens();
+        let operation_arguments = match (
+            self.args.args_struct.is_some(),
+            self.args.keep_raw_args.is_some(),
+        ) {
+            (false, false) => quote! {operation_arguments},
+            (true, false) => quote! {operation_args_struct},
+            (false, true) => quote! {operation_arguments_keep_raw},
+            (true, true) => quote! {operation_args_struct_keep_raw},
+        };
         quote! {
-            impl crate::Operation {
+            impl __appsync_lambda_main::Operation {
                 #vis async fn #op_fct_name(
                     mut event: ::lambda_appsync::AppsyncEvent<Self>
                 ) -> #return_type {
-                    let (#(#arg_names,)*) = #op_module_path::operation_arguments(&mut event)?;
+                    let (#(#arg_names,)*) = #op_module_path::#operation_arguments(&mut event)?;
+                    #validation
                     #operation_body
                 }
             }
@@ -268,13 +668,87 @@ impl TryFrom<(Args, Fct)> for AppsyncOperation {
     type Error = syn::Error;
 
     fn try_from((args, fct): (Args, Fct)) -> Result<Self, Self::Error> {
+        // With `args_struct`, the function takes the arguments struct and possibly the event
+        let expected_params = 1 + usize::from(args.with_appsync_event);
+        let args_struct_error = args
+            .args_struct
+            .filter(|_| fct.args.len() != expected_params)
+            .map(|span| {
+                syn::Error::new(
+                    span,
+                    if args.with_appsync_event {
+                        "With `args_struct`, the function must take the arguments struct and the AppSync event as its only parameters"
+                    } else {
+                        "With `args_struct`, the function must take the arguments struct as its only parameter"
+                    },
+                )
+            });
+        // The arguments kept in the event are only visible with `with_appsync_event`
+        let keep_raw_args_error = args
+            .keep_raw_args
+            .filter(|_| !args.with_appsync_event)
+            .map(|span| syn::Error::new(span, "`keep_raw_args` requires `with_appsync_event`"));
+        if let Some(e) = args_struct_error
+            .into_iter()
+            .chain(keep_raw_args_error)
+            .chain(args
+            .validators
+            .iter()
+            .filter(|_| args.args_struct.is_none())
+            .filter(|(arg_name, _)| !fct.args.iter().any(|arg| arg.name == *arg_name))
+            .map(|(arg_name, _)| {
+                syn::Error::new(
+                    arg_name.span(),
+                    format!("Cannot validate `{arg_name}`, the function has no such argument"),
+                )
+            }))
+            .chain(
+                args.cache_key
+                    .iter()
+                    .flatten()
+                    .filter(|_| args.args_struct.is_none())
+                    .filter(|arg_name| !fct.args.iter().any(|arg| arg.name == **arg_name))
+                    .map(|arg_name| {
+                        syn::Error::new(
+                            arg_name.span(),
+                            format!(
+                                "Cannot build the cache key from `{arg_name}`, the function has no such argument"
+                            ),
+                        )
+                    }),
+            )
+            .reduce(|mut acc, e| {
+                acc.combine(e);
+                acc
+            })
+        {
+            return Err(e);
+        }
         Ok(Self { args, fct })
     }
 }
 impl ToTokens for AppsyncOperation {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        tokens.extend(self.check_signature_to_tokens());
-        tokens.extend(self.impl_operation_to_tokens());
+        // Everything generated by `appsync_lambda_main!` is reached through this single import, so that
+        // forgetting the macro fails with one error naming it instead of one per missing item
+        let span = self.args.op_name.to_var_ident().span();
+        let import_main = quote_spanned! {span=>
+            use crate::__appsync_lambda_main;
+        };
+        let check_signature = self.check_signature_to_tokens();
+        let impl_operation = self.impl_operation_to_tokens();
+        let cache_key = self.cache_key_to_tokens();
+        let no_batch = self.no_batch_to_tokens();
+        tokens.extend(quote! {
+            const _: () = {
+                #[allow(unused_imports)]
+                #import_main
+                #check_signature
+                #impl_operation
+                #cache_key
+                #no_batch
+            };
+        });
         if self.args.keep_original_function_name {
             self.fct.to_tokens(tokens);
         }
@@ -33,6 +33,14 @@ impl CaseType {
     }
 }
 
+// How the fields of the generated structs are named on the Rust side
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum FieldCase {
+    #[default]
+    Snake, // Converted to snake case
+    Preserve, // Kept as written in the GraphQL schema
+}
+
 // Word is always stored as lowercase
 #[derive(Debug)]
 pub(crate) struct Word(String);
@@ -66,6 +74,7 @@ pub(crate) struct Name {
     span: Span,
     words: Vec<Word>,
     name_override: Option<String>,
+    namespace: Option<String>,
 }
 impl From<String> for Name {
     fn from(value: String) -> Self {
@@ -108,6 +117,7 @@ impl From<(String, Span)> for Name {
             span,
             words,
             name_override: None,
+            namespace: None,
         }
     }
 }
@@ -115,9 +125,17 @@ impl Name {
     pub(crate) fn override_name(&mut self, name: String) {
         self.name_override.replace(name);
     }
+    /// Sets a namespace that will prefix the name returned by [Name::to_type_ident],
+    /// unless the name is overriden
+    pub(crate) fn set_namespace(&mut self, namespace: &str) {
+        self.namespace.replace(namespace.to_owned());
+    }
     pub(crate) fn orig(&self) -> &str {
         &self.orig
     }
+    pub(crate) fn is_overriden(&self) -> bool {
+        self.name_override.is_some()
+    }
     // pub(crate) fn set_span(&mut self, span: Span) {
     //     self.span = Some(span);
     // }
@@ -154,7 +172,23 @@ impl Name {
         }
     }
     pub(crate) fn to_type_ident(&self) -> proc_macro2::Ident {
-        proc_macro2::Ident::new(&self.to_case(CaseType::Pascal), self.span)
+        match self.namespace {
+            Some(ref namespace) if self.name_override.is_none() => proc_macro2::Ident::new(
+                &format!("{namespace}{}", self.to_case(CaseType::Pascal)),
+                self.span,
+            ),
+            _ => match self.to_case(CaseType::Pascal).as_str() {
+                // The only keyword in PascalCase, prefixed like `r_self` for fields
+                "Self" => proc_macro2::Ident::new("RSelf", self.span),
+                ident => proc_macro2::Ident::new(ident, self.span),
+            },
+        }
+    }
+    /// Converts the name, or its override, to an enum variant identifier in PascalCase, ignoring the
+    /// namespace. Used for the variants generated from field names, whose overrides are in snake case
+    pub(crate) fn to_variant_ident(&self) -> proc_macro2::Ident {
+        let name = self.name_override.as_ref().unwrap_or(&self.orig);
+        Name::from((name.clone(), self.span)).to_type_ident()
     }
     /// Converts the name to a valid Rust identifier in snake case, automatically escaping Rust keywords.
     ///
@@ -164,6 +198,11 @@ impl Name {
     /// However, some keywords like `crate`, `self`, `super` cannot be escaped this way
     /// and will instead be prefixed with `r_` (e.g. `r_self`).
     pub(crate) fn to_var_ident(&self) -> proc_macro2::Ident {
+        self.to_field_ident(FieldCase::Snake)
+    }
+    /// Converts the name to a valid Rust identifier using the given [FieldCase], automatically
+    /// escaping Rust keywords the same way [Name::to_var_ident] does.
+    pub(crate) fn to_field_ident(&self, field_case: FieldCase) -> proc_macro2::Ident {
         // List of Rust keywords that need escaping
         const RUST_KEYWORDS: &[&str] = &[
             // Keywords used in current Rust
@@ -227,7 +266,13 @@ impl Name {
             // Keywords that cannot be escaped with r#
             "crate", "self", "super",
         ];
-        let ident_str = self.to_case(CaseType::Snake);
+        let ident_str = match field_case {
+            FieldCase::Snake => self.to_case(CaseType::Snake),
+            FieldCase::Preserve => self
+                .name_override
+                .clone()
+                .unwrap_or_else(|| self.orig.clone()),
+        };
 
         if self.words.len() == 1 && RUST_INESCAPABLE_KEYWORDS.contains(&ident_str.as_str()) {
             proc_macro2::Ident::new(&format!("r_{}", ident_str), self.span)
@@ -237,6 +282,14 @@ impl Name {
             proc_macro2::Ident::new(&ident_str, self.span)
         }
     }
+    /// The `<type>_strategy` function of the generated type, in snake case, see `proptest`
+    pub(crate) fn to_strategy_fct_ident(&self) -> proc_macro2::Ident {
+        let type_name = Name::from(self.to_type_ident().to_string());
+        proc_macro2::Ident::new(
+            &format!("{}_strategy", type_name.to_case(CaseType::Snake)),
+            self.span,
+        )
+    }
     pub(crate) fn to_prefixed_fct_ident(&self, prefix: &str) -> proc_macro2::Ident {
         proc_macro2::Ident::new(
             &format!("{prefix}_{}", self.to_case(CaseType::Snake)),
@@ -275,6 +328,13 @@ impl OperationKind {
             Self::Subscription => "subscriptions",
         }
     }
+    pub(crate) fn resolvers_trait_name(self, span: Span) -> proc_macro2::Ident {
+        match self {
+            Self::Query => proc_macro2::Ident::new("QueryResolvers", span),
+            Self::Mutation => proc_macro2::Ident::new("MutationResolvers", span),
+            Self::Subscription => proc_macro2::Ident::new("SubscriptionResolvers", span),
+        }
+    }
     pub(crate) fn operation_enum_name(self, span: Span) -> proc_macro2::Ident {
         match self {
             Self::Query => proc_macro2::Ident::new("QueryField", span),
@@ -24,22 +24,194 @@ use proc_macro::TokenStream;
 /// - An absolute filesystem path (e.g. "/home/user/project/schema.graphql")
 /// - A relative path, that will be relative to your crate's root directory (e.g. "schema.graphql", "graphql/schema.gql")
 /// - When in a workspace context, the relative path will be relative to the workspace root directory
+/// - With `from_out_dir = true`, a relative path is relative to the `OUT_DIR` of the crate's build script,
+///   for schemas fetched or generated by a `build.rs` (e.g. `"schema.graphql"` for `$OUT_DIR/schema.graphql`)
 ///
 /// # Options
 ///
 /// - `batch = bool`: Enable/disable batch request handling (default: true)
-/// - `hook = fn_name`: Add a custom hook function for request validation/auth
+/// - `from_out_dir = bool`: Resolve a relative schema path from the `OUT_DIR` of the build script
+///   instead of the crate or workspace root (default: `false`)
+/// - `streaming = bool`: Return the response through the Lambda response streaming API, requires
+///   `batch = false`, see section below for details (default: `false`)
+/// - `hook = fn_name`: Add a custom hook function for request validation/auth, which must be an
+///   `async fn(&AppsyncEvent<Operation>) -> Option<AppsyncResponse>`. Its signature is checked even when
+///   the Lambda handler is not generated
+/// - `post_hook = fn_name`: Pass every response through a
+///   `fn(&AppsyncEventInfo<Operation>, &AppsyncIdentity, AppsyncResponse) -> AppsyncResponse` before it is
+///   returned, e.g. to scrub data or audit the outcome of each request, once per event in batch mode. It also
+///   receives the responses returned by the `hook`. As operations consume their event, the post hook receives
+///   a copy of its info and identity, made before the operation runs. Its signature is checked even when the
+///   Lambda handler is not generated
+/// - `strict_inputs = bool`: Reject unknown fields when deserializing GraphQL input types, by adding
+///   `#[serde(deny_unknown_fields)]` to the generated input structs, so that a client typo fails with an
+///   `InvalidArgs` error instead of being silently ignored (default: `false`). Output types and top-level
+///   operation arguments are not affected. As serde does not support `deny_unknown_fields` together with
+///   `#[serde(flatten)]`, do not combine it with a `type_override` to a type flattening its fields
+/// - `operation_timeout_ms = u64`: Bound the execution of every operation, an operation still running
+///   after this many milliseconds fails with a `Timeout` error (default: no timeout). See also the
+///   `timeout_ms` option of [appsync_operation]
+/// - `runtime_flavor = multi_thread|current_thread`: Flavor of the `tokio` runtime started by the generated
+///   `main` (default: `multi_thread`). A Lambda function handles one event at a time, `current_thread`
+///   runs it without spawning a worker thread per vCPU. The binary size does not change, `lambda_runtime`
+///   always enables the `rt-multi-thread` feature of `tokio`
+/// - `resolvers = Type`: Also generate the `QueryResolvers` and `MutationResolvers` traits, with one
+///   async method per operation taking its arguments, and dispatch the operations that have no
+///   [appsync_operation] handler to the implementation of these traits by `Type`. The methods that are
+///   not overridden return an `Unimplemented` error. `Type` must implement
+///   `Default + Send + Sync + 'static`, a single instance is created on the first call. Business logic
+///   written against the traits can be unit tested with a mock implementation. Subscriptions are not
+///   covered (default: none)
+/// - `warn_unimplemented = bool`: Emit a compiler warning for each query or mutation that has no
+///   [appsync_operation] handler (nor `resolvers`) and therefore always returns an `Unimplemented` error.
+///   The warning is a `deprecated` lint, `#![deny(deprecated)]` turns it into an error (default: `false`)
+/// - `on_error = fn_name`: Call a `fn(&AppsyncError, &Operation)` with every error returned by an operation,
+///   before it is converted into the response, e.g. to report errors to an alerting system
+/// - `middleware = fn_name`: Wrap every operation in an
+///   `async fn(AppsyncEvent<Operation>, Next<Operation>) -> AppsyncResponse`, which executes the operation
+///   with `next.run(event).await` and can run code around it, post-process the response or short-circuit
+///   the operation, see `lambda_appsync::middleware` (default: none)
+/// - `serialize_response = fn_name`: Serialize the response with a
+///   `fn(&R) -> Result<Vec<u8>, lambda_runtime::Error>` instead of `serde_json`, e.g. to use a faster
+///   serializer. `R` is `AppsyncResponse`, or `Vec<AppsyncResponse>` in batch mode, so a function generic
+///   over `R: Serialize` works in both cases. The bytes must be JSON: they are returned to the Lambda
+///   runtime as a `serde_json::value::RawValue`, which checks them once, or as the body of the stream with
+///   `streaming = true`. Compressing the response is out of scope: AppSync does not decompress Lambda
+///   responses
+/// - `event_adapter = fn_name`: Pass the payload received by the Lambda function through a
+///   `fn(serde_json::Value) -> Result<serde_json::Value, lambda_runtime::Error>` before deserializing it, e.g.
+///   to unwrap the AppSync event from the envelope of a proxy. It must return an AppSync event, or a list of
+///   them in batch mode; `capture_raw_event` captures the adapted events. An error fails the invocation
+///   (default: none, the payload is deserialized as is)
+/// - `health_check_op = "__health"`: Answer a `{"fieldName": "__health"}` payload, e.g. the smoke test of a
+///   deployment invoking the function directly, with
+///   `{"data": {"status": "healthy", "schemaHash": "...", "coldStart": true}}` without deserializing nor
+///   dispatching it. A probe sent in a single-element list is answered in a list, whatever the `batch` mode,
+///   and the answer does not go through `serialize_response`; `function_handler` then returns a
+///   `lambda_appsync::HandlerResponse`. `schemaHash` identifies the schema the function was compiled
+///   with and `coldStart` is `true` for the first invocation of the Lambda instance (default: none)
+/// - `correlation_header = "x-correlation-id"`: Read the correlation id of each AppSync request from this
+///   request header and add it to every log line of the default log initialization while handling the request,
+///   as a `correlation_id` field of the span of the request with the `tracing` feature, or after the level and
+///   target with `env_logger`. Resolvers and a custom `log_init` get it from
+///   `lambda_appsync::correlation::correlation_id()` (default: none)
 /// - `log_init = fn_name`: Use a custom log initialization function instead of the default one
+/// - `log_filter = "warn,my_crate=debug"`: Default filter of the default log initialization, used when the
+///   `RUST_LOG` environment variable is not set (default: `"info,tracing::span=warn"`). Ignored when
+///   `log_init` is used
+/// - `log_color = auto|always|never`: Whether the default log initialization writes ANSI colors, `auto`
+///   only coloring a terminal. Colors are handy locally but clutter CloudWatch logs. The `RUST_LOG_STYLE`
+///   environment variable still takes precedence with `env_logger` (default: `never`). Ignored when
+///   `log_init` is used
 /// - (feature: `log`) `event_logging = bool`: If true, the macro will generate code to dump the
 ///   lambda payload JSON as well as parsed `AppsyncEvent<Operation>`s in the logs at debug level (default: `false`)
+/// - `emit_registry = bool`: Also generate a `pub fn operation_registry() -> &'static [OperationMeta]`
+///   function describing every operation and its arguments, see `lambda_appsync::registry` (default: `false`)
+/// - `emit_type_name_map = bool`: Also generate a `pub const TYPE_NAME_MAP: &[(&str, &str)]` mapping the
+///   GraphQL name of each type, input and enum to the name of the generated Rust type, taking
+///   `name_override` and `namespace` into account (default: `false`)
+/// - `emit_field_enums = bool`: Also generate a `<Type>Field` enum for each type and input (e.g. `PlayerField`
+///   with `Id`, `Name` and `Team` variants), whose `as_str()` returns the GraphQL name of the field, to build
+///   projections without string typos. `<Type>Field::ALL` lists the fields and `<Type>Field::from_name` maps a
+///   GraphQL name, e.g. from `AppsyncEventInfo::selected_fields`, back to its field (default: `false`)
+/// - `lists_non_null = bool|strict`: Generate the lists with nullable elements (`[Player]`) as lists of
+///   required elements (`Vec<Player>` instead of `Vec<Option<Player>>`), for schemas sloppy about inner
+///   nullability. With `true`, the `null` elements of the outer list are skipped when deserializing
+///   arguments and fields; with `strict`, they fail the deserialization. Use it only when the lists never
+///   hold `null` in practice: a `null` sent by AppSync is silently dropped (or rejected with `strict`)
+///   rather than passed to the operation (default: `false`)
+/// - `emit_typescript = "path.d.ts"`: Write TypeScript declarations (aliases for the AWS and custom scalars,
+///   interfaces for types, interfaces and inputs, union types for unions and enums) of the schema to the
+///   given file during macro expansion, relative to the current directory like the schema path. Names are
+///   the GraphQL names, overrides are not applied and the file is only rewritten when its content changes
+/// - `emit_sdl = "path.graphql"`: Write the GraphQL schema as the macro interpreted it to the given file
+///   during macro expansion, relative to the current directory like the schema path, to diff it against the
+///   input schema. It has the operations, types, inputs and enums the macro uses with their GraphQL names
+///   and types (overrides are not applied); the root types are named `Query`, `Mutation` and `Subscription`.
+///   Anything the macro ignores, like directives, descriptions, unions or interfaces, is missing. The file
+///   is only rewritten when its content changes
+/// - `typed_filters = bool`: Also generate a `subscription_fields` module with, for each subscription, a
+///   function per field of its return type returning a `TypedFieldPath` that only accepts values of the
+///   field type. Only scalar and enum fields (or lists of them) are covered. Each module also aliases the
+///   published type as `PayloadType` and lists its GraphQL field names in `FIELDS` (default: `false`)
+/// - `field_case = snake|preserve`: Rust naming of the fields of the generated structs, either converted
+///   to snake case or kept as written in the GraphQL schema. Rust keywords are escaped in both cases and
+///   `serde` always uses the schema name (default: `snake`)
+/// - `namespace = ident`: Prefix the names of the generated types, inputs and enums with the PascalCase
+///   version of `ident` (e.g. `namespace = admin` generates `AdminPlayer`), so that several schemas can be
+///   used in the same crate. Names changed with `name_override` are not prefixed. The operation enums and the
+///   Lambda handler are not namespaced: only one invocation per crate can generate them
+/// - `emf_metrics = bool`: Print an AWS Embedded Metric Format record to the standard output for every
+///   operation handled, which CloudWatch turns into metrics without any other infrastructure. The metrics
+///   are `Latency` (milliseconds, hooks and middleware included) and `Count`, in the `LambdaAppsync`
+///   namespace, with the dimension sets `Operation` (e.g. `Query.players`) and `Operation, ErrorType`
+///   (`None` for the operations that succeeded), see `lambda_appsync::emf_metrics_record` (default: `false`)
+/// - `capture_raw_event = bool`: Keep a copy of the raw JSON event sent by AppSync in the `raw` field of
+///   each `AppsyncEvent`, useful to access fields the typed event does not model. This doubles the memory
+///   used by the events (default: `false`)
+/// - `args_source = arguments|stash|prev_result`: Where the operation arguments are read from in each event:
+///   the standard `arguments`, the pipeline `stash` or the result of the previous function of the pipeline
+///   (`prev.result`), useful when the Lambda is a pipeline step consuming the output of a prior function.
+///   The source replaces the `arguments` of the event before deserialization, also in the captured raw event
+///   (default: `arguments`)
+/// - `args_may_be_string = bool`: Parse the arguments of each event when they arrive as a JSON-encoded
+///   string, e.g. double-encoded by a VTL template, instead of failing with "expected object, found
+///   string" errors. Strings that are not JSON are left as is (default: `false`)
+/// - `federation_keys = bool`: Read the `@key(fields: "id")` directives of the GraphQL types (as in Apollo
+///   Federation) and generate a `key()` method returning a reference to the key field, or a tuple of
+///   references for a compound key like `@key(fields: "gameId playerId")`. Only the first `@key` of a type
+///   is used and nested fields are not supported (default: `false`)
+/// - `generate_patch = Type`: Also generate a `TypePatch` struct for the given GraphQL type or input, with
+///   all its fields optional, and a `Type::apply_patch(&mut self, patch: TypePatch)` method overwriting only
+///   the fields present in the patch. Nullable fields are `Nullable` in the patch, so that "don't touch" and
+///   "set to null" can be told apart. Can be repeated for several types
+/// - `required_override = Type.field` or `required_override = Type.field: default`: Generate a field that is
+///   nullable in the schema as required in Rust, e.g. `Team` instead of `Option<Team>` for a field the
+///   resolvers always set. With `: default`, an absent or `null` value is deserialized as the
+///   `Default::default()` of the field type (which must implement `Default`). Without it, an absent or `null`
+///   value fails the deserialization of the whole type or input, and therefore the operation receiving it.
+///   The schema is not changed: AppSync can still send `null` for such a field, and the other clients still
+///   see it as nullable. Can be repeated for several fields
+/// - `skip_serialize = Type.field`: Add `#[serde(skip_serializing)]` to a field of a type or input, e.g. for
+///   internal state computed server-side: the field is still deserialized but never sent back. Only accepts
+///   optional fields, as AppSync fails a response missing a non-null field. Can be repeated for several fields
+/// - `serde_rename_all = Type: snake_case`: Add `#[serde(rename_all = "snake_case")]` to a type or input, so
+///   that its fields are (de)serialized with this casing of their Rust name instead of their GraphQL name, e.g.
+///   to reuse the type with a store using snake_case attributes. The fields with a `name_override` keep their
+///   GraphQL name. Accepts the casings of serde, the kebab ones as strings (e.g. `Type: "kebab-case"`). Can be
+///   repeated for several types
+/// - `enum_discriminants = bool`: Give the generated enums `#[repr(i32)]` and discriminants following their
+///   declaration order in the schema, and generate `From<Enum> for i32` and `TryFrom<i32> for Enum`, e.g. to
+///   store them as integers. Also generate `Enum::from_ordinal(u32) -> Option<Enum>` and
+///   `Enum::to_ordinal(self) -> u32`, for stores keeping enums as their ordinal. The serde representation stays
+///   the GraphQL name (default: `false`). Integers and ordinals only stay valid as long as the values of the
+///   enum are not reordered, inserted or removed in the schema: only append new values to the end
+/// - (feature: `arbitrary`) `arbitrary = bool`: Also implement `arbitrary::Arbitrary` for the generated types,
+///   inputs and enums, e.g. to drive fuzzers and property tests with random instances of the operation
+///   arguments. Enums pick one of their values and scalars use the `Arbitrary` implementation of their Rust type.
+///   The types of `type_override`s must implement `Arbitrary` too (default: `false`)
+/// - (feature: `proptest`) `proptest = bool`: Also generate a `pub fn player_strategy() -> BoxedStrategy<Player>`
+///   function per generated type, input and enum (named after the Rust type), returning a proptest strategy
+///   composed of the strategies of the fields, for property-based testing. Scalars use their
+///   `proptest::arbitrary::Arbitrary` implementation, except `Float` which stays within `-1e9..1e9`, and the
+///   types of `type_override`s must implement it too. As a type can refer to itself through a list, lists of
+///   types are nested at most twice, `player_strategy_with_depth(depth: u32)` changes this limit (default: `false`)
 /// - `exclude_lambda_handler = bool`: Skip generation of Lambda handler code
 /// - `only_lambda_handler = bool`: Only generate Lambda handler code
 /// - `exclude_appsync_types = bool`: Skip generation of GraphQL type definitions
 /// - `only_appsync_types = bool`: Only generate GraphQL type definitions
 /// - `exclude_appsync_operations = bool`: Skip generation of operation enums
 /// - `only_appsync_operations = bool`: Only generate operation enums
+/// - `only_types = [Type, ...]`: Only generate the given GraphQL types, enums or inputs and the types
+///   their fields refer to, transitively (e.g. `only_types = [Player]` also generates the `Team` enum
+///   of its `team` field). Like `only_appsync_types`, no operation nor Lambda handler is generated
+/// - `validate_schema = bool`: Only parse the schema and run the checks of the other options (overrides,
+///   `operation_cfg`, `nullable_arg`...), without generating any code. A dedicated crate or test can use
+///   it to fail the build on a malformed or unsupported schema
 /// - `type_override` - see section below for details
 /// - `name_override` - see section below for details
+/// - `operation_cfg` - see section below for details
+/// - `nullable_arg` - see section below for details
 /// - `field_type_override` (Deprecated): Same as `type_override`
 ///
 /// ## Type Overrides
@@ -53,6 +225,10 @@ use proc_macro::TokenStream;
 /// These overrides are only for the Rust code and must be compatible for serialization/deserialization purposes,
 /// i.e. you can use `String` for a GraphQL `ID` but you cannot use a `u32` for a GraphQL `Float`.
 ///
+/// The override types must implement the `serde` traits needed by the generated code: `Serialize` and
+/// `Deserialize` for type and input fields, `Serialize` for operation return types and `Deserialize` for
+/// operation arguments. A missing implementation is reported at the override site.
+///
 /// ## Name Overrides
 ///
 /// The `name_override` option supports renaming various schema elements:
@@ -64,6 +240,53 @@ use proc_macro::TokenStream;
 /// These overrides are only for the Rust code and will not change serialization/deserialization,
 /// i.e. `serde` will rename to the original GraphQL schema name.
 ///
+/// Renaming a type also renames it in the fields and operations using it, unless they have their
+/// own `type_override`.
+///
+/// ## Enums
+///
+/// The variants of the generated enums are named in PascalCase (e.g. `MULTI_WORDS_TEAM` becomes
+/// `MultiWordsTeam`) but keep their GraphQL name everywhere else: `serde`, `Display` and `FromStr` all
+/// use `MULTI_WORDS_TEAM`. Serializing and displaying intentionally differ by the JSON quotes only:
+/// `serde_json::to_string(&Team::Rust)` is `"\"RUST\""` while `Team::Rust.to_string()` is `"RUST"`.
+///
+/// ## Operation cfg
+///
+/// The `operation_cfg` option puts an operation behind a `cfg` predicate:
+///
+/// - `operation_cfg = OpType.operation: feature = "admin"`
+/// - `operation_cfg = OpType.operation: any(feature = "admin", test)`
+///
+/// The generated enum variant, dispatch arm and default implementation of the operation are only
+/// compiled when the predicate holds. This allows several binaries to share the same schema while
+/// each one only compiles the operations it serves. When compiled out, the operation is unknown to
+/// the generated code and requests for it fail to deserialize.
+///
+/// The corresponding [appsync_operation] function must be gated with the same `#[cfg(...)]`.
+///
+/// ## Nullable arguments
+///
+/// By default, an optional argument is extracted as an `Option<T>`, which cannot tell an absent argument
+/// from an argument explicitly set to `null`. For partial updates, where "don't touch" and "set to null"
+/// differ, the `nullable_arg` option extracts an optional argument as a `lambda_appsync::Nullable<T>` instead:
+///
+/// - `nullable_arg = OpType.operation.arg`
+///
+/// The corresponding [appsync_operation] function must take a `Nullable<T>` for this argument.
+/// Required (non-null) arguments cannot be selected.
+///
+/// ## Response streaming
+///
+/// With `streaming = true` (and `batch = false`), the generated `function_handler` returns a
+/// `lambda_runtime::streaming::Response` instead of the buffered response, so that the Lambda runtime
+/// sends the serialized `AppsyncResponse` using the response streaming protocol.
+///
+/// Note that AWS AppSync invokes Direct Lambda resolvers with the regular `Invoke` API and does not consume
+/// response streams: for AppSync, Lambda buffers the streamed response and the usual payload limits apply.
+/// No specific configuration is needed on the AppSync data source. Streaming is only beneficial if the same
+/// function is also invoked with `InvokeWithResponseStream` or through a function URL configured with
+/// the `RESPONSE_STREAM` invoke mode.
+///
 /// # AWS SDK Clients
 ///
 /// AWS SDK clients can be initialized by providing function definitions that return a cached SDK client type.
@@ -117,6 +340,36 @@ use proc_macro::TokenStream;
 /// # fn main() {}
 /// ```
 ///
+/// Creating a client inside a resolver loads a new configuration and opens new connections on every
+/// invocation, defeating the purpose of the cache. When at least one client is declared, the macro also
+/// generates an `aws_sdk_config()` function returning the `&'static SdkConfig` loaded once at startup.
+/// Clients that are not declared in the macro should be built from it and cached the same way:
+/// ```no_run
+/// # fn aws_sdk_config() -> &'static lambda_appsync::aws_config::SdkConfig {
+/// #   todo!()
+/// # }
+/// # mod sub {
+/// use crate::aws_sdk_config;
+/// use std::sync::OnceLock;
+///
+/// fn custom_s3() -> aws_sdk_s3::Client {
+///     static CLIENT: OnceLock<aws_sdk_s3::Client> = OnceLock::new();
+///     CLIENT
+///         .get_or_init(|| {
+///             let config = aws_sdk_s3::config::Builder::from(aws_sdk_config())
+///                 .force_path_style(true)
+///                 .build();
+///             aws_sdk_s3::Client::from_conf(config)
+///         })
+///         .clone()
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// With the `testing` feature, `lambda_appsync::assert_client_cached!(custom_s3)` checks in a test that
+/// such a getter returns a cached client.
+///
 /// # Examples
 ///
 /// ## Basic usage with authentication hook:
@@ -150,6 +403,48 @@ use proc_macro::TokenStream;
 /// # fn main() {}
 /// ```
 ///
+/// ## Exhaustive custom dispatch:
+/// The generated `Operation` enum and its `QueryField`, `MutationField` and `SubscriptionField`
+/// enums are not `#[non_exhaustive]`: a `match` without wildcard arm is checked for exhaustiveness,
+/// so adding an operation to the schema fails to compile until the new operation is handled.
+/// ```no_run
+/// # mod sub {
+/// use lambda_appsync::{appsync_lambda_main, AppsyncEvent, AppsyncResponse};
+///
+/// enum Datastore {
+///     Primary,
+///     ReadReplica,
+/// }
+///
+/// // No `_ =>` arm: a new schema operation is a compile error here
+/// fn datastore(operation: Operation) -> Datastore {
+///     match operation {
+///         Operation::Query(QueryField::Players | QueryField::GameStatus | QueryField::Player) => {
+///             Datastore::ReadReplica
+///         }
+///         Operation::Mutation(
+///             MutationField::CreatePlayer
+///             | MutationField::DeletePlayer
+///             | MutationField::SetGameStatus,
+///         ) => Datastore::Primary,
+///         Operation::Subscription(
+///             SubscriptionField::OnCreatePlayer
+///             | SubscriptionField::OnDeletePlayer
+///             | SubscriptionField::OnGameStatusChange,
+///         ) => Datastore::Primary,
+///     }
+/// }
+///
+/// async fn hook(event: &AppsyncEvent<Operation>) -> Option<AppsyncResponse> {
+///     let _datastore = datastore(event.info.operation);
+///     None
+/// }
+///
+/// appsync_lambda_main!("schema.graphql", hook = hook);
+/// # }
+/// # fn main() {}
+/// ```
+///
 /// ## Generate only types for lib code generation:
 /// ```no_run
 /// # mod sub {
@@ -197,11 +492,7 @@ use proc_macro::TokenStream;
 ///     name_override = Player.name: email,
 ///     // Override team `PYTHON` to be `Snake` (instead of `Python`)
 ///     name_override = Team.PYTHON: Snake,
-///     // MUST also override ALL the operations return type !!!
-///     type_override = Query.players: NewPlayer,
-///     type_override = Query.player: NewPlayer,
-///     type_override = Mutation.createPlayer: NewPlayer,
-///     type_override = Mutation.deletePlayer: NewPlayer,
+///     // The operations returning a `Player` now return a `NewPlayer`
 /// );
 /// # }
 /// # fn main() {}
@@ -286,7 +577,7 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
     appsync_lambda_main::appsync_lambda_main_impl(input)
 }
 
-/// Marks an async function as an AWS AppSync resolver operation, binding it to a specific Query,
+/// Marks a function as an AWS AppSync resolver operation, binding it to a specific Query,
 /// Mutation or Subscription operation defined in the GraphQL schema.
 ///
 /// The marked function must match the signature of the GraphQL operation, with parameters and return
@@ -298,6 +589,23 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 /// root level (typically in `main.rs`). The code generated by this macro depends on types and
 /// implementations that are created by [appsync_lambda_main!].
 ///
+/// An attribute macro cannot inspect the rest of the crate, so forgetting [appsync_lambda_main!]
+/// (or invoking it inside a sub-module) cannot be reported directly. Everything the operation uses
+/// is reached through the `__appsync_lambda_main` module generated by [appsync_lambda_main!], so the
+/// compiler reports a single error naming it:
+///
+/// ```text
+/// error[E0432]: unresolved import `crate`
+///  --> src/main.rs:3:27
+///   |
+/// 3 | #[appsync_operation(query(players))]
+///   |                           ^^^^^^^ no `__appsync_lambda_main` in the root
+/// ```
+///
+/// If you see this error, make sure [appsync_lambda_main!] is invoked at the root of the crate
+/// (`main.rs` or `lib.rs`) and that it does not use `exclude_appsync_operations = true` or
+/// `only_appsync_types = true`.
+///
 /// # Example Usage
 ///
 /// ```no_run
@@ -367,14 +675,41 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 /// # fn main() {}
 /// ```
 ///
-/// Note that the `args` field of the [AppsyncEvent](struct.AppsyncEvent.html) will always contain
+/// Note that the `args` field of the [AppsyncEvent](struct.AppsyncEvent.html) will contain
 /// [Null](https://docs.rs/serde_json/latest/serde_json/enum.Value.html#variant.Null) at this stage because its initial content is taken to extract
 /// the argument values for the operation.
 ///
+/// Adding the `keep_raw_args` flag, which requires `with_appsync_event`, extracts the arguments from a clone
+/// of `args` instead, so the handler gets both the typed arguments and their original JSON. This costs a deep
+/// copy of the arguments for every call of the operation:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, AppsyncEvent};
+/// use crate::{Operation, Player};
+///
+/// #[appsync_operation(mutation(createPlayer), with_appsync_event, keep_raw_args)]
+/// async fn create_player(name: String, event: &AppsyncEvent<Operation>) -> Result<Player, AppsyncError> {
+///     // The original arguments, e.g. `{"name": "..."}`
+///     println!("createPlayer called with {}", event.args);
+///     todo!()
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
 /// ## Preserve original function name
 ///
-/// By default the [macro@appsync_operation] macro will discard your function's name but
-/// you can also keep it available by adding the `keep_original_function_name` flag:
+/// By default the [macro@appsync_operation] macro will discard your function's name: the operation
+/// becomes a method of the generated `Operation` enum named after the kind and the snake case name of
+/// the operation (e.g. `Operation::query_players`, `Operation::mutation_create_player`).
+/// These names cannot collide between kinds and [appsync_lambda_main!] rejects schemas where two operations
+/// of the same kind would produce the same method (e.g. `getPlayer` and `get_player`).
+///
+/// You can also keep your function available under its own name by adding the `keep_original_function_name` flag:
 /// ```no_run
 /// # lambda_appsync::appsync_lambda_main!(
 /// #    "schema.graphql",
@@ -402,6 +737,248 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 /// # fn main() {}
 /// ```
 ///
+/// ## Synchronous operations
+///
+/// The marked function does not have to be `async`. For resolvers that never await anything,
+/// a plain function can be used and the macro takes care of the integration with the async
+/// dispatch:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError};
+///
+/// // Your types are declared at the crate level by the appsync_lambda_main! macro
+/// use crate::GameStatus;
+///
+/// #[appsync_operation(query(gameStatus))]
+/// fn get_game_status() -> Result<GameStatus, AppsyncError> {
+///     Ok(GameStatus::Started)
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Returning raw JSON
+///
+/// With the `raw_return` option, a query or mutation may return a `serde_json::Value` instead of the
+/// type derived from the schema, for shapes that do not map to a generated type (e.g. aggregations or
+/// `AWSJSON` fields) or during a migration. The arguments are still checked against the schema but the
+/// returned value is not: it is sent to AppSync as-is and must match what the schema declares.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, serde_json::{json, Value}, AppsyncError, ID};
+///
+/// #[appsync_operation(query(player), raw_return)]
+/// async fn get_player(id: ID) -> Result<Value, AppsyncError> {
+///     Ok(json!({"id": id, "name": "Ferris", "team": "RUST"}))
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Returning a serializable type
+///
+/// With the `loose_return` option, a query or mutation may return any type implementing `Serialize`
+/// instead of the type derived from the schema, e.g. a domain type or a view model whose JSON has the
+/// same shape. Like with `raw_return`, the returned value is not checked against the schema.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, serde::Serialize, AppsyncError, ID};
+///
+/// #[derive(Serialize)]
+/// # #[serde(crate = "lambda_appsync::serde")]
+/// struct PlayerView {
+///     id: ID,
+///     name: &'static str,
+///     team: &'static str,
+/// }
+///
+/// #[appsync_operation(query(player), loose_return)]
+/// async fn get_player(id: ID) -> Result<PlayerView, AppsyncError> {
+///     Ok(PlayerView { id, name: "Ferris", team: "RUST" })
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Taking the arguments as a struct
+///
+/// With the `args_struct` option, the function takes a single parameter gathering all the arguments of
+/// the operation instead of one parameter per argument. The struct is generated by
+/// [appsync_lambda_main!] for every operation, in the `operation_args::queries`, `operation_args::mutations`
+/// and `operation_args::subscriptions` modules, named after the operation with an `Args` suffix and
+/// with one public field per argument. With `with_appsync_event`, the event is still the last parameter.
+///
+/// The struct implements [Debug] and [Clone], so it can be used with `retry`. The `validate` and `cache_key`
+/// options then name fields of the struct, e.g. `validate(name = non_empty)` calls `non_empty(&args.name)`,
+/// and the `_cache_key` function takes a reference to the struct.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, ID};
+/// use crate::{operation_args::mutations::CreatePlayerArgs, Player, Team};
+///
+/// #[appsync_operation(mutation(createPlayer), args_struct)]
+/// async fn create_player(args: CreatePlayerArgs) -> Result<Player, AppsyncError> {
+///     Ok(Player { id: ID::new(), name: args.name, team: Team::Rust })
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Retrying transient errors
+///
+/// The `retry = { max: N, base_ms: M }` option runs the operation again, up to `N` more times, while it
+/// fails with a retryable error, waiting `M * 2^attempt` milliseconds before each new attempt (`base_ms`
+/// defaults to 50). Errors are retryable according to [AppsyncError::is_retryable](struct.AppsyncError.html#method.is_retryable)
+/// (throttling and transient AWS errors), or to a custom `fn(&AppsyncError) -> bool` given as `retryable: path`.
+///
+/// Each attempt receives a clone of the arguments, so they must implement [Clone].
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// # async fn dynamodb_get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+/// #    todo!()
+/// # }
+/// use lambda_appsync::{appsync_operation, AppsyncError, ID};
+/// use crate::Player;
+///
+/// #[appsync_operation(query(player), retry = { max: 3, base_ms: 50 })]
+/// async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+///     dynamodb_get_player(id).await
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Timeouts
+///
+/// The `timeout_ms = N` option fails the operation with a `Timeout` error if it is still running after
+/// `N` milliseconds, retries included. It takes precedence over the global `operation_timeout_ms` option
+/// of [appsync_lambda_main!] when shorter. As with any `tokio` timeout, the operation can only be
+/// interrupted when it awaits, so a synchronous operation always runs to completion.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// # async fn dynamodb_get_players() -> Result<Vec<Player>, AppsyncError> {
+/// #    todo!()
+/// # }
+/// use lambda_appsync::{appsync_operation, AppsyncError};
+/// use crate::Player;
+///
+/// #[appsync_operation(query(players), timeout_ms = 2000)]
+/// async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+///     dynamodb_get_players().await
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Validating arguments
+///
+/// The `validate(arg = validator, ...)` option calls `validator(&arg)` after the arguments are
+/// extracted and before the operation body runs. A validator returns `Result<(), E>` with `E: Into<String>`;
+/// an `Err(message)` fails the operation with a `BadRequest` error carrying `message`. An argument may
+/// be listed several times, its validators are called in order.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, ID};
+/// use crate::{Player, Team};
+///
+/// fn non_empty(value: &str) -> Result<(), String> {
+///     if value.trim().is_empty() {
+///         Err("The name cannot be empty".to_owned())
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// #[appsync_operation(mutation(createPlayer), validate(name = non_empty))]
+/// async fn create_player(name: String) -> Result<Player, AppsyncError> {
+///     Ok(Player { id: ID::new(), name, team: Team::Rust })
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Caching
+///
+/// The `cache_key(arg, ...)` option generates an associated function of `Operation` named after the
+/// operation method with a `_cache_key` suffix. It takes references to the listed arguments and returns a
+/// stable key made of the GraphQL type and field names followed by each argument, separated with `:` (e.g.
+/// `Query.player:<id>`). Arguments are formatted with [cache_key_part](../lambda_appsync/fn.cache_key_part.html),
+/// an argument that cannot be serialized to JSON failing with an `InvalidCacheKey` error.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, ID};
+/// use crate::{Operation, Player};
+///
+/// #[appsync_operation(query(player), cache_key(id))]
+/// async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+///     let _key = Operation::query_player_cache_key(&id)?;
+///     // Lookup `_key` in a cache before hitting the database...
+///     todo!()
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Opting out of concurrent batch processing
+///
+/// With `batch = true` (the default), the events of a batch are handled concurrently. The `no_batch` flag
+/// makes the events of an operation run one after the other instead, e.g. for a heavy report query that
+/// would contend for resources: they are still answered in the same batch response, while the other events
+/// of the batch keep running concurrently. There is no global concurrency limit for the batches, `no_batch`
+/// only serializes the events of the flagged operations. `Operation::is_no_batch` tells whether an operation
+/// is flagged, e.g. for a custom batch handler. The flag has no effect with `batch = false`.
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// # async fn dynamodb_scan_players() -> Result<Vec<Player>, AppsyncError> {
+/// #    todo!()
+/// # }
+/// use lambda_appsync::{appsync_operation, AppsyncError};
+/// use crate::Player;
+///
+/// #[appsync_operation(query(players), no_batch)]
+/// async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+///     dynamodb_scan_players().await
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
 /// ## Using enhanced subscription filters
 ///
 /// ```no_run
@@ -440,6 +1017,43 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 /// # fn main() {}
 /// ```
 ///
+/// A subscription can also return an `Option<SubscriptionFilters>`, combining several
+/// [FilterGroup](subscription_filters/struct.FilterGroup.html)s with OR logic, for instance one per authorization rule:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # use lambda_appsync::{appsync_operation, AppsyncError, ID};
+/// # use lambda_appsync::subscription_filters::{FilterGroup, FieldPath, SubscriptionFilters};
+/// #[appsync_operation(subscription(onDeletePlayer))]
+/// async fn on_delete_player(id: ID) -> Result<Option<SubscriptionFilters>, AppsyncError> {
+///     Ok(Some(SubscriptionFilters::new([
+///         FilterGroup::from(FieldPath::new("id")?.eq(id)),
+///         FilterGroup::from(FieldPath::new("team")?.eq("RUST")),
+///     ])?))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// With the `typed_filters = true` option of [appsync_lambda_main!], the field paths can be checked
+/// against the field types at compile time:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// #     typed_filters = true,
+/// # );
+/// # use lambda_appsync::{appsync_operation, AppsyncError};
+/// # use lambda_appsync::subscription_filters::FilterGroup;
+/// #[appsync_operation(subscription(onCreatePlayer))]
+/// async fn on_create_player(name: String) -> Result<Option<FilterGroup>, AppsyncError> {
+///     // `name()` returns a `TypedFieldPath<String>`, `.eq(42)` would not compile
+///     Ok(Some(subscription_fields::on_create_player::name().contains(name).into()))
+/// }
+/// # fn main() {}
+/// ```
+///
 /// ### Important Note
 ///
 /// When using enhanced subscription filters (i.e., returning a [FilterGroup](subscription_filters/struct.FilterGroup.html)
@@ -0,0 +1,8 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/colliding_operations.graphql",
+    exclude_lambda_handler = true,
+);
+
+fn main() {}
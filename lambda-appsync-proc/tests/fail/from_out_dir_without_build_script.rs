@@ -0,0 +1,6 @@
+use lambda_appsync::appsync_lambda_main;
+
+// There is no build script, so OUT_DIR is not set
+appsync_lambda_main!("schema.graphql", from_out_dir = true);
+
+fn main() {}
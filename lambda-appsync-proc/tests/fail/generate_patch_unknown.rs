@@ -0,0 +1,3 @@
+lambda_appsync::appsync_lambda_main!("../../../../schema.graphql", generate_patch = Team);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation};
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+#[appsync_operation(mutation(createPlayer), args_struct)]
+async fn create_player(name: String, team: Team) -> Result<Player, lambda_appsync::AppsyncError> {
+    todo!()
+}
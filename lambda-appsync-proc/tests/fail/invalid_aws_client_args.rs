@@ -0,0 +1,8 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    dynamodb(region) -> aws_sdk_dynamodb::Client,
+);
+
+fn main() {}
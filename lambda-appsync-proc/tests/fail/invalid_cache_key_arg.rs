@@ -0,0 +1,8 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation};
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+#[appsync_operation(query(player), cache_key(player_id))]
+async fn get_player(id: lambda_appsync::ID) -> Result<Option<Player>, lambda_appsync::AppsyncError> {
+    todo!()
+}
@@ -0,0 +1,9 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/invalid_federation_keys.graphql",
+    exclude_lambda_handler = true,
+    federation_keys = true,
+);
+
+fn main() {}
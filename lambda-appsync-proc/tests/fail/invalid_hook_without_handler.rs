@@ -0,0 +1,13 @@
+use lambda_appsync::{appsync_lambda_main, AppsyncResponse};
+
+// The hook signature is checked even when the Lambda handler is not generated
+async fn verify_request(_event: &Operation) -> Option<AppsyncResponse> {
+    None
+}
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    hook = verify_request
+);
+
+fn main() {}
@@ -0,0 +1,14 @@
+use lambda_appsync::{appsync_lambda_main, AppsyncEvent, AppsyncResponse};
+
+// Missing the `Next` continuation
+async fn middleware(_event: AppsyncEvent<Operation>) -> AppsyncResponse {
+    AppsyncResponse::unauthorized()
+}
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    middleware = middleware,
+);
+
+fn main() {}
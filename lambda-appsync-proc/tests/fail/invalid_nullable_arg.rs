@@ -0,0 +1,14 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    // Required argument
+    nullable_arg = Query.player.id,
+    // Unknown argument
+    nullable_arg = Mutation.createPlayer.team,
+    // Unknown operation
+    nullable_arg = Mutation.updatePlayer.name,
+);
+
+fn main() {}
@@ -0,0 +1,5 @@
+use lambda_appsync::{appsync_lambda_main, AppsyncError};
+
+fn report_error(_e: AppsyncError) {}
+
+appsync_lambda_main!("../../../../schema.graphql", on_error = report_error);
@@ -0,0 +1,9 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    operation_cfg = Mutation.invalidMutation: feature = "admin",
+);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use lambda_appsync::{appsync_lambda_main, AppsyncEventInfo, AppsyncIdentity, AppsyncResponse};
+
+// Must take the response by value and return it
+fn finalize(
+    _info: &AppsyncEventInfo<Operation>,
+    _identity: &AppsyncIdentity,
+    _response: &AppsyncResponse,
+) {
+}
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    post_hook = finalize,
+);
+
+fn main() {}
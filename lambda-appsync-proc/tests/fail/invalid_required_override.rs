@@ -0,0 +1,14 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    // Required field
+    required_override = Player.name,
+    // Unknown field
+    required_override = Player.score,
+    // Unknown type
+    required_override = Game.winner,
+);
+
+fn main() {}
@@ -0,0 +1,7 @@
+lambda_appsync::appsync_lambda_main!(
+    "../../../../schema.graphql",
+    only_appsync_types = true,
+    serde_rename_all = Player: snakecase,
+);
+
+fn main() {}
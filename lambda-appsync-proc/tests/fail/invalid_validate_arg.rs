@@ -0,0 +1,16 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation};
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+fn non_empty(value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        Err("empty".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+#[appsync_operation(mutation(createPlayer), validate(nickname = non_empty))]
+async fn create_player(name: String) -> Result<Player, lambda_appsync::AppsyncError> {
+    todo!()
+}
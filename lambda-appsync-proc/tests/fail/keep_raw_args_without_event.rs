@@ -0,0 +1,8 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation};
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+#[appsync_operation(mutation(createPlayer), keep_raw_args)]
+async fn create_player(name: String) -> Result<Player, lambda_appsync::AppsyncError> {
+    todo!()
+}
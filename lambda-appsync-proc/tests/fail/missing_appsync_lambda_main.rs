@@ -0,0 +1,8 @@
+use lambda_appsync::{appsync_operation, AppsyncError};
+
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<String>, AppsyncError> {
+    Ok(vec![])
+}
+
+fn main() {}
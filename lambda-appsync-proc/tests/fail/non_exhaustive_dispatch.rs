@@ -0,0 +1,11 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+// The generated enums are exhaustive: forgetting `QueryField::Player` is a compile error
+pub fn is_read(operation: Operation) -> bool {
+    match operation {
+        Operation::Query(QueryField::Players | QueryField::GameStatus) => true,
+        Operation::Mutation(_) | Operation::Subscription(_) => false,
+    }
+}
@@ -0,0 +1,3 @@
+lambda_appsync::appsync_lambda_main!("../../../../schema.graphql", only_types = [Player, Unknown]);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation};
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+#[appsync_operation(subscription(onGameStatusChange), raw_return)]
+async fn on_game_status_change(
+) -> Result<lambda_appsync::serde_json::Value, lambda_appsync::AppsyncError> {
+    Ok(lambda_appsync::serde_json::Value::Null)
+}
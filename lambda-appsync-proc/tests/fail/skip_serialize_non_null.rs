@@ -0,0 +1,7 @@
+lambda_appsync::appsync_lambda_main!(
+    "../../../../schema.graphql",
+    only_appsync_types = true,
+    skip_serialize = PlayerStats.gamesPlayed,
+);
+
+fn main() {}
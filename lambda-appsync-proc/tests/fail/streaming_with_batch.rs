@@ -0,0 +1,5 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!("../../../../schema.graphql", streaming = true);
+
+fn main() {}
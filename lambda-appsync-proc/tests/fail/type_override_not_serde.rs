@@ -0,0 +1,11 @@
+use lambda_appsync::appsync_lambda_main;
+
+pub struct NotSerde;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    type_override = Query.gameStatus: NotSerde,
+);
+
+fn main() {}
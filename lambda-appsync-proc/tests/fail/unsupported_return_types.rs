@@ -0,0 +1,10 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/unsupported_return_types.graphql",
+    exclude_lambda_handler = true,
+    // Overridden return types are supported
+    type_override = Query.nextCursor: String,
+);
+
+fn main() {}
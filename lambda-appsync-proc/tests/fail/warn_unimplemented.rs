@@ -0,0 +1,25 @@
+#![deny(deprecated)]
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+appsync_lambda_main!("../../../../schema.graphql", warn_unimplemented = true);
+
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    todo!()
+}
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    todo!()
+}
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    todo!("{id}")
+}
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    todo!("{name}")
+}
+#[appsync_operation(mutation(deletePlayer))]
+async fn delete_player(id: ID) -> Result<Player, AppsyncError> {
+    todo!("{id}")
+}
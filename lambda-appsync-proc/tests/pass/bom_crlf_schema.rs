@@ -0,0 +1,16 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+// The schema starts with a UTF-8 BOM and uses CRLF line endings
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/bom_crlf.graphql",
+    exclude_lambda_handler = true
+);
+fn main() {}
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(Some(Player {
+        id,
+        name: "Ferris".to_owned(),
+    }))
+}
@@ -0,0 +1,20 @@
+use lambda_appsync::appsync_lambda_main;
+
+// The argument and input field descriptions become doc comments
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/descriptions.graphql",
+    exclude_lambda_handler = true,
+);
+
+fn main() {
+    let args = operation_args::queries::PlayerArgs {
+        id: lambda_appsync::ID::new(),
+        with_team: Some(true),
+    };
+    assert_eq!(args.with_team, Some(true));
+    let filter = PlayerFilter {
+        name_prefix: Some("Fer".to_owned()),
+        limit: None,
+    };
+    assert_eq!(filter.name_prefix.as_deref(), Some("Fer"));
+}
@@ -0,0 +1,23 @@
+// The adapter and the response serializer can be combined, also with streaming
+mod no_run {
+    use lambda_appsync::{appsync_lambda_main, lambda_runtime, serde_json::Value};
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        batch = false,
+        streaming = true,
+        event_adapter = unwrap_event,
+        serialize_response = serialize_response
+    );
+
+    fn unwrap_event(mut payload: Value) -> Result<Value, lambda_runtime::Error> {
+        Ok(payload["event"].take())
+    }
+
+    fn serialize_response<T: lambda_appsync::serde::Serialize>(
+        response: &T,
+    ) -> Result<Vec<u8>, lambda_runtime::Error> {
+        Ok(lambda_appsync::serde_json::to_vec(response)?)
+    }
+}
+
+fn main() {}
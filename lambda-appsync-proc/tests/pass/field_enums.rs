@@ -0,0 +1,25 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    only_appsync_types = true,
+    emit_field_enums = true,
+    name_override = PlayerStats.winRate: ratio,
+);
+
+fn main() {
+    assert_eq!(
+        PlayerField::ALL,
+        [PlayerField::Id, PlayerField::Name, PlayerField::Team]
+    );
+    assert_eq!(PlayerField::Team.as_str(), "team");
+    assert_eq!(PlayerField::from_name("name"), Some(PlayerField::Name));
+    assert_eq!(PlayerField::from_name("nickname"), None);
+
+    // The GraphQL name is kept when the field is renamed
+    assert_eq!(PlayerStatsField::GamesPlayed.as_str(), "gamesPlayed");
+    assert_eq!(PlayerStatsField::Ratio.to_string(), "winRate");
+
+    // `Self` is a keyword
+    assert_eq!(WeirdFieldNamesField::RSelf.as_str(), "self");
+}
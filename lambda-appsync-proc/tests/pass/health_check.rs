@@ -0,0 +1,28 @@
+// The health check answers through the stream as well
+mod no_run {
+    use lambda_appsync::appsync_lambda_main;
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        batch = false,
+        streaming = true,
+        health_check_op = "__health"
+    );
+}
+
+// The health check answer bypasses a serializer that only accepts the responses of the batch mode
+mod no_run_serialize_response {
+    use lambda_appsync::{appsync_lambda_main, lambda_runtime, AppsyncResponse};
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        health_check_op = "__health",
+        serialize_response = serialize_response
+    );
+
+    fn serialize_response(
+        response: &Vec<AppsyncResponse>,
+    ) -> Result<Vec<u8>, lambda_runtime::Error> {
+        Ok(lambda_appsync::serde_json::to_vec(response)?)
+    }
+}
+
+fn main() {}
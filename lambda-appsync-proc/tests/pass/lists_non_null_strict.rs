@@ -0,0 +1,19 @@
+use lambda_appsync::{appsync_lambda_main, serde_json::json};
+
+// The list elements are required, a `null` element fails the deserialization
+appsync_lambda_main!(
+    "../../../../lambda-appsync/tests/schemas/lists_non_null.graphql",
+    only_appsync_types = true,
+    lists_non_null = strict,
+);
+
+fn main() {
+    let team: Team =
+        lambda_appsync::serde_json::from_value(json!({"members": [], "tags": ["rust"]})).unwrap();
+    let tags: Vec<String> = team.tags.unwrap();
+    assert_eq!(tags, ["rust"]);
+    assert!(lambda_appsync::serde_json::from_value::<Team>(
+        json!({"members": [], "tags": ["rust", null]})
+    )
+    .is_err());
+}
@@ -0,0 +1,7 @@
+// The default log initialization writes colors when logging to a terminal
+mod no_run {
+    use lambda_appsync::appsync_lambda_main;
+    appsync_lambda_main!("../../../../schema.graphql", log_color = auto);
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// The default log initialization uses a custom default filter
+mod no_run {
+    use lambda_appsync::appsync_lambda_main;
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        log_filter = "warn,lambda_appsync=debug"
+    );
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, serde::Serialize, AppsyncError, AppsyncEvent, ID,
+};
+
+appsync_lambda_main!("../../../../schema.graphql", exclude_lambda_handler = true);
+fn main() {}
+
+// A domain type with the shape of `Player`
+#[derive(Serialize)]
+#[serde(crate = "lambda_appsync::serde")]
+struct PlayerView {
+    id: ID,
+    name: String,
+    team: &'static str,
+}
+
+// The returned value only has to be serializable
+#[appsync_operation(query(players), loose_return)]
+async fn get_players() -> Result<Vec<PlayerView>, AppsyncError> {
+    Ok(vec![])
+}
+
+// Arguments are still checked
+#[appsync_operation(query(player), loose_return, with_appsync_event)]
+async fn get_player(
+    id: ID,
+    _event: &AppsyncEvent<Operation>,
+) -> Result<Option<PlayerView>, AppsyncError> {
+    Ok(Some(PlayerView {
+        id,
+        name: "Test".to_owned(),
+        team: "RUST",
+    }))
+}
+
+#[appsync_operation(mutation(createPlayer), loose_return, keep_original_function_name)]
+fn create_player(name: String) -> Result<PlayerView, AppsyncError> {
+    Ok(PlayerView {
+        id: ID::new(),
+        name,
+        team: "RUST",
+    })
+}
+
+// Operations without `loose_return` keep the schema type
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
@@ -0,0 +1,17 @@
+// Test generating only some GraphQL types and their dependencies
+lambda_appsync::appsync_lambda_main!("../../../../schema.graphql", only_types = [Player]);
+
+// Not generated, so it can be defined here without conflict
+#[allow(dead_code)]
+struct GameStatus;
+
+fn main() {
+    // `Team` is generated as a dependency of `Player`
+    let player = Player {
+        id: lambda_appsync::ID::new(),
+        name: "Test Player".into(),
+        team: Team::Rust,
+    };
+
+    assert_eq!(player.team, Team::Rust);
+}
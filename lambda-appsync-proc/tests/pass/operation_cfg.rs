@@ -0,0 +1,28 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    // `any()` is always false: the operation is compiled out
+    operation_cfg = Mutation.deletePlayer: any(),
+    // `all()` is always true: the operation is kept
+    operation_cfg = Query.player: all(),
+);
+
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+// Gated the same way as the operation it implements
+#[cfg(any())]
+#[appsync_operation(mutation(deletePlayer))]
+async fn delete_player(_id: ID) -> Result<Player, AppsyncError> {
+    unreachable!()
+}
+
+fn main() {
+    assert!(Operation::from_names("Query", "player").is_some());
+    assert!(Operation::from_names("Mutation", "deletePlayer").is_none());
+    assert!(Operation::from_names("Mutation", "createPlayer").is_some());
+}
@@ -0,0 +1,23 @@
+use lambda_appsync::{appsync_lambda_main, registry::OperationKind};
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    emit_registry = true,
+    operation_cfg = Mutation.deletePlayer: any(),
+);
+
+fn main() {
+    let registry = operation_registry();
+    // 3 queries, 2 mutations (deletePlayer is compiled out) and 3 subscriptions
+    assert_eq!(registry.len(), 8);
+    assert!(!registry.iter().any(|op| op.name == "deletePlayer"));
+
+    let create_player = registry
+        .iter()
+        .find(|op| op.kind == OperationKind::Mutation && op.name == "createPlayer")
+        .unwrap();
+    assert_eq!(create_player.args.len(), 1);
+    assert_eq!(create_player.args[0].name, "name");
+    assert!(create_player.args[0].required);
+}
@@ -0,0 +1,30 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, serde_json::json, serde_json::Value, AppsyncError,
+    AppsyncEvent, ID,
+};
+
+appsync_lambda_main!("../../../../schema.graphql", exclude_lambda_handler = true);
+fn main() {}
+
+// The returned value does not have to match the schema type
+#[appsync_operation(query(players), raw_return)]
+async fn get_players() -> Result<Value, AppsyncError> {
+    Ok(json!([{"id": ID::new(), "name": "Test", "team": "RUST", "score": 42}]))
+}
+
+// Arguments are still checked
+#[appsync_operation(query(player), raw_return, with_appsync_event)]
+async fn get_player(id: ID, _event: &AppsyncEvent<Operation>) -> Result<Value, AppsyncError> {
+    Ok(json!({"id": id}))
+}
+
+#[appsync_operation(mutation(createPlayer), raw_return, keep_original_function_name)]
+fn create_player(name: String) -> Result<Value, AppsyncError> {
+    Ok(json!({"id": ID::new(), "name": name, "team": "RUST"}))
+}
+
+// Operations without `raw_return` keep the schema type
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
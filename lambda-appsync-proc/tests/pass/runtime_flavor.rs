@@ -0,0 +1,10 @@
+// The generated main uses a current-thread tokio runtime
+mod no_run {
+    use lambda_appsync::appsync_lambda_main;
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        runtime_flavor = current_thread
+    );
+}
+
+fn main() {}
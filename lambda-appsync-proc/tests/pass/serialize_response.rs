@@ -0,0 +1,16 @@
+// A generic serializer works for batch responses as well as single ones
+mod no_run {
+    use lambda_appsync::appsync_lambda_main;
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        serialize_response = serialize_response
+    );
+
+    fn serialize_response<T: lambda_appsync::serde::Serialize>(
+        response: &T,
+    ) -> Result<Vec<u8>, lambda_appsync::lambda_runtime::Error> {
+        Ok(lambda_appsync::serde_json::to_vec(response)?)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation,
+    subscription_filters::{FieldPath, FilterGroup, SubscriptionFilters},
+    AppsyncError, ID,
+};
+
+appsync_lambda_main!("../../../../schema.graphql", exclude_lambda_handler = true);
+fn main() {}
+
+// Single group
+#[appsync_operation(subscription(onCreatePlayer))]
+async fn on_create_player(name: String) -> Result<Option<FilterGroup>, AppsyncError> {
+    Ok(Some(FieldPath::new("name")?.eq(name).into()))
+}
+
+// Several groups combined with OR logic
+#[appsync_operation(subscription(onDeletePlayer))]
+async fn on_delete_player(id: ID) -> Result<Option<SubscriptionFilters>, AppsyncError> {
+    Ok(Some(SubscriptionFilters::new([
+        FilterGroup::from(FieldPath::new("id")?.eq(id)),
+        FilterGroup::from(FieldPath::new("team")?.eq("RUST")),
+    ])?))
+}
@@ -0,0 +1,33 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, subscription_filters::FilterGroup, AppsyncError, ID,
+};
+
+appsync_lambda_main!("../../../../schema.graphql", exclude_lambda_handler = true);
+fn main() {}
+
+#[appsync_operation(query(gameStatus))]
+fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+#[appsync_operation(query(player), keep_original_function_name)]
+fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+#[appsync_operation(mutation(createPlayer), with_appsync_event)]
+pub fn create_player(
+    name: String,
+    _event: &lambda_appsync::AppsyncEvent<Operation>,
+) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team: Team::Rust,
+    })
+}
+
+#[appsync_operation(subscription(onGameStatusChange))]
+fn on_game_status_change() -> Result<Option<FilterGroup>, AppsyncError> {
+    Ok(None)
+}
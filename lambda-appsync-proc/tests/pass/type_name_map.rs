@@ -0,0 +1,15 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    only_appsync_types = true,
+    emit_type_name_map = true,
+    name_override = Player: NewPlayer,
+);
+
+fn main() {
+    assert!(TYPE_NAME_MAP.contains(&("Player", "NewPlayer")));
+    assert!(TYPE_NAME_MAP.contains(&("Team", "Team")));
+    assert!(TYPE_NAME_MAP.contains(&("GameStatus", "GameStatus")));
+    assert!(!TYPE_NAME_MAP.iter().any(|(graphql, _)| *graphql == "Query"));
+}
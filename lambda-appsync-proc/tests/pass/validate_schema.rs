@@ -0,0 +1,8 @@
+// Test only validating the schema, without generating any code
+lambda_appsync::appsync_lambda_main!("../../../../schema.graphql", validate_schema = true);
+
+// Not generated, so it can be defined here without conflict
+#[allow(dead_code)]
+struct Player;
+
+fn main() {}
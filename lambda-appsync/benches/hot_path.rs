@@ -0,0 +1,192 @@
+//! Allocations and duration of the hot path of a Lambda function: deserializing the AppSync events,
+//! extracting the arguments, running the operations and building the responses.
+//!
+//! Run with `cargo bench -p lambda-appsync --bench hot_path`.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+// Counts the allocations made by the benchmarked code
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+appsync_lambda_main!("schema.graphql", exclude_lambda_handler = true);
+
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    Ok((0..10)
+        .map(|i| Player {
+            id: ID::new(),
+            name: format!("Player {i}"),
+            team: Team::Rust,
+        })
+        .collect())
+}
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team: Team::Rust,
+    })
+}
+
+// The adjacently tagged representation replaced by the generated deserialization of `Operation`
+#[derive(lambda_appsync::serde::Deserialize)]
+#[serde(crate = "lambda_appsync::serde")]
+#[serde(tag = "parentTypeName", content = "fieldName")]
+#[allow(dead_code)]
+enum TaggedOperation {
+    Query(QueryField),
+    Mutation(MutationField),
+    Subscription(SubscriptionField),
+}
+
+fn event(parent_type_name: &str, field_name: &str, arguments: Value) -> Value {
+    json!({
+        "info": {
+            "fieldName": field_name,
+            "parentTypeName": parent_type_name,
+            "variables": {"name": "Ferris"},
+            "selectionSetList": ["id", "name", "team"],
+            "selectionSetGraphQL": "{\n  id\n  name\n  team\n}"
+        },
+        "arguments": arguments,
+        "identity": {
+            "sub": "user123",
+            "issuer": "https://cognito-idp.eu-west-1.amazonaws.com/eu-west-1_abc",
+            "username": "testuser",
+            "claims": {"email": "user@example.com"},
+            "sourceIp": ["1.1.1.1"],
+            "defaultAuthStrategy": "ALLOW",
+            "groups": ["admin"]
+        },
+        "request": {"headers": {"host": "example.appsync-api.eu-west-1.amazonaws.com"}},
+        "source": null
+    })
+}
+
+// Runs `f` `iterations` times and prints the allocations and the duration per iteration
+fn bench<T>(name: &str, iterations: usize, mut setup: impl FnMut() -> T, mut f: impl FnMut(T)) {
+    let mut allocations = 0;
+    let mut elapsed = std::time::Duration::ZERO;
+    for _ in 0..iterations {
+        let input = setup();
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        f(input);
+        elapsed += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+    }
+    println!(
+        "{name:<40} {:>8} allocations {:>10.2?}",
+        allocations / iterations,
+        elapsed / iterations as u32
+    );
+}
+
+fn main() {
+    const ITERATIONS: usize = 10_000;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    bench(
+        "deserialize event",
+        ITERATIONS,
+        || event("Mutation", "createPlayer", json!({"name": "Ferris"})),
+        |event| {
+            black_box(
+                serde_json::from_value::<lambda_appsync::AppsyncEvent<Operation>>(event).unwrap(),
+            );
+        },
+    );
+    const OPERATION: &str = r#"{"parentTypeName":"Mutation","fieldName":"createPlayer"}"#;
+    bench(
+        "deserialize operation",
+        ITERATIONS,
+        || (),
+        |()| {
+            black_box(serde_json::from_str::<Operation>(OPERATION).unwrap());
+        },
+    );
+    bench(
+        "deserialize operation (serde tagged)",
+        ITERATIONS,
+        || (),
+        |()| {
+            black_box(serde_json::from_str::<TaggedOperation>(OPERATION).unwrap());
+        },
+    );
+    bench(
+        "mutation createPlayer",
+        ITERATIONS,
+        || {
+            serde_json::from_value::<lambda_appsync::AppsyncEvent<Operation>>(event(
+                "Mutation",
+                "createPlayer",
+                json!({"name": "Ferris"}),
+            ))
+            .unwrap()
+        },
+        |event| {
+            black_box(runtime.block_on(event.info.operation.execute(event)));
+        },
+    );
+    bench(
+        "query players (10 items)",
+        ITERATIONS,
+        || {
+            serde_json::from_value::<lambda_appsync::AppsyncEvent<Operation>>(event(
+                "Query",
+                "players",
+                json!({}),
+            ))
+            .unwrap()
+        },
+        |event| {
+            black_box(runtime.block_on(event.info.operation.execute(event)));
+        },
+    );
+    bench(
+        "serialize response (10 items)",
+        ITERATIONS,
+        || {
+            let event = serde_json::from_value::<lambda_appsync::AppsyncEvent<Operation>>(event(
+                "Query",
+                "players",
+                json!({}),
+            ))
+            .unwrap();
+            runtime.block_on(event.info.operation.execute(event))
+        },
+        |response| {
+            black_box(serde_json::to_vec(&response).unwrap());
+        },
+    );
+}
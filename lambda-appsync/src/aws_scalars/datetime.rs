@@ -1,6 +1,256 @@
-impl_new_string!(AWSDateTime);
-impl_new_string!(AWSDate);
-impl_new_string!(AWSTime);
+impl_new_string!(custom_deserialize AWSDateTime);
+impl_new_string!(custom_deserialize AWSDate);
+impl_new_string!(custom_deserialize AWSTime);
+
+// Any string is accepted by default, the `validate_datetime` feature rejects the values
+// that do not match the AWS format
+macro_rules! impl_deserialize {
+    ($name:ident, $format:literal) => {
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                #[cfg(feature = "validate_datetime")]
+                if !Self::is_valid(&value) {
+                    return Err(serde::de::Error::custom(format!(
+                        "invalid {} `{value}`, expected {}",
+                        stringify!($name),
+                        $format
+                    )));
+                }
+                Ok(Self(value))
+            }
+        }
+    };
+}
+impl_deserialize!(AWSDateTime, "YYYY-MM-DDThh:mm[:ss[.sss]]tz");
+impl_deserialize!(AWSDate, "YYYY-MM-DD[tz]");
+impl_deserialize!(AWSTime, "hh:mm[:ss[.sss]][tz]");
+
+impl AWSDateTime {
+    /// Returns `true` if `value` is a valid `AWSDateTime`: an extended ISO 8601 date and time
+    /// with a mandatory time zone offset, e.g. `2024-02-14T15:30Z` or `2024-02-14T15:30:00.123+05:30`
+    ///
+    /// Deserialization rejects invalid values with the `validate_datetime` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AWSDateTime;
+    /// assert!(AWSDateTime::is_valid("2024-02-14T15:30:00Z"));
+    /// assert!(!AWSDateTime::is_valid("2024-02-14T15:30:00"));
+    /// ```
+    pub fn is_valid(value: &str) -> bool {
+        parse_date(value)
+            .and_then(|rest| rest.strip_prefix('T'))
+            .and_then(parse_time)
+            .and_then(parse_offset)
+            .is_some_and(str::is_empty)
+    }
+}
+
+impl AWSDate {
+    /// Returns `true` if `value` is a valid `AWSDate`: an extended ISO 8601 date with an optional
+    /// time zone offset, e.g. `2024-02-14` or `2024-02-14-07:00`
+    ///
+    /// Deserialization rejects invalid values with the `validate_datetime` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AWSDate;
+    /// assert!(AWSDate::is_valid("2024-02-14"));
+    /// assert!(!AWSDate::is_valid("2024-02-30"));
+    /// ```
+    pub fn is_valid(value: &str) -> bool {
+        parse_date(value)
+            .and_then(parse_optional_offset)
+            .is_some_and(str::is_empty)
+    }
+}
+
+impl AWSTime {
+    /// Returns `true` if `value` is a valid `AWSTime`: an extended ISO 8601 time with an optional
+    /// time zone offset, e.g. `15:30`, `15:30:00.123` or `15:30:00Z`
+    ///
+    /// Deserialization rejects invalid values with the `validate_datetime` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AWSTime;
+    /// assert!(AWSTime::is_valid("15:30:00"));
+    /// assert!(!AWSTime::is_valid("25:00"));
+    /// ```
+    pub fn is_valid(value: &str) -> bool {
+        parse_time(value)
+            .and_then(parse_optional_offset)
+            .is_some_and(str::is_empty)
+    }
+}
+
+// Valid values only, see `is_valid`
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls {
+    use super::{AWSDate, AWSDateTime, AWSTime};
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    // YYYY-MM-DD, days past the 28th are left out to stay valid in every month
+    fn date(u: &mut Unstructured<'_>) -> Result<String> {
+        let year: u16 = u.int_in_range(0..=9999)?;
+        let month: u8 = u.int_in_range(1..=12)?;
+        let day: u8 = u.int_in_range(1..=28)?;
+        Ok(format!("{year:04}-{month:02}-{day:02}"))
+    }
+
+    // hh:mm:ss
+    fn time(u: &mut Unstructured<'_>) -> Result<String> {
+        let hour: u8 = u.int_in_range(0..=23)?;
+        let minute: u8 = u.int_in_range(0..=59)?;
+        let second: u8 = u.int_in_range(0..=59)?;
+        Ok(format!("{hour:02}:{minute:02}:{second:02}"))
+    }
+
+    impl<'a> Arbitrary<'a> for AWSDateTime {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(Self(format!("{}T{}Z", date(u)?, time(u)?)))
+        }
+    }
+    impl<'a> Arbitrary<'a> for AWSDate {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(Self(date(u)?))
+        }
+    }
+    impl<'a> Arbitrary<'a> for AWSTime {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(Self(time(u)?))
+        }
+    }
+}
+
+// Valid values only, see `is_valid`
+#[cfg(feature = "proptest")]
+mod proptest_impls {
+    use super::{AWSDate, AWSDateTime, AWSTime};
+    use proptest::{
+        arbitrary::Arbitrary,
+        strategy::{BoxedStrategy, Strategy},
+    };
+
+    // YYYY-MM-DD, days past the 28th are left out to stay valid in every month
+    fn date() -> impl Strategy<Value = String> {
+        (0u16..=9999, 1u8..=12, 1u8..=28)
+            .prop_map(|(year, month, day)| format!("{year:04}-{month:02}-{day:02}"))
+    }
+
+    // hh:mm:ss
+    fn time() -> impl Strategy<Value = String> {
+        (0u8..=23, 0u8..=59, 0u8..=59)
+            .prop_map(|(hour, minute, second)| format!("{hour:02}:{minute:02}:{second:02}"))
+    }
+
+    impl Arbitrary for AWSDateTime {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            (date(), time())
+                .prop_map(|(date, time)| Self(format!("{date}T{time}Z")))
+                .boxed()
+        }
+    }
+    impl Arbitrary for AWSDate {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            date().prop_map(Self).boxed()
+        }
+    }
+    impl Arbitrary for AWSTime {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            time().prop_map(Self).boxed()
+        }
+    }
+}
+
+// The parsers below consume the beginning of the string and return the rest, or `None` if invalid
+
+// Exactly `n` ASCII digits
+fn parse_digits(s: &str, n: usize) -> Option<(u32, &str)> {
+    let (digits, rest) = s.split_at_checked(n)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((digits.parse().ok()?, rest))
+}
+
+// Exactly `n` ASCII digits followed by `separator`, at most `max`
+fn parse_field(s: &str, n: usize, max: u32, separator: Option<char>) -> Option<(u32, &str)> {
+    let (value, rest) = parse_digits(s, n)?;
+    let rest = match separator {
+        Some(separator) => rest.strip_prefix(separator)?,
+        None => rest,
+    };
+    (value <= max).then_some((value, rest))
+}
+
+// YYYY-MM-DD
+fn parse_date(s: &str) -> Option<&str> {
+    let (year, rest) = parse_field(s, 4, 9999, Some('-'))?;
+    let (month, rest) = parse_field(rest, 2, 12, Some('-'))?;
+    let (day, rest) = parse_field(rest, 2, 31, None)?;
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap_year => 29,
+        2 => 28,
+        _ => return None,
+    };
+    (1..=days_in_month).contains(&day).then_some(rest)
+}
+
+// hh:mm[:ss[.sss]]
+fn parse_time(s: &str) -> Option<&str> {
+    let (_, rest) = parse_field(s, 2, 23, Some(':'))?;
+    let (_, rest) = parse_field(rest, 2, 59, None)?;
+    let Some(rest) = rest.strip_prefix(':') else {
+        return Some(rest);
+    };
+    let (_, rest) = parse_field(rest, 2, 59, None)?;
+    let Some(rest) = rest.strip_prefix('.') else {
+        return Some(rest);
+    };
+    let fraction_len = rest.bytes().take_while(u8::is_ascii_digit).count();
+    (1..=9)
+        .contains(&fraction_len)
+        .then(|| &rest[fraction_len..])
+}
+
+// Z, ±hh:mm or ±hh:mm:ss
+fn parse_offset(s: &str) -> Option<&str> {
+    if let Some(rest) = s.strip_prefix('Z') {
+        return Some(rest);
+    }
+    let rest = s.strip_prefix('+').or_else(|| s.strip_prefix('-'))?;
+    let (_, rest) = parse_field(rest, 2, 23, Some(':'))?;
+    let (_, rest) = parse_field(rest, 2, 59, None)?;
+    match rest.strip_prefix(':') {
+        Some(rest) => parse_field(rest, 2, 59, None).map(|(_, rest)| rest),
+        None => Some(rest),
+    }
+}
+
+fn parse_optional_offset(s: &str) -> Option<&str> {
+    if s.is_empty() {
+        Some(s)
+    } else {
+        parse_offset(s)
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -45,6 +295,99 @@ mod test {
         assert_eq!(s, "15:30:00");
     }
 
+    #[test]
+    fn datetime_is_valid() {
+        for valid in [
+            "2024-02-14T15:30Z",
+            "2024-02-14T15:30:00Z",
+            "2024-02-14T15:30:00.123456789-07:00",
+            "2024-02-14T15:30:00+05:30:15",
+        ] {
+            assert!(AWSDateTime::is_valid(valid), "{valid}");
+        }
+        for invalid in [
+            "2024-02-14T15:30:00",
+            "2024-02-14 15:30:00Z",
+            "2024-02-14T15:30:00.Z",
+            "2024-02-14T15:30:00ZZ",
+            "2024-13-14T15:30:00Z",
+            "",
+        ] {
+            assert!(!AWSDateTime::is_valid(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn date_is_valid() {
+        for valid in [
+            "2024-02-14",
+            "2024-02-29",
+            "2000-02-29",
+            "2024-02-14Z",
+            "2024-02-14-07:00",
+        ] {
+            assert!(AWSDate::is_valid(valid), "{valid}");
+        }
+        for invalid in [
+            "2023-02-29",
+            "1900-02-29",
+            "2024-04-31",
+            "2024-00-10",
+            "2024-2-14",
+            "24-02-14",
+            "2024-02-14T",
+            "2024-02-14+7:00",
+        ] {
+            assert!(!AWSDate::is_valid(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn time_is_valid() {
+        for valid in [
+            "15:30",
+            "15:30:00",
+            "15:30:00.5",
+            "00:00:00Z",
+            "23:59:59.999+01:00",
+        ] {
+            assert!(AWSTime::is_valid(valid), "{valid}");
+        }
+        for invalid in [
+            "24:00",
+            "15:60",
+            "15:30:60",
+            "15:30:00.1234567890",
+            "1530",
+            "15:30 ",
+        ] {
+            assert!(!AWSTime::is_valid(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "validate_datetime"))]
+    fn deserialize_permissive() {
+        let date: AWSDate = serde_json::from_value(serde_json::json!("not a date")).unwrap();
+        assert_eq!(*date, "not a date");
+    }
+
+    #[test]
+    #[cfg(feature = "validate_datetime")]
+    fn deserialize_validated() {
+        let date: AWSDate = serde_json::from_value(serde_json::json!("2024-02-14")).unwrap();
+        assert_eq!(*date, "2024-02-14");
+        let err = serde_json::from_value::<AWSDate>(serde_json::json!("not a date")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid AWSDate `not a date`, expected YYYY-MM-DD[tz]"
+        );
+        assert!(serde_json::from_value::<AWSTime>(serde_json::json!("25:00")).is_err());
+        assert!(
+            serde_json::from_value::<AWSDateTime>(serde_json::json!("2024-02-14T15:30")).is_err()
+        );
+    }
+
     #[test]
     fn display_implementations() {
         let dt = AWSDateTime::from("2024-02-14T15:30:00Z");
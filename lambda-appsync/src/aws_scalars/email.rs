@@ -24,6 +24,18 @@ impl core::str::FromStr for AWSEmail {
         Ok(Self::from(s))
     }
 }
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AWSEmail {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let local = super::arbitrary_alphanumeric(u, 1, 16)?;
+        let domain = super::arbitrary_alphanumeric(u, 1, 16)?;
+        Ok(Self(format!("{local}@{domain}.com")))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl_proptest_regex!(AWSEmail, "[a-z0-9]{1,16}@[a-z0-9]{1,16}\\.com");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,4 +77,20 @@ mod tests {
         let email = AWSEmail::from(value);
         assert_eq!(email.to_string(), value);
     }
+
+    #[test]
+    fn email_as_ref_str() {
+        fn domain(email: impl AsRef<str>) -> String {
+            email.as_ref().split('@').nth(1).unwrap().to_owned()
+        }
+        let email = AWSEmail::from("test@example.com");
+        assert_eq!(domain(&email), "example.com");
+    }
+
+    #[test]
+    fn email_borrow_str() {
+        let emails = std::collections::HashSet::from([AWSEmail::from("test@example.com")]);
+        assert!(emails.contains("test@example.com"));
+        assert!(!emails.contains("other@example.com"));
+    }
 }
@@ -11,6 +11,34 @@ macro_rules! impl_new_string {
                 &self.0
             }
         }
+        impl_new_string!(as_ref $name);
+    };
+    (base_custom_deserialize $name:ident) => {
+        #[doc = "AWS AppSync specific GraphQL scalar type implemented a [String] new-type"]
+        #[derive(Debug, Clone, serde::Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[serde(transparent)]
+        pub struct $name(String);
+        impl core::ops::Deref for $name {
+            type Target = String;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl_new_string!(as_ref $name);
+    };
+    (as_ref $name:ident) => {
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+        // The derived `Hash`, `Eq` and `Ord` are those of the inner `String`, consistent with `str`
+        impl core::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
     };
     (display $name:ident) => {
         impl core::fmt::Display for $name {
@@ -59,6 +87,12 @@ macro_rules! impl_new_string {
         impl_new_string!(display $name);
         impl_new_string!(from $name);
     };
+    (custom_deserialize $name:ident) => {
+        impl_new_string!(base_custom_deserialize $name);
+        impl_new_string!(display $name);
+        impl_new_string!(from $name);
+        impl_new_string!(into $name);
+    };
     ($name:ident) => {
         impl_new_string!(base $name);
         impl_new_string!(display $name);
@@ -67,6 +101,39 @@ macro_rules! impl_new_string {
     };
 }
 
+// A string of `min..=max` lowercase ASCII letters and digits
+#[cfg(feature = "arbitrary")]
+fn arbitrary_alphanumeric(
+    u: &mut arbitrary::Unstructured<'_>,
+    min: usize,
+    max: usize,
+) -> arbitrary::Result<String> {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    let len = u.int_in_range(min..=max)?;
+    (0..len)
+        .map(|_| u.choose(CHARS).map(|c| *c as char))
+        .collect()
+}
+
+// `proptest::arbitrary::Arbitrary` for a scalar, generating the strings matching `regex`
+#[cfg(feature = "proptest")]
+macro_rules! impl_proptest_regex {
+    ($name:ident, $regex:literal) => {
+        impl proptest::arbitrary::Arbitrary for $name {
+            type Parameters = ();
+            type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                use proptest::strategy::Strategy;
+                proptest::string::string_regex($regex)
+                    .expect("valid regex")
+                    .prop_map(Self)
+                    .boxed()
+            }
+        }
+    };
+}
+
 pub mod datetime;
 pub mod email;
 pub mod phone;
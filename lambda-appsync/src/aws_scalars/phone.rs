@@ -1,5 +1,20 @@
 impl_new_string!(AWSPhone);
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AWSPhone {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // E.164: a `+` followed by at most 15 digits
+        let len = u.int_in_range(8..=15)?;
+        let digits = (0..len)
+            .map(|_| u.int_in_range(0..=9).map(|d: u8| (b'0' + d) as char))
+            .collect::<arbitrary::Result<String>>()?;
+        Ok(Self(format!("+{digits}")))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl_proptest_regex!(AWSPhone, "\\+[0-9]{8,15}");
+
 #[cfg(test)]
 mod tests {
     use super::AWSPhone;
@@ -32,4 +47,18 @@ mod tests {
         let phone = AWSPhone::from(value);
         assert_eq!(phone.to_string(), value);
     }
+
+    #[test]
+    fn phone_as_ref_str() {
+        let value = "+12345678901";
+        let phone = AWSPhone::from(value);
+        let str_ref: &str = phone.as_ref();
+        assert_eq!(str_ref, value);
+    }
+
+    #[test]
+    fn phone_borrow_str() {
+        let phones = std::collections::BTreeMap::from([(AWSPhone::from("+12345678901"), "Ferris")]);
+        assert_eq!(phones.get("+12345678901"), Some(&"Ferris"));
+    }
 }
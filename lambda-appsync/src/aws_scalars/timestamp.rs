@@ -1,12 +1,112 @@
-use serde::{Deserialize, Serialize};
+use aws_smithy_types::date_time::{DateTime, Format};
+use serde::{de, Deserialize, Serialize};
 use std::time::{Duration, SystemTime};
 
 /// AWS AppSync specific GraphQL scalar type implemented [SystemTime] new-type.
 /// Note that this type implements Copy
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(from = "u64", into = "u64")]
+///
+/// It always serializes to a number of seconds since the UNIX epoch, but deserializes from either
+/// a number of seconds or a string containing a number of seconds or an RFC3339 date-time, since
+/// timestamps coming from other sources than AppSync are often formatted that way. Formats that are
+/// not human-readable (e.g. `bincode`) only hold the number of seconds.
+///
+/// # Examples
+/// ```
+/// use lambda_appsync::AWSTimestamp;
+///
+/// let from_secs: AWSTimestamp = serde_json::from_str("1700000000").unwrap();
+/// let from_rfc3339: AWSTimestamp = serde_json::from_str(r#""2023-11-14T22:13:20Z""#).unwrap();
+/// assert_eq!(from_secs, from_rfc3339);
+/// assert_eq!(serde_json::to_string(&from_rfc3339).unwrap(), "1700000000");
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(into = "u64")]
 pub struct AWSTimestamp(SystemTime);
 
+// The smithy parser only accepts UTC date-times (`Z` suffix), numeric offsets are applied here
+fn parse_rfc3339(v: &str) -> Option<SystemTime> {
+    let parse_utc = |v: &str| {
+        DateTime::from_str(v, Format::DateTime)
+            .ok()
+            .and_then(|date_time| SystemTime::try_from(date_time).ok())
+    };
+    if v.ends_with(['Z', 'z']) {
+        return parse_utc(v);
+    }
+    // `[+-]HH:MM` suffix
+    let split = v.len().checked_sub(6)?;
+    let (local, offset) = (v.get(..split)?, v.get(split..)?.as_bytes());
+    let sign = match offset[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = |b: &[u8]| {
+        b.iter()
+            .all(u8::is_ascii_digit)
+            .then(|| u64::from(b[0] - b'0') * 10 + u64::from(b[1] - b'0'))
+    };
+    if offset[3] != b':' {
+        return None;
+    }
+    let (hours, minutes) = (digits(&offset[1..3])?, digits(&offset[4..6])?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    let offset = Duration::from_secs(hours * 3600 + minutes * 60);
+    let local = parse_utc(&format!("{local}Z"))?;
+    // UTC = local time - offset
+    if sign > 0 {
+        local.checked_sub(offset)
+    } else {
+        local.checked_add(offset)
+    }
+}
+
+impl<'de> Deserialize<'de> for AWSTimestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct AWSTimestampVisitor;
+        impl de::Visitor<'_> for AWSTimestampVisitor {
+            type Value = AWSTimestamp;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter
+                    .write_str("a number of seconds since the UNIX epoch or an RFC3339 date-time")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(AWSTimestamp::from(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u64::try_from(v)
+                    .map(AWSTimestamp::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                if let Ok(secs) = v.parse::<u64>() {
+                    return Ok(AWSTimestamp::from(secs));
+                }
+                parse_rfc3339(v)
+                    .filter(|time| *time >= std::time::UNIX_EPOCH)
+                    .map(AWSTimestamp)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+        // Self-describing formats may hold any of the accepted representations, the others can only
+        // hold the number of seconds this type serializes to
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(AWSTimestampVisitor)
+        } else {
+            deserializer.deserialize_u64(AWSTimestampVisitor)
+        }
+    }
+}
+
 impl AWSTimestamp {
     /// Returns an [AWSTimestamp] representing the current date and time, as reported by the system clock.
     ///
@@ -81,6 +181,26 @@ impl From<u64> for AWSTimestamp {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AWSTimestamp {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Up to 9999-12-31T23:59:59Z, the last date-time an `AWSDateTime` can hold
+        Ok(Self::from(u.int_in_range(0..=253_402_300_799)?))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for AWSTimestamp {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        // Up to 9999-12-31T23:59:59Z, the last date-time an `AWSDateTime` can hold
+        (0..=253_402_300_799u64).prop_map(Self::from).boxed()
+    }
+}
+
 impl Default for AWSTimestamp {
     fn default() -> Self {
         Self::now()
@@ -230,4 +350,78 @@ mod tests {
         let ts = AWSTimestamp::from_u64(1234);
         assert_eq!(ts.into_u64(), 1234);
     }
+
+    #[test]
+    fn test_deserialize_seconds() {
+        let ts: AWSTimestamp = serde_json::from_str("1234").unwrap();
+        assert_eq!(ts.into_u64(), 1234);
+        let ts: AWSTimestamp = serde_json::from_str(r#""1234""#).unwrap();
+        assert_eq!(ts.into_u64(), 1234);
+        assert!(serde_json::from_str::<AWSTimestamp>("-1").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rfc3339() {
+        let ts: AWSTimestamp = serde_json::from_str(r#""1970-01-01T00:20:34Z""#).unwrap();
+        assert_eq!(ts.into_u64(), 1234);
+        let ts: AWSTimestamp = serde_json::from_str(r#""2023-11-14T22:13:20.500Z""#).unwrap();
+        assert_eq!(ts.into_u64(), 1_700_000_000);
+        assert_eq!(
+            ts - AWSTimestamp::from(1_700_000_000),
+            Duration::from_millis(500)
+        );
+        let ts: AWSTimestamp = serde_json::from_str(r#""2023-11-15T00:13:20+02:00""#).unwrap();
+        assert_eq!(ts.into_u64(), 1_700_000_000);
+        let ts: AWSTimestamp = serde_json::from_str(r#""2023-11-14T20:13:20-02:00""#).unwrap();
+        assert_eq!(ts.into_u64(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(serde_json::from_str::<AWSTimestamp>(r#""not a date""#).is_err());
+        assert!(serde_json::from_str::<AWSTimestamp>(r#""1969-12-31T23:59:59Z""#).is_err());
+        assert!(serde_json::from_str::<AWSTimestamp>("1.5").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_invalid_offset() {
+        assert!(serde_json::from_str::<AWSTimestamp>(r#""2023-11-15T00:13:20+24:00""#).is_err());
+        assert!(serde_json::from_str::<AWSTimestamp>(r#""2023-11-15T00:13:20+02:60""#).is_err());
+        assert!(serde_json::from_str::<AWSTimestamp>(r#""2023-11-15T00:13:20++2:00""#).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_compact() {
+        // A format that is not self-describing, which only supports the hinted type
+        struct Compact(u64);
+        impl<'de> de::Deserializer<'de> for Compact {
+            type Error = de::value::Error;
+
+            fn deserialize_any<V: de::Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+                Err(de::Error::custom("not self-describing"))
+            }
+
+            fn deserialize_u64<V: de::Visitor<'de>>(self, v: V) -> Result<V::Value, Self::Error> {
+                v.visit_u64(self.0)
+            }
+
+            fn is_human_readable(&self) -> bool {
+                false
+            }
+
+            serde::forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u128 f32 f64 char str string bytes byte_buf
+                option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+                identifier ignored_any
+            }
+        }
+        let ts = AWSTimestamp::deserialize(Compact(1234)).unwrap();
+        assert_eq!(ts.into_u64(), 1234);
+    }
+
+    #[test]
+    fn test_serialize_stays_numeric() {
+        let ts: AWSTimestamp = serde_json::from_str(r#""1970-01-01T00:20:34Z""#).unwrap();
+        assert_eq!(serde_json::to_string(&ts).unwrap(), "1234");
+    }
 }
@@ -1,5 +1,17 @@
 impl_new_string!(AWSUrl);
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AWSUrl {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let host = super::arbitrary_alphanumeric(u, 1, 16)?;
+        let path = super::arbitrary_alphanumeric(u, 0, 16)?;
+        Ok(Self(format!("https://{host}.com/{path}")))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl_proptest_regex!(AWSUrl, "https://[a-z0-9]{1,16}\\.com/[a-z0-9]{0,16}");
+
 #[cfg(test)]
 mod tests {
     use super::AWSUrl;
@@ -0,0 +1,197 @@
+use serde::Serialize;
+
+use crate::AppsyncError;
+
+/// The results of an operation processing several items, some of which may fail.
+///
+/// A [BatchResult] collects the result of each item. When all of them succeed,
+/// [BatchResult::into_result] returns the list of items; otherwise it returns an [AppsyncError]
+/// carrying the partial list, with `null` in place of the failed items. AppSync then resolves the field
+/// to this partial list **and** reports the error in the `errors` of the GraphQL response.
+///
+/// The error combines the errors of the failed items, as the `|` operator of [AppsyncError] does:
+/// its `errorType` joins their types with `|` and its `errorMessage` has one line per failed item,
+/// prefixed with the item index (e.g. `Item 2: Name already taken`). Its `errorInfo` is the one of the
+/// first failed item that has one.
+///
+/// This is unrelated to the AppSync batching of Lambda invocations: a single operation surfaces both
+/// successes and item-level errors. As failed items are `null`, the list items must be nullable in
+/// the schema, e.g. `createPlayers(names: [String!]!): [Player]!`.
+///
+/// # Example
+/// ```
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{AppsyncError, BatchResult, ID};
+/// use crate::{Player, Team};
+///
+/// async fn create_player(name: String) -> Result<Player, AppsyncError> {
+///     if name.is_empty() {
+///         return Err(AppsyncError::new("BadRequest", "Name cannot be empty"));
+///     }
+///     Ok(Player { id: ID::new(), name, team: Team::Rust })
+/// }
+///
+/// async fn create_players(names: Vec<String>) -> Result<Vec<Option<Player>>, AppsyncError> {
+///     let mut batch = BatchResult::new();
+///     for name in names {
+///         batch.push(create_player(name).await);
+///     }
+///     batch.into_result()
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct BatchResult<T> {
+    items: Vec<Option<T>>,
+    error: Option<AppsyncError>,
+}
+
+impl<T> Default for BatchResult<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl<T> BatchResult<T> {
+    /// Creates an empty [BatchResult]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the result of the next item
+    pub fn push(&mut self, result: Result<T, AppsyncError>) {
+        match result {
+            Ok(item) => self.items.push(Some(item)),
+            Err(mut e) => {
+                e.error_message = format!("Item {}: {}", self.items.len(), e.error_message);
+                self.error = Some(match self.error.take() {
+                    Some(error) => error | e,
+                    None => e,
+                });
+                self.items.push(None);
+            }
+        }
+    }
+
+    /// Returns the number of items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there is no item
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns `true` if at least one item failed
+    pub fn has_errors(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+impl<T: Serialize> BatchResult<T> {
+    /// Returns the items if they all succeeded, or the combined error of the failed items carrying
+    /// the partial list of items otherwise
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, AppsyncResponse, BatchResult};
+    /// let batch: BatchResult<u32> = [
+    ///     Ok(1),
+    ///     Err(AppsyncError::new("NotFound", "No such item")),
+    ///     Ok(3),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let error = batch.into_result().unwrap_err();
+    /// assert_eq!(error.error_message, "Item 1: No such item");
+    ///
+    /// let response = serde_json::to_value(AppsyncResponse::from(error)).unwrap();
+    /// assert_eq!(response["data"], serde_json::json!([1, null, 3]));
+    /// assert_eq!(response["errorType"], "NotFound");
+    /// ```
+    pub fn into_result(self) -> Result<Vec<Option<T>>, AppsyncError> {
+        match self.error {
+            None => Ok(self.items),
+            Some(mut error) => {
+                error.data = Some(crate::res_to_json(self.items));
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<T> FromIterator<Result<T, AppsyncError>> for BatchResult<T> {
+    fn from_iter<I: IntoIterator<Item = Result<T, AppsyncError>>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        for result in iter {
+            batch.push(result);
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppsyncResponse;
+    use serde_json::json;
+
+    #[test]
+    fn test_all_succeed() {
+        let batch: BatchResult<u32> = [Ok(1), Ok(2)].into_iter().collect();
+        assert!(!batch.has_errors());
+        assert_eq!(batch.into_result().unwrap(), vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_partial_failure() {
+        let mut batch = BatchResult::new();
+        batch.push(Err(AppsyncError::new("BadRequest", "Empty name")));
+        batch.push(Ok("Ferris"));
+        batch.push(Err(AppsyncError::new("Conflict", "Name already taken")));
+        assert_eq!(batch.len(), 3);
+        assert!(batch.has_errors());
+
+        let error = batch.into_result().unwrap_err();
+        assert_eq!(error.error_type, "BadRequest|Conflict");
+        assert_eq!(
+            error.error_message,
+            "Item 0: Empty name\nItem 2: Name already taken"
+        );
+
+        let response = serde_json::to_value(AppsyncResponse::from(error)).unwrap();
+        assert_eq!(
+            response,
+            json!({
+                "data": [null, "Ferris", null],
+                "errorType": "BadRequest|Conflict",
+                "errorMessage": "Item 0: Empty name\nItem 2: Name already taken"
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_info_is_kept() {
+        let batch: BatchResult<u32> = [
+            Ok(1),
+            Err(AppsyncError::new("NotFound", "No such item").with_error_info(json!({"id": 2}))),
+        ]
+        .into_iter()
+        .collect();
+        let error = batch.into_result().unwrap_err();
+        assert_eq!(error.error_message, "Item 1: No such item");
+
+        let response = serde_json::to_value(AppsyncResponse::from(error)).unwrap();
+        assert_eq!(response["errorInfo"], json!({"id": 2}));
+        assert_eq!(response["data"], json!([1, null]));
+    }
+}
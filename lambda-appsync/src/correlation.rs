@@ -0,0 +1,60 @@
+//! Correlation id of the AppSync request being handled
+//!
+//! When given `correlation_header = "x-correlation-id"`,
+//! [appsync_lambda_main](crate::appsync_lambda_main) reads the correlation id of each AppSync
+//! request from this request header and handles the request in a scope where [correlation_id]
+//! returns it. The default log initialization adds it to every log line: as a field of the span of
+//! the request with the `tracing` feature, or after the level and target with `env_logger`.
+//! A custom `log_init` can use [correlation_id] to do the same, e.g. to propagate it to downstream
+//! services.
+//!
+//! # Example
+//! ```
+//! # lambda_appsync::appsync_lambda_main!(
+//! #    "schema.graphql",
+//! #    exclude_lambda_handler = true,
+//! # );
+//! use lambda_appsync::{appsync_operation, correlation::correlation_id, AppsyncError};
+//!
+//! #[appsync_operation(query(players))]
+//! async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+//!     if let Some(correlation_id) = correlation_id() {
+//!         // e.g. pass it along to the downstream services
+//!     }
+//!     Ok(vec![])
+//! }
+//! # fn main() {}
+//! ```
+use std::future::Future;
+
+tokio::task_local! {
+    static CORRELATION_ID: Option<String>;
+}
+
+/// Returns the correlation id of the AppSync request handled by the current task, if the request
+/// had the `correlation_header` header
+///
+/// Returns `None` outside of the handling of a request, or when `correlation_header` is not used.
+pub fn correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok().flatten()
+}
+
+/// Runs `f` in a scope where [correlation_id] returns `correlation_id`, used by the generated code
+#[doc(hidden)]
+pub async fn scope<F: Future>(correlation_id: Option<String>, f: F) -> F::Output {
+    CORRELATION_ID.scope(correlation_id, f).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_correlation_id_scope() {
+        assert_eq!(correlation_id(), None);
+        let id = scope(Some("abc".to_owned()), async { correlation_id() }).await;
+        assert_eq!(id.as_deref(), Some("abc"));
+        assert_eq!(scope(None, async { correlation_id() }).await, None);
+        assert_eq!(correlation_id(), None);
+    }
+}
@@ -1,3 +1,7 @@
+//! The [ID] scalar and its alternative serde representations
+//!
+//! [ID] (de)serializes as its hyphenated string, as expected by AppSync. The [compact] module
+//! represents it by its 16 bytes instead, for other protocols of the same service.
 use serde::{Deserialize, Serialize};
 
 /// A custom UUID-based identifier type for AppSync GraphQL objects.
@@ -6,6 +10,9 @@ use serde::{Deserialize, Serialize};
 /// in AppSync while ensuring type safety and validation. It implements serialization
 /// and deserialization as [String] as expected by GraphQL.
 ///
+/// IDs are ordered by their underlying [Uuid](uuid::Uuid), i.e. by their bytes, which is also the
+/// order of their string representations.
+///
 /// # Example
 /// ```
 /// use lambda_appsync::ID;
@@ -13,7 +20,7 @@ use serde::{Deserialize, Serialize};
 /// let id = ID::new();
 /// let id_str: String = id.into();
 /// ```
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(try_from = "String", into = "String")]
 pub struct ID(uuid::Uuid);
 impl ID {
@@ -28,6 +35,44 @@ impl ID {
     pub fn new() -> Self {
         Self(uuid::Uuid::new_v4())
     }
+
+    /// Wraps an existing [Uuid](uuid::Uuid), e.g. read from a database key.
+    ///
+    /// # Example
+    /// ```
+    /// use lambda_appsync::ID;
+    ///
+    /// let uuid = uuid::Uuid::new_v4();
+    /// let id = ID::from_uuid(uuid);
+    /// assert_eq!(id.into_uuid(), uuid);
+    /// ```
+    pub const fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Returns the wrapped [Uuid](uuid::Uuid).
+    pub const fn into_uuid(self) -> uuid::Uuid {
+        self.0
+    }
+
+    /// Creates an ID from the 16 bytes of a UUID, e.g. stored in a binary DynamoDB attribute.
+    ///
+    /// # Example
+    /// ```
+    /// use lambda_appsync::ID;
+    ///
+    /// let id = ID::new();
+    /// let bytes = *id.as_bytes();
+    /// assert_eq!(ID::from_bytes(bytes), id);
+    /// ```
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(uuid::Uuid::from_bytes(bytes))
+    }
+
+    /// Returns the 16 bytes of the wrapped UUID.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
 }
 impl Default for ID {
     fn default() -> Self {
@@ -76,11 +121,41 @@ impl core::str::FromStr for ID {
         Ok(ID(uuid::Uuid::parse_str(s)?))
     }
 }
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ID {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self(uuid::Uuid::from_bytes(u.arbitrary()?)))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for ID {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::arbitrary::any::<[u8; 16]>()
+            .prop_map(|bytes| Self(uuid::Uuid::from_bytes(bytes)))
+            .boxed()
+    }
+}
+
 impl core::fmt::Display for ID {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
+impl From<uuid::Uuid> for ID {
+    fn from(value: uuid::Uuid) -> Self {
+        Self::from_uuid(value)
+    }
+}
+impl From<ID> for uuid::Uuid {
+    fn from(value: ID) -> Self {
+        value.into_uuid()
+    }
+}
 impl From<ID> for String {
     fn from(value: ID) -> Self {
         value.to_string()
@@ -93,6 +168,51 @@ impl core::ops::Deref for ID {
         &self.0
     }
 }
+pub mod compact {
+    //! Serializes an [ID] as its 16 bytes instead of its hyphenated string
+    //!
+    //! The bytes are serialized as a `[u8; 16]`: 16 bytes with binary formats such as bincode or
+    //! postcard, an array of 16 numbers with JSON. This is meant for internal protocols (binary
+    //! caches, inter-service messages...), AppSync always expects the string representation.
+    //!
+    //! # Example
+    //! ```
+    //! use lambda_appsync::{serde::{Deserialize, Serialize}, ID};
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! # #[serde(crate = "lambda_appsync::serde")]
+    //! struct CacheEntry {
+    //!     #[serde(with = "lambda_appsync::id::compact")]
+    //!     id: ID,
+    //! }
+    //!
+    //! let id = ID::new();
+    //! let json = lambda_appsync::serde_json::to_value(CacheEntry { id }).unwrap();
+    //! assert_eq!(json["id"].as_array().unwrap().len(), 16);
+    //! let entry: CacheEntry = lambda_appsync::serde_json::from_value(json).unwrap();
+    //! assert_eq!(entry.id, id);
+    //! ```
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::ID;
+
+    /// Serializes `id` as a `[u8; 16]`
+    pub fn serialize<S>(id: &ID, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.as_bytes().serialize(serializer)
+    }
+
+    /// Deserializes an [ID] from a `[u8; 16]`
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ID, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <[u8; 16]>::deserialize(deserializer).map(ID::from_bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +259,94 @@ mod tests {
         assert_eq!(uuid.to_string(), id.to_string());
     }
 
+    #[test]
+    fn test_id_uuid_round_trip() {
+        let uuid = uuid::Uuid::new_v4();
+        let id = ID::from(uuid);
+        assert_eq!(id.to_string(), uuid.to_string());
+        assert_eq!(uuid::Uuid::from(id), uuid);
+
+        let id = ID::new();
+        assert_eq!(ID::from_uuid(id.into_uuid()), id);
+    }
+
+    #[test]
+    fn test_id_bytes_round_trip() {
+        let id: ID = "123e4567-e89b-12d3-a456-426614174000".parse().unwrap();
+        assert_eq!(
+            id.as_bytes(),
+            &[
+                0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17,
+                0x40, 0x00
+            ]
+        );
+        let from_bytes = ID::from_bytes(*id.as_bytes());
+        assert_eq!(from_bytes, id);
+        assert_eq!(
+            from_bytes.to_string(),
+            "123e4567-e89b-12d3-a456-426614174000"
+        );
+    }
+
+    #[test]
+    fn test_id_ordering() {
+        let ids: Vec<ID> = [
+            "123e4567-e89b-12d3-a456-426614174000",
+            "00000000-0000-0000-0000-000000000001",
+            "ffffffff-ffff-ffff-ffff-ffffffffffff",
+            "123e4567-e89b-12d3-a456-426614174001",
+        ]
+        .into_iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+
+        let mut sorted = ids.clone();
+        sorted.sort();
+        let mut sorted_strings: Vec<String> = ids.iter().map(ID::to_string).collect();
+        sorted_strings.sort();
+        assert_eq!(
+            sorted.iter().map(ID::to_string).collect::<Vec<_>>(),
+            sorted_strings
+        );
+
+        for (a, b) in ids.iter().zip(&ids[1..]) {
+            assert_eq!(a.cmp(b), a.into_uuid().cmp(&b.into_uuid()));
+            assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+        }
+
+        let map: std::collections::BTreeMap<ID, usize> = ids.iter().copied().zip(0..).collect();
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), sorted);
+    }
+
+    #[test]
+    fn test_id_compact() {
+        #[derive(Serialize, Deserialize)]
+        struct Compact {
+            #[serde(with = "compact")]
+            id: ID,
+        }
+
+        let id: ID = "123e4567-e89b-12d3-a456-426614174000".parse().unwrap();
+        let json = serde_json::to_value(Compact { id }).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"id": [
+                0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17,
+                0x40, 0x00
+            ]})
+        );
+        let compact: Compact = serde_json::from_value(json).unwrap();
+        assert_eq!(compact.id, id);
+
+        // The default representation is untouched
+        assert_eq!(
+            serde_json::to_value(id).unwrap(),
+            "123e4567-e89b-12d3-a456-426614174000"
+        );
+
+        assert!(serde_json::from_value::<Compact>(serde_json::json!({"id": [1, 2, 3]})).is_err());
+    }
+
     #[test]
     fn test_id_display() {
         let id = ID::new();
@@ -73,17 +73,46 @@
 //! // and wires everything up to handle AWS AppSync requests automatically
 //! # mod child {fn main() {}}
 //! ```
+//!
+//! # Re-exported dependencies
+//!
+//! The code generated by the macros only refers to this crate and to the crates it re-exports
+//! ([serde], [serde_json], [tokio], [lambda_runtime], [aws_config], ...), so a crate using the macros
+//! does not need to depend on them directly. These re-exports are part of the public API.
+//!
+//! The generated types implement the `Serialize`/`Deserialize` traits of the re-exported [serde],
+//! which is also used to deserialize the AppSync events and serialize the responses. For this reason
+//! the serde crate used by the generated code cannot be changed. To use the generated types with
+//! your own code, either use `lambda_appsync::serde` or depend on `serde` 1.x, that cargo unifies
+//! with the version used by this crate.
 
 mod aws_scalars;
-mod id;
+mod batch_result;
+pub mod correlation;
+pub mod id;
+pub mod middleware;
+mod nullable;
+pub mod registry;
+#[cfg(feature = "selection_set")]
+mod selection_set;
 pub mod subscription_filters;
+#[cfg(feature = "testing")]
+mod testing;
 
-use std::{collections::HashMap, ops::BitOr};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::BitOr,
+    time::{Duration, SystemTime},
+};
 
 use aws_smithy_types::error::metadata::ProvideErrorMetadata;
 use serde_json::Value;
 
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{
+    de::{DeserializeOwned, Error as _, IgnoredAny, IntoDeserializer, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use thiserror::Error;
 
 pub use aws_scalars::{
@@ -93,7 +122,9 @@ pub use aws_scalars::{
     timestamp::AWSTimestamp,
     url::AWSUrl,
 };
+pub use batch_result::BatchResult;
 pub use id::ID;
+pub use nullable::Nullable;
 
 #[doc(inline)]
 pub use lambda_appsync_proc::appsync_lambda_main;
@@ -119,13 +150,19 @@ pub use tracing;
 #[cfg(feature = "tracing")]
 pub use tracing_subscriber;
 
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
+
+#[cfg(feature = "proptest")]
+pub use proptest;
+
 /// Authorization strategy for AppSync operations.
 ///
 /// It determines whether operations are allowed or denied based on the
 /// authentication context provided by AWS AppSync. It is typically used by AppSync
 /// itself in conjunction with AWS Cognito user pools and usually do not concern
 /// the application code.
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum AppsyncAuthStrategy {
     /// Allows the operation by default if no explicit authorizer is associated to the field
@@ -135,7 +172,9 @@ pub enum AppsyncAuthStrategy {
 }
 
 /// Identity information for Cognito User Pools authenticated requests.
-#[derive(Debug, Deserialize)]
+///
+/// With the `testing` feature, [AppsyncIdentityCognito::test] creates one for tests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppsyncIdentityCognito {
     /// Unique identifier of the authenticated user/client
@@ -148,7 +187,7 @@ pub struct AppsyncIdentityCognito {
     pub default_auth_strategy: AppsyncAuthStrategy,
     /// Source IP addresses associated with the request
     pub source_ip: Vec<String>,
-    /// Groups the authenticated user belongs to
+    /// Groups the authenticated user belongs to, `None` when AppSync sends `null` or omits them
     pub groups: Option<Vec<String>>,
     /// Additional claims/attributes associated with the identity
     pub claims: Value,
@@ -165,7 +204,7 @@ pub enum CognitoIdentityAuthType {
 }
 
 /// Cognito Identity Pool information for federated IAM authentication
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct CognitoFederatedIdentity {
     /// Unique identifier assigned to the authenticated/unauthenticated identity
     /// within the Cognito Identity Pool
@@ -180,16 +219,17 @@ pub struct CognitoFederatedIdentity {
     #[serde(rename = "cognitoIdentityAuthType")]
     pub auth_type: CognitoIdentityAuthType,
     /// For authenticated identities, contains information about the identity provider
-    /// used for authentication. Format varies by provider type
+    /// used for authentication. Format varies by provider type.
+    /// `None` for unauthenticated guests
     #[serde(rename = "cognitoIdentityAuthProvider")]
-    pub auth_provider: String,
+    pub auth_provider: Option<String>,
 }
 
 /// Identity information for IAM-authenticated requests.
 ///
 /// Contains AWS IAM-specific authentication details, including optional Cognito
 /// identity pool information when using federated identities.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppsyncIdentityIam {
     /// AWS account ID of the caller
@@ -200,13 +240,14 @@ pub struct AppsyncIdentityIam {
     pub username: String,
     /// Full IAM ARN of the caller
     pub user_arn: String,
-    /// Federated identity information when using Cognito Identity Pools
+    /// Federated identity information when using Cognito Identity Pools,
+    /// `None` when the `cognitoIdentity*` fields are absent or null
     #[serde(flatten)]
     pub federated_identity: Option<CognitoFederatedIdentity>,
 }
 
 /// Identity information for OIDC-authenticated requests.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AppsyncIdentityOidc {
     /// The issuer of the token
     pub iss: String,
@@ -224,7 +265,7 @@ pub struct AppsyncIdentityOidc {
 }
 
 /// Identity information for Lambda-authorized requests.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AppsyncIdentityLambda {
     /// Custom resolver context returned by the Lambda authorizer
     #[serde(rename = "resolverContext")]
@@ -252,17 +293,32 @@ pub struct AppsyncIdentityLambda {
 /// - [Lambda](AppsyncIdentity::Lambda): Custom authorization through an AWS Lambda function
 ///   that evaluates each request.
 ///
-/// - [ApiKey](AppsyncIdentity::ApiKey): Simple API key-based authentication using keys
-///   generated and managed by AppSync.
+/// - [Unauthenticated](AppsyncIdentity::Unauthenticated): No identity, AppSync sends a `null` identity
+///   for requests authorized with an API key generated and managed by AppSync.
 ///
 /// The variant is determined by the authorization configuration of your AppSync API and
 /// the authentication credentials provided in the request. Each variant contains structured
 /// information specific to that authentication mode, which can be used in resolvers for
 /// custom authorization logic.
 ///
+/// An [Unauthenticated](AppsyncIdentity::Unauthenticated) request carries no user information at all:
+/// authorization checks should match the variants they accept rather than only rejecting the variants
+/// they know, so that such a request does not slip through.
+///
+/// ```
+/// # use lambda_appsync::AppsyncIdentity;
+/// fn is_admin(identity: &AppsyncIdentity) -> bool {
+///     match identity {
+///         AppsyncIdentity::Cognito(cognito) => cognito.groups.iter().flatten().any(|g| g == "admin"),
+///         // Including `AppsyncIdentity::Unauthenticated`
+///         _ => false,
+///     }
+/// }
+/// assert!(!is_admin(&AppsyncIdentity::Unauthenticated));
+/// ```
+///
 /// More information can be found in the [AWS documentation](https://docs.aws.amazon.com/appsync/latest/devguide/security-authz.html).
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone)]
 pub enum AppsyncIdentity {
     /// Amazon Cognito User Pools authentication
     Cognito(AppsyncIdentityCognito),
@@ -272,8 +328,49 @@ pub enum AppsyncIdentity {
     Oidc(AppsyncIdentityOidc),
     /// Lambda authorizer authentication
     Lambda(AppsyncIdentityLambda),
-    /// API Key authentication (represents null identity in JSON)
-    ApiKey,
+    /// No identity, e.g. API Key authentication (represents null identity in JSON)
+    Unauthenticated,
+}
+
+impl AppsyncIdentity {
+    /// The former name of [AppsyncIdentity::Unauthenticated]
+    ///
+    /// Only usable as a value, patterns must match [AppsyncIdentity::Unauthenticated].
+    #[deprecated(note = "renamed `AppsyncIdentity::Unauthenticated`")]
+    #[allow(non_upper_case_globals)]
+    pub const ApiKey: Self = Self::Unauthenticated;
+}
+
+// Picks the variant from a key specific to each authorization type. An untagged enum would try the
+// variants in turn from a buffered copy of the identity, cloning every string it contains.
+impl<'de> Deserialize<'de> for AppsyncIdentity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let identity = match value {
+            Value::Null => return Ok(Self::Unauthenticated),
+            Value::Object(ref identity) if identity.contains_key("issuer") => {
+                serde_json::from_value(value).map(Self::Cognito)
+            }
+            Value::Object(ref identity) if identity.contains_key("accountId") => {
+                serde_json::from_value(value).map(Self::Iam)
+            }
+            Value::Object(ref identity) if identity.contains_key("iss") => {
+                serde_json::from_value(value).map(Self::Oidc)
+            }
+            Value::Object(ref identity) if identity.contains_key("resolverContext") => {
+                serde_json::from_value(value).map(Self::Lambda)
+            }
+            _ => {
+                return Err(D::Error::custom(
+                    "data did not match any variant of AppsyncIdentity",
+                ))
+            }
+        };
+        identity.map_err(D::Error::custom)
+    }
 }
 
 /// Metadata about an AppSync GraphQL operation execution.
@@ -282,22 +379,206 @@ pub enum AppsyncIdentity {
 /// including the operation type, selected fields, and variables. The type parameter
 /// `O` represents the enum generated by [appsync_lambda_main] that defines all valid
 /// operations for this Lambda resolver.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct AppsyncEventInfo<O> {
     /// The specific GraphQL operation being executed (Query/Mutation)
-    #[serde(flatten)]
     pub operation: O,
     /// Raw GraphQL selection set as a string
-    #[serde(rename = "selectionSetGraphQL")]
     pub selection_set_graphql: String,
     /// List of selected field paths in the GraphQL query
-    #[serde(rename = "selectionSetList")]
     pub selection_set_list: Vec<String>,
     /// Variables passed to the GraphQL operation
     pub variables: HashMap<String, Value>,
 }
 
+#[cfg(feature = "selection_set")]
+impl<O> AppsyncEventInfo<O> {
+    /// Returns the names of the top-level fields requested by the client, parsed from
+    /// [selection_set_graphql](Self::selection_set_graphql)
+    ///
+    /// Fields of inline fragments and fragment spreads are included and aliases are resolved to the
+    /// name of the field, e.g. to only fetch the requested attributes from a database. The set is
+    /// empty for an operation returning a scalar. Fails with an `InvalidSelectionSet` error if the
+    /// selection set cannot be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncEvent;
+    /// # use serde_json::{json, Value};
+    /// let event: AppsyncEvent<Value> = serde_json::from_value(json!({
+    ///     "info": {"parentTypeName": "Query", "fieldName": "player", "variables": {},
+    ///              "selectionSetList": ["id", "team", "team/name"],
+    ///              "selectionSetGraphQL": "{\n  id\n  team {\n    name\n  }\n}"},
+    ///     "arguments": {}, "identity": null, "source": null, "request": null
+    /// })).unwrap();
+    /// let fields = event.info.selected_fields().unwrap();
+    /// assert_eq!(fields.into_iter().collect::<Vec<_>>(), ["id", "team"]);
+    /// ```
+    pub fn selected_fields(&self) -> Result<std::collections::BTreeSet<String>, AppsyncError> {
+        selection_set::top_level_fields(&self.selection_set_graphql)
+    }
+}
+
+// The operation is deserialized from the `parentTypeName` and `fieldName` moved out of the info.
+// `#[serde(flatten)]` would buffer the unknown fields of the info and clone them for the operation.
+impl<'de, O: Deserialize<'de>> Deserialize<'de> for AppsyncEventInfo<O> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        enum Key {
+            #[serde(rename = "parentTypeName")]
+            ParentTypeName,
+            #[serde(rename = "fieldName")]
+            FieldName,
+            #[serde(rename = "selectionSetGraphQL")]
+            SelectionSetGraphql,
+            #[serde(rename = "selectionSetList")]
+            SelectionSetList,
+            #[serde(rename = "variables")]
+            Variables,
+            #[serde(other)]
+            Other,
+        }
+
+        struct InfoVisitor<O>(std::marker::PhantomData<O>);
+        impl<'de, O: Deserialize<'de>> Visitor<'de> for InfoVisitor<O> {
+            type Value = AppsyncEventInfo<O>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("the info of an AppSync event")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut parent_type_name: Option<OperationName<'de>> = None;
+                let mut field_name: Option<OperationName<'de>> = None;
+                let mut selection_set_graphql = None;
+                let mut selection_set_list = None;
+                let mut variables = None;
+                while let Some(key) = map.next_key::<Key>()? {
+                    match key {
+                        Key::ParentTypeName => parent_type_name = Some(map.next_value()?),
+                        Key::FieldName => field_name = Some(map.next_value()?),
+                        Key::SelectionSetGraphql => selection_set_graphql = Some(map.next_value()?),
+                        Key::SelectionSetList => selection_set_list = Some(map.next_value()?),
+                        Key::Variables => variables = Some(map.next_value()?),
+                        Key::Other => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                let names = parent_type_name
+                    .map(|name| ("parentTypeName", name))
+                    .into_iter()
+                    .chain(field_name.map(|name| ("fieldName", name)));
+                let operation =
+                    O::deserialize(serde::de::value::MapDeserializer::<_, A::Error>::new(names))?;
+                Ok(AppsyncEventInfo {
+                    operation,
+                    selection_set_graphql: selection_set_graphql
+                        .ok_or_else(|| A::Error::missing_field("selectionSetGraphQL"))?,
+                    selection_set_list: selection_set_list
+                        .ok_or_else(|| A::Error::missing_field("selectionSetList"))?,
+                    variables: variables.ok_or_else(|| A::Error::missing_field("variables"))?,
+                })
+            }
+        }
+
+        const FIELDS: &[&str] = &[
+            "parentTypeName",
+            "fieldName",
+            "selectionSetGraphQL",
+            "selectionSetList",
+            "variables",
+        ];
+        deserializer.deserialize_struct(
+            "AppsyncEventInfo",
+            FIELDS,
+            InfoVisitor(std::marker::PhantomData),
+        )
+    }
+}
+
+/// A name of the operation of an AppSync event, `parentTypeName` or `fieldName`, borrowed from the
+/// input when possible
+///
+/// Used by the `Operation` deserialization generated by [appsync_lambda_main] to read the names
+/// without allocating.
+#[doc(hidden)]
+pub struct OperationName<'de>(Cow<'de, str>);
+
+impl OperationName<'_> {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationName<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NameVisitor;
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = OperationName<'de>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an operation name")
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(
+                self,
+                v: &'de str,
+            ) -> Result<Self::Value, E> {
+                Ok(OperationName(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(OperationName(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(OperationName(Cow::Owned(v)))
+            }
+        }
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+// Hands the names read by `AppsyncEventInfo` over to the `Operation` without copying them
+impl<'de, E: serde::de::Error> IntoDeserializer<'de, E> for OperationName<'de> {
+    type Deserializer = OperationNameDeserializer<'de, E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        OperationNameDeserializer(self.0, std::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub struct OperationNameDeserializer<'de, E>(Cow<'de, str>, std::marker::PhantomData<E>);
+
+impl<'de, E: serde::de::Error> Deserializer<'de> for OperationNameDeserializer<'de, E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.0 {
+            Cow::Borrowed(v) => visitor.visit_borrowed_str(v),
+            Cow::Owned(v) => visitor.visit_string(v),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
 /// Represents a complete AWS AppSync event sent to a Lambda resolver.
 ///
 /// Contains all context and data needed to resolve a GraphQL operation, including
@@ -307,7 +588,7 @@ pub struct AppsyncEventInfo<O> {
 /// # Limitations
 /// - Omits the `stash` field used for pipeline resolvers
 /// - Omits the `prev` field as it's not relevant for direct Lambda resolvers
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct AppsyncEvent<O> {
     /// Authentication context
@@ -321,14 +602,97 @@ pub struct AppsyncEvent<O> {
     /// Arguments passed to the GraphQL field
     #[serde(rename = "arguments")]
     pub args: Value,
+    /// The raw JSON event, as sent by AppSync before its deserialization
+    ///
+    /// Only populated when [appsync_lambda_main] is used with `capture_raw_event = true`, `None` otherwise
+    #[serde(skip)]
+    pub raw: Option<Value>,
+    /// The context of the Lambda invocation, e.g. its request id and deadline
+    ///
+    /// Populated by the handler generated by [appsync_lambda_main], `None` if the event is
+    /// deserialized by other means. See also [AppsyncEvent::remaining_time].
+    #[serde(skip)]
+    pub context: Option<lambda_runtime::Context>,
     // Should never be usefull in a Direct Lambda Invocation context
     // pub stash: Value,
     // pub prev: Value,
 }
 
+impl<O> AppsyncEvent<O> {
+    /// Returns the time left before the Lambda invocation times out, if the [context](Self::context)
+    /// is known
+    ///
+    /// Allows long operations to stop early and return a partial result instead of being
+    /// terminated. Returns [Duration::ZERO] once the deadline is passed.
+    pub fn remaining_time(&self) -> Option<Duration> {
+        let deadline = self.context.as_ref()?.deadline();
+        Some(
+            deadline
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+
+    /// Returns the custom domain name of the AppSync API the request was sent to, if any
+    ///
+    /// AppSync only provides it when the API is invoked through a custom domain name.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncEvent;
+    /// # use serde_json::{json, Value};
+    /// let event: AppsyncEvent<Value> = serde_json::from_value(json!({
+    ///     "info": {"parentTypeName": "Query", "fieldName": "players", "variables": {},
+    ///              "selectionSetList": [], "selectionSetGraphQL": ""},
+    ///     "arguments": {}, "identity": null, "source": null,
+    ///     "request": {"headers": {"host": "api.example.com"}, "domainName": "api.example.com"}
+    /// })).unwrap();
+    /// assert_eq!(event.domain_name(), Some("api.example.com"));
+    /// ```
+    pub fn domain_name(&self) -> Option<&str> {
+        self.request.get("domainName")?.as_str()
+    }
+
+    /// Returns the `host` header of the request, identifying the AppSync API endpoint it was sent to
+    ///
+    /// AppSync does not send the API id, but the `host` of its default endpoint
+    /// (`<endpoint-id>.appsync-api.<region>.amazonaws.com`) is unique to each API, which allows a
+    /// Lambda function shared by several APIs to tell them apart. With a custom domain name,
+    /// it is the custom domain name.
+    pub fn host(&self) -> Option<&str> {
+        self.header("host")
+    }
+
+    /// Returns the value of a header of the request, the name being case-insensitive
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncEvent;
+    /// # use serde_json::{json, Value};
+    /// let event: AppsyncEvent<Value> = serde_json::from_value(json!({
+    ///     "info": {"parentTypeName": "Query", "fieldName": "players", "variables": {},
+    ///              "selectionSetList": [], "selectionSetGraphQL": ""},
+    ///     "arguments": {}, "identity": null, "source": null,
+    ///     "request": {"headers": {"x-correlation-id": "abc"}, "domainName": null}
+    /// })).unwrap();
+    /// assert_eq!(event.header("X-Correlation-Id"), Some("abc"));
+    /// assert_eq!(event.header("x-api-key"), None);
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.request
+            .get("headers")?
+            .as_object()?
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))?
+            .1
+            .as_str()
+    }
+}
+
 /// Response structure returned to AWS AppSync from a Lambda resolver.
 ///
-/// Can contain either successful data or error information, but not both.
+/// Can contain either successful data or error information, or both for the partial
+/// results of a [BatchResult].
 /// Should be constructed using From implementations for either [Value] (success)
 /// or [AppsyncError] (failure).
 ///
@@ -364,6 +728,49 @@ impl AppsyncResponse {
     pub fn unauthorized() -> Self {
         AppsyncError::new("Unauthorized", "This operation cannot be authorized").into()
     }
+
+    /// Returns `true` if this response carries an error
+    ///
+    /// # Examples
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, AppsyncResponse};
+    /// # use serde_json::json;
+    /// assert!(AppsyncResponse::unauthorized().is_error());
+    /// assert!(!AppsyncResponse::from(json!({ "id": 123 })).is_error());
+    /// ```
+    pub fn is_error(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Returns the error type of this response, if it carries an error
+    ///
+    /// # Examples
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, AppsyncResponse};
+    /// # use serde_json::json;
+    /// let response: AppsyncResponse = AppsyncError::new("NotFound", "User not found").into();
+    /// assert_eq!(response.error_type(), Some("NotFound"));
+    /// assert_eq!(AppsyncResponse::from(json!({ "id": 123 })).error_type(), None);
+    /// ```
+    pub fn error_type(&self) -> Option<&str> {
+        self.error.as_ref().map(|e| e.error_type.as_str())
+    }
+
+    /// Returns the data of this response, if any
+    ///
+    /// An error response usually has no data, but may carry a partial result (see [BatchResult]).
+    ///
+    /// # Examples
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, AppsyncResponse};
+    /// # use serde_json::json;
+    /// let response = AppsyncResponse::from(json!({ "id": 123 }));
+    /// assert_eq!(response.data(), Some(&json!({ "id": 123 })));
+    /// assert_eq!(AppsyncResponse::unauthorized().data(), None);
+    /// ```
+    pub fn data(&self) -> Option<&Value> {
+        self.data.as_ref()
+    }
 }
 
 impl From<Value> for AppsyncResponse {
@@ -375,14 +782,38 @@ impl From<Value> for AppsyncResponse {
     }
 }
 impl From<AppsyncError> for AppsyncResponse {
-    fn from(value: AppsyncError) -> Self {
+    fn from(mut value: AppsyncError) -> Self {
         Self {
-            data: None,
+            data: value.data.take(),
             error: Some(value),
         }
     }
 }
 
+/// Value returned by the `function_handler` generated by `appsync_lambda_main!` with the `health_check_op`
+/// option: a single response or a list of them, following the shape of the payload.
+///
+/// Serializes to the inner response or list of responses.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum HandlerResponse {
+    /// Response to a single event
+    Single(AppsyncResponse),
+    /// Responses to a batch of events, in the order of the events
+    Batch(Vec<AppsyncResponse>),
+}
+
+impl From<AppsyncResponse> for HandlerResponse {
+    fn from(value: AppsyncResponse) -> Self {
+        Self::Single(value)
+    }
+}
+impl From<Vec<AppsyncResponse>> for HandlerResponse {
+    fn from(value: Vec<AppsyncResponse>) -> Self {
+        Self::Batch(value)
+    }
+}
+
 /// Error type for AWS AppSync operations
 ///
 /// Multiple errors can be combined in one using the pipe operator
@@ -395,7 +826,8 @@ impl From<AppsyncError> for AppsyncResponse {
 /// // error_message: "Email address is invalid\nUser not found in database"
 /// ```
 ///
-/// Can be created from any AWS SDK error or directly by the user.
+/// Can be created from any AWS SDK error or directly by the user with [AppsyncError::new], as the
+/// struct is `#[non_exhaustive]`.
 ///
 /// # Example
 /// ```
@@ -418,11 +850,18 @@ impl From<AppsyncError> for AppsyncResponse {
 #[derive(Debug, Error, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[error("{error_type}: {error_message}")]
+#[non_exhaustive]
 pub struct AppsyncError {
     /// The type/category of error that occurred (e.g. "ValidationError", "NotFound", "DatabaseError")
     pub error_type: String,
     /// A detailed message describing the specific error condition
     pub error_message: String,
+    // Partial result returned along with the error, see [BatchResult]
+    #[serde(skip)]
+    data: Option<Value>,
+    // Additional information on the error, returned as the `errorInfo` of the response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_info: Option<Value>,
 }
 impl AppsyncError {
     /// Creates a new AppSync error with the specified error type and message
@@ -440,8 +879,77 @@ impl AppsyncError {
         AppsyncError {
             error_type: error_type.into(),
             error_message: error_message.into(),
+            data: None,
+            error_info: None,
         }
     }
+
+    /// Sets a partial result returned in the `data` of the response along with the error
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, AppsyncResponse};
+    /// # use serde_json::json;
+    /// let error = AppsyncError::new("PartialFailure", "Some items could not be loaded")
+    ///     .with_data(json!([{ "id": 1 }, null]));
+    /// let response = AppsyncResponse::from(error);
+    /// assert_eq!(response.data(), Some(&json!([{ "id": 1 }, null])));
+    /// ```
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets additional information on the error, returned as the `errorInfo` of the response
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, AppsyncResponse};
+    /// # use serde_json::json;
+    /// let error = AppsyncError::new("NotFound", "User with ID 123 not found")
+    ///     .with_error_info(json!({ "id": 123 }));
+    /// let response = serde_json::to_value(AppsyncResponse::from(error)).unwrap();
+    /// assert_eq!(response["errorInfo"], json!({ "id": 123 }));
+    /// ```
+    pub fn with_error_info(mut self, error_info: Value) -> Self {
+        self.error_info = Some(error_info);
+        self
+    }
+
+    /// Returns `true` if the error is a transient AWS error, worth retrying
+    ///
+    /// An error is retryable if its type (or one of its types, for combined errors) is a throttling or
+    /// transient service error code of the AWS SDKs, such as `ThrottlingException`,
+    /// `ProvisionedThroughputExceededException` or `TransactionConflictException`.
+    /// This is the default predicate of the `retry` option of
+    /// [appsync_operation](crate::appsync_operation).
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncError;
+    /// assert!(AppsyncError::new("ThrottlingException", "Rate exceeded").is_retryable());
+    /// assert!(!AppsyncError::new("ValidationError", "Invalid email").is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        const RETRYABLE_ERROR_TYPES: &[&str] = &[
+            "Throttling",
+            "ThrottlingException",
+            "ThrottledException",
+            "RequestThrottled",
+            "RequestThrottledException",
+            "TooManyRequestsException",
+            "RequestLimitExceeded",
+            "ProvisionedThroughputExceededException",
+            "TransactionConflictException",
+            "SlowDown",
+            "InternalServerError",
+            "InternalFailure",
+            "ServiceUnavailable",
+        ];
+        self.error_type
+            .split('|')
+            .any(|error_type| RETRYABLE_ERROR_TYPES.contains(&error_type))
+    }
 }
 impl<T: ProvideErrorMetadata> From<T> for AppsyncError {
     fn from(value: T) -> Self {
@@ -449,6 +957,8 @@ impl<T: ProvideErrorMetadata> From<T> for AppsyncError {
         AppsyncError {
             error_type: meta.code().unwrap_or("Unknown").to_owned(),
             error_message: meta.message().unwrap_or_default().to_owned(),
+            data: None,
+            error_info: None,
         }
     }
 }
@@ -459,6 +969,8 @@ impl BitOr for AppsyncError {
         AppsyncError {
             error_type: format!("{}|{}", self.error_type, rhs.error_type),
             error_message: format!("{}\n{}", self.error_message, rhs.error_message),
+            data: self.data.or(rhs.data),
+            error_info: self.error_info.or(rhs.error_info),
         }
     }
 }
@@ -473,6 +985,9 @@ impl BitOr for AppsyncError {
 /// * `Ok(T)` - Successfully deserialized value of type T
 /// * `Err(AppsyncError)` - Error if argument is missing or invalid format
 ///
+/// With the `serde_path_to_error` feature, the error message also reports the JSON path
+/// of the value that failed to deserialize (e.g. `input.filters[2].team`).
+///
 /// # Examples
 /// ```
 /// # use serde_json::json;
@@ -505,17 +1020,95 @@ pub fn arg_from_json<T: DeserializeOwned>(
     args: &mut serde_json::Value,
     arg_name: &'static str,
 ) -> Result<T, AppsyncError> {
-    serde_json::from_value(
-        args.get_mut(arg_name)
-            .unwrap_or(&mut serde_json::Value::Null)
-            .take(),
-    )
-    .map_err(|e| {
+    let arg = args
+        .get_mut(arg_name)
+        .unwrap_or(&mut serde_json::Value::Null)
+        .take();
+
+    #[cfg(not(feature = "serde_path_to_error"))]
+    let result = serde_json::from_value(arg).map_err(|e| {
         AppsyncError::new(
             "InvalidArgs",
             format!("Argument \"{arg_name}\" is not the expected format ({e})"),
         )
-    })
+    });
+
+    #[cfg(feature = "serde_path_to_error")]
+    let result = serde_path_to_error::deserialize(arg).map_err(|e| {
+        let path = e.path().to_string();
+        let path = match path.as_str() {
+            // The argument itself is invalid
+            "." => arg_name.to_owned(),
+            // The argument is a list
+            p if p.starts_with('[') => format!("{arg_name}{p}"),
+            p => format!("{arg_name}.{p}"),
+        };
+        AppsyncError::new(
+            "InvalidArgs",
+            format!(
+                "Argument \"{arg_name}\" is not the expected format at `{path}` ({})",
+                e.inner()
+            ),
+        )
+    });
+
+    result
+}
+
+/// Extracts an optional argument as a [Nullable], telling an absent argument from an explicit `null`
+///
+/// Used by the code generated by [appsync_lambda_main] for the arguments selected with the
+/// `nullable_arg` option. Errors are the same as [arg_from_json].
+///
+/// # Examples
+/// ```
+/// # use serde_json::json;
+/// # use lambda_appsync::{nullable_arg_from_json, Nullable};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut args = json!({
+///     "nickname": null,
+///     "level": 3
+/// });
+///
+/// let nickname: Nullable<String> = nullable_arg_from_json(&mut args, "nickname")?;
+/// assert_eq!(nickname, Nullable::Null);
+///
+/// let level: Nullable<i32> = nullable_arg_from_json(&mut args, "level")?;
+/// assert_eq!(level, Nullable::Value(3));
+///
+/// let team: Nullable<String> = nullable_arg_from_json(&mut args, "team")?;
+/// assert_eq!(team, Nullable::Absent);
+/// # Ok(())
+/// # }
+/// ```
+pub fn nullable_arg_from_json<T: DeserializeOwned>(
+    args: &mut serde_json::Value,
+    arg_name: &'static str,
+) -> Result<Nullable<T>, AppsyncError> {
+    if args.get(arg_name).is_none() {
+        return Ok(Nullable::Absent);
+    }
+    arg_from_json(args, arg_name)
+}
+
+/// Extracts a list argument like [arg_from_json], skipping its `null` elements
+///
+/// Used by the operations of [appsync_lambda_main](crate::appsync_lambda_main) given
+/// `lists_non_null = true` for their list arguments.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::list_arg_from_json;
+/// # use serde_json::json;
+/// let mut args = json!({"ids": ["1", null, "2"]});
+/// let ids: Vec<String> = list_arg_from_json(&mut args, "ids").unwrap();
+/// assert_eq!(ids, ["1", "2"]);
+/// ```
+pub fn list_arg_from_json<T: NonNullList>(
+    args: &mut serde_json::Value,
+    arg_name: &'static str,
+) -> Result<T, AppsyncError> {
+    arg_from_json::<T::WithNulls>(args, arg_name).map(T::skip_nulls)
 }
 
 /// Serializes a value into a JSON Value for AppSync responses
@@ -560,6 +1153,286 @@ pub fn res_to_json<T: Serialize>(res: T) -> serde_json::Value {
     serde_json::to_value(res).expect("Appsync schema objects are JSON compatible")
 }
 
+/// Serializes the value returned by an operation whose return type has a `type_override`
+///
+/// Used instead of [res_to_json] by [appsync_lambda_main](crate::appsync_lambda_main), so that an
+/// override type that does not implement [Serialize] is reported by a single error at the override.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "override type `{Self}` must implement `Serialize`",
+    label = "this type is used in a `type_override` of an operation return type"
+)]
+pub trait OverrideToJson {
+    fn override_to_json(self) -> serde_json::Value;
+}
+// `do_not_recommend` is only known from Rust 1.85, older compilers report the `Serialize` bound instead
+#[allow(unknown_or_malformed_diagnostic_attributes)]
+#[diagnostic::do_not_recommend]
+impl<T: Serialize> OverrideToJson for T {
+    fn override_to_json(self) -> serde_json::Value {
+        res_to_json(self)
+    }
+}
+
+/// Deserializes a value, falling back to its default value when it is null
+///
+/// Used with `#[serde(default, deserialize_with = "...")]` by the fields of the
+/// `required_override = Type.field: default` option of
+/// [appsync_lambda_main](crate::appsync_lambda_main), so that an absent or null value gives the default value.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::{null_as_default, serde::Deserialize};
+/// #[derive(Deserialize)]
+/// struct Player {
+///     #[serde(default, deserialize_with = "null_as_default")]
+///     nickname: String,
+/// }
+/// let player: Player = serde_json::from_str(r#"{"nickname": null}"#).unwrap();
+/// assert_eq!(player.nickname, "");
+/// let player: Player = serde_json::from_str(r#"{"nickname": "Ferris"}"#).unwrap();
+/// assert_eq!(player.nickname, "Ferris");
+/// ```
+pub fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// A list type whose `null` elements can be skipped when deserializing, see the
+/// `lists_non_null` option of [appsync_lambda_main](crate::appsync_lambda_main)
+///
+/// Implemented for `Vec<T>` and `Option<Vec<T>>`. Only the elements of the outer list are skipped, the
+/// elements of nested lists must not be `null`.
+pub trait NonNullList: Sized {
+    /// The type of the list as written in the GraphQL schema, with nullable elements
+    type WithNulls: DeserializeOwned;
+    /// Drops the `null` elements of the list
+    fn skip_nulls(list: Self::WithNulls) -> Self;
+}
+impl<T: DeserializeOwned> NonNullList for Vec<T> {
+    type WithNulls = Vec<Option<T>>;
+    fn skip_nulls(list: Self::WithNulls) -> Self {
+        list.into_iter().flatten().collect()
+    }
+}
+impl<T: NonNullList> NonNullList for Option<T> {
+    type WithNulls = Option<T::WithNulls>;
+    fn skip_nulls(list: Self::WithNulls) -> Self {
+        list.map(T::skip_nulls)
+    }
+}
+
+/// Deserializes a list, skipping its `null` elements
+///
+/// Used with `#[serde(deserialize_with = "...")]` by the list fields of the types and inputs when
+/// [appsync_lambda_main](crate::appsync_lambda_main) is given `lists_non_null = true`.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::skip_null_elements;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Team {
+///     #[serde(deserialize_with = "skip_null_elements")]
+///     players: Vec<String>,
+/// }
+/// let team: Team = serde_json::from_str(r#"{"players": ["Ferris", null, "Corro"]}"#).unwrap();
+/// assert_eq!(team.players, ["Ferris", "Corro"]);
+/// ```
+pub fn skip_null_elements<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: NonNullList,
+{
+    T::WithNulls::deserialize(deserializer).map(T::skip_nulls)
+}
+
+/// Formats a value as a part of a cache key, see the `cache_key` option of
+/// [appsync_operation](crate::appsync_operation)
+///
+/// Strings are used as is and other values as compact JSON, `%` and `:` being percent-encoded so that
+/// the parts of a key can be separated with `:` without ambiguity.
+///
+/// # Errors
+/// Fails if the value cannot be serialized to JSON, e.g. a map with non-string keys.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::cache_key_part;
+/// assert_eq!(cache_key_part("ferris").unwrap(), "ferris");
+/// assert_eq!(cache_key_part("a:b").unwrap(), "a%3Ab");
+/// assert_eq!(cache_key_part(&Some(3)).unwrap(), "3");
+/// assert_eq!(cache_key_part(&vec!["a", "b"]).unwrap(), r#"["a","b"]"#);
+/// ```
+pub fn cache_key_part<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let part = match serde_json::to_value(value)? {
+        Value::String(s) => s,
+        value => value.to_string(),
+    };
+    Ok(part.replace('%', "%25").replace(':', "%3A"))
+}
+
+/// Builds the [Embedded Metric Format](https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/CloudWatch_Embedded_Metric_Format_Specification.html)
+/// record of an operation, see the `emf_metrics` option of [appsync_lambda_main](crate::appsync_lambda_main)
+///
+/// The record has the `Latency` (in milliseconds) and `Count` metrics of the `LambdaAppsync` namespace,
+/// with two dimension sets: `Operation` and `Operation, ErrorType`. `Operation` is the
+/// `parentTypeName.fieldName` of the operation and `ErrorType` the error type of its response, `None`
+/// when it succeeded. Printed as a single line to the standard output of a Lambda function, it is turned
+/// into CloudWatch metrics.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::emf_metrics_record;
+/// # use std::time::Duration;
+/// let record = emf_metrics_record("Query.players", None, Duration::from_millis(12));
+/// assert_eq!(record["Operation"], "Query.players");
+/// assert_eq!(record["ErrorType"], "None");
+/// assert_eq!(record["Latency"], 12.0);
+/// assert_eq!(record["Count"], 1);
+/// assert_eq!(record["_aws"]["CloudWatchMetrics"][0]["Namespace"], "LambdaAppsync");
+/// ```
+pub fn emf_metrics_record(operation: &str, error_type: Option<&str>, latency: Duration) -> Value {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    serde_json::json!({
+        "_aws": {
+            "Timestamp": timestamp,
+            "CloudWatchMetrics": [{
+                "Namespace": "LambdaAppsync",
+                "Dimensions": [["Operation"], ["Operation", "ErrorType"]],
+                "Metrics": [
+                    {"Name": "Latency", "Unit": "Milliseconds"},
+                    {"Name": "Count", "Unit": "Count"}
+                ]
+            }]
+        },
+        "Operation": operation,
+        "ErrorType": error_type.unwrap_or("None"),
+        "Latency": latency.as_secs_f64() * 1000.0,
+        "Count": 1
+    })
+}
+
+/// Replaces the `arguments` of an event with the value found at a JSON pointer of the event, see the
+/// `args_source` option of [appsync_lambda_main](crate::appsync_lambda_main)
+///
+/// An array payload is a batch of events, each of them is handled separately. A missing value gives
+/// `null` arguments.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::args_from_source;
+/// # use serde_json::json;
+/// let mut payload = json!({"arguments": {}, "prev": {"result": {"id": "123"}}});
+/// args_from_source(&mut payload, "/prev/result");
+/// assert_eq!(payload["arguments"], json!({"id": "123"}));
+/// ```
+pub fn args_from_source(payload: &mut Value, pointer: &str) {
+    match payload {
+        Value::Array(events) => events
+            .iter_mut()
+            .for_each(|event| args_from_source(event, pointer)),
+        Value::Object(_) => {
+            let args = payload.pointer_mut(pointer).map(Value::take);
+            payload["arguments"] = args.unwrap_or_default();
+        }
+        _ => (),
+    }
+}
+
+/// Parses the `arguments` of an event when they are a JSON-encoded string, see the
+/// `args_may_be_string` option of [appsync_lambda_main](crate::appsync_lambda_main)
+///
+/// An array payload is a batch of events, each of them is handled separately. Arguments that are not a
+/// string, or a string that is not JSON, are left untouched.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::parse_string_args;
+/// # use serde_json::json;
+/// let mut payload = json!({"arguments": r#"{"id": "123"}"#});
+/// parse_string_args(&mut payload);
+/// assert_eq!(payload["arguments"], json!({"id": "123"}));
+/// ```
+pub fn parse_string_args(payload: &mut Value) {
+    match payload {
+        Value::Array(events) => events.iter_mut().for_each(parse_string_args),
+        Value::Object(event) => {
+            if let Some(Value::String(args)) = event.get("arguments") {
+                if let Ok(args) = serde_json::from_str(args) {
+                    event.insert("arguments".to_owned(), args);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Generates an arbitrary `AWSJSON` value, used by the `Arbitrary` implementations generated by
+/// the `arbitrary` option of [appsync_lambda_main] as [Value] cannot implement `Arbitrary`
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+pub fn arbitrary_json(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Value> {
+    // Nested arrays and objects are limited to a depth of 2
+    fn value(u: &mut arbitrary::Unstructured<'_>, depth: u8) -> arbitrary::Result<Value> {
+        let kinds = if depth == 0 { 4 } else { 6 };
+        Ok(match u.choose_index(kinds)? {
+            0 => Value::Null,
+            1 => Value::Bool(u.arbitrary()?),
+            2 => Value::from(u.arbitrary::<i64>()?),
+            3 => Value::String(u.arbitrary()?),
+            4 => {
+                let mut array = vec![];
+                while u.arbitrary()? {
+                    array.push(value(u, depth - 1)?);
+                }
+                Value::Array(array)
+            }
+            _ => {
+                let mut object = serde_json::Map::new();
+                while u.arbitrary()? {
+                    object.insert(u.arbitrary()?, value(u, depth - 1)?);
+                }
+                Value::Object(object)
+            }
+        })
+    }
+    value(u, 2)
+}
+
+/// Strategy generating arbitrary `AWSJSON` values, used by the strategies generated by the
+/// `proptest` option of [appsync_lambda_main] as [Value] cannot implement `Arbitrary`
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub fn json_strategy() -> proptest::strategy::BoxedStrategy<Value> {
+    use proptest::{
+        arbitrary::any,
+        collection::{btree_map, vec},
+        strategy::{Just, Strategy},
+    };
+    let leaf = proptest::prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<String>().prop_map(Value::from),
+    ];
+    // Nested arrays and objects are limited to a depth of 2
+    leaf.prop_recursive(2, 16, 4, |inner| {
+        proptest::prop_oneof![
+            vec(inner.clone(), 0..4).prop_map(Value::Array),
+            btree_map(any::<String>(), inner, 0..4)
+                .prop_map(|object| Value::Object(object.into_iter().collect())),
+        ]
+    })
+    .boxed()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -621,6 +1494,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_appsync_identity_cognito_without_groups() {
+        let mut json = json!({
+            "sub": "user123",
+            "username": "testuser",
+            "issuer": "https://cognito-idp.region.amazonaws.com/pool_id",
+            "defaultAuthStrategy": "ALLOW",
+            "sourceIp": ["1.2.3.4"],
+            "claims": {}
+        });
+        let AppsyncIdentity::Cognito(cognito) = serde_json::from_value(json.clone()).unwrap()
+        else {
+            panic!("Expected Cognito variant");
+        };
+        assert_eq!(cognito.groups, None);
+
+        json["groups"] = Value::Null;
+        let AppsyncIdentity::Cognito(cognito) = serde_json::from_value(json).unwrap() else {
+            panic!("Expected Cognito variant");
+        };
+        assert_eq!(cognito.groups, None);
+    }
+
     #[test]
     fn test_appsync_identity_iam() {
         let json = json!({
@@ -668,9 +1564,57 @@ mod tests {
                 CognitoIdentityAuthType::Authenticated
             ));
             assert_eq!(
-                federated.auth_provider,
-                "cognito-idp.region.amazonaws.com/pool_id"
+                federated.auth_provider.as_deref(),
+                Some("cognito-idp.region.amazonaws.com/pool_id")
+            );
+        } else {
+            panic!("Expected IAM variant");
+        }
+    }
+
+    #[test]
+    fn test_appsync_identity_iam_with_cognito_guest() {
+        let json = json!({
+            "accountId": "123456789012",
+            "sourceIp": ["1.2.3.4"],
+            "username": "AROAEXAMPLE:CognitoIdentityCredentials",
+            "userArn": "arn:aws:sts::123456789012:assumed-role/Unauth_Role/CognitoIdentityCredentials",
+            "cognitoIdentityId": "region:id",
+            "cognitoIdentityPoolId": "region:pool_id",
+            "cognitoIdentityAuthType": "unauthenticated",
+            "cognitoIdentityAuthProvider": null
+        });
+
+        if let AppsyncIdentity::Iam(iam) = serde_json::from_value(json).unwrap() {
+            let federated = iam.federated_identity.unwrap();
+            assert_eq!(federated.identity_id, "region:id");
+            assert_eq!(federated.identity_pool_id, "region:pool_id");
+            assert_eq!(
+                federated.auth_type,
+                CognitoIdentityAuthType::Unauthenticated
             );
+            assert!(federated.auth_provider.is_none());
+        } else {
+            panic!("Expected IAM variant");
+        }
+    }
+
+    #[test]
+    fn test_appsync_identity_iam_with_null_cognito() {
+        let json = json!({
+            "accountId": "123456789012",
+            "sourceIp": ["1.2.3.4"],
+            "username": "IAMUser",
+            "userArn": "arn:aws:iam::123456789012:user/IAMUser",
+            "cognitoIdentityId": null,
+            "cognitoIdentityPoolId": null,
+            "cognitoIdentityAuthType": null,
+            "cognitoIdentityAuthProvider": null
+        });
+
+        if let AppsyncIdentity::Iam(iam) = serde_json::from_value(json).unwrap() {
+            assert_eq!(iam.username, "IAMUser");
+            assert!(iam.federated_identity.is_none());
         } else {
             panic!("Expected IAM variant");
         }
@@ -744,16 +1688,77 @@ mod tests {
     }
 
     #[test]
-    fn test_appsync_identity_api_key() {
+    fn test_appsync_identity_unauthenticated() {
         let json = serde_json::Value::Null;
 
-        if let AppsyncIdentity::ApiKey = serde_json::from_value(json).unwrap() {
-            // Test passes if we get the ApiKey variant
+        if let AppsyncIdentity::Unauthenticated = serde_json::from_value(json).unwrap() {
+            // Test passes if we get the Unauthenticated variant
         } else {
-            panic!("Expected ApiKey variant");
+            panic!("Expected Unauthenticated variant");
         }
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_appsync_identity_api_key_alias() {
+        assert!(matches!(
+            AppsyncIdentity::ApiKey,
+            AppsyncIdentity::Unauthenticated
+        ));
+    }
+
+    #[test]
+    fn test_appsync_identity_unknown() {
+        let err = serde_json::from_value::<AppsyncIdentity>(json!({"sub": "user123"})).unwrap_err();
+        assert!(err.to_string().contains("did not match any variant"));
+
+        // The variant is chosen from its specific key, then its own errors are reported
+        let err =
+            serde_json::from_value::<AppsyncIdentity>(json!({"issuer": "cognito"})).unwrap_err();
+        assert!(err.to_string().contains("missing field `sub`"));
+    }
+
+    #[test]
+    fn test_appsync_event_request_accessors() {
+        let event = |request: Value| -> AppsyncEvent<Value> {
+            serde_json::from_value(json!({
+                "info": {
+                    "parentTypeName": "Query",
+                    "fieldName": "players",
+                    "variables": {},
+                    "selectionSetList": [],
+                    "selectionSetGraphQL": ""
+                },
+                "arguments": {},
+                "identity": null,
+                "source": null,
+                "request": request
+            }))
+            .unwrap()
+        };
+
+        let with_custom_domain = event(json!({
+            "headers": {"host": "api.example.com", "x-api-key": "key"},
+            "domainName": "api.example.com"
+        }));
+        assert_eq!(with_custom_domain.domain_name(), Some("api.example.com"));
+        assert_eq!(with_custom_domain.host(), Some("api.example.com"));
+
+        let default_endpoint = event(json!({
+            "headers": {"host": "abcdefghij.appsync-api.eu-west-1.amazonaws.com"},
+            "domainName": null
+        }));
+        assert_eq!(default_endpoint.domain_name(), None);
+        assert_eq!(
+            default_endpoint.host(),
+            Some("abcdefghij.appsync-api.eu-west-1.amazonaws.com")
+        );
+
+        let no_request = event(Value::Null);
+        assert_eq!(no_request.domain_name(), None);
+        assert_eq!(no_request.host(), None);
+    }
+
     #[test]
     fn test_appsync_response() {
         let success = AppsyncResponse::from(json!({"field": "value"}));
@@ -765,6 +1770,48 @@ mod tests {
         assert!(error.error.is_some());
     }
 
+    #[test]
+    fn test_appsync_response_error_accessors() {
+        let success = AppsyncResponse::from(json!({"field": "value"}));
+        assert!(!success.is_error());
+        assert_eq!(success.error_type(), None);
+
+        let error = AppsyncResponse::from(AppsyncError::new("TestError", "message"));
+        assert!(error.is_error());
+        assert_eq!(error.error_type(), Some("TestError"));
+
+        let unauthorized = AppsyncResponse::unauthorized();
+        assert!(unauthorized.is_error());
+        assert_eq!(unauthorized.error_type(), Some("Unauthorized"));
+    }
+
+    #[test]
+    fn test_appsync_error_builder() {
+        let error = AppsyncError::new("NotFound", "message")
+            .with_data(json!({"id": 1}))
+            .with_error_info(json!({"retryAfter": 10}));
+        let response = serde_json::to_value(AppsyncResponse::from(error)).unwrap();
+        assert_eq!(
+            response,
+            json!({
+                "data": {"id": 1},
+                "errorType": "NotFound",
+                "errorMessage": "message",
+                "errorInfo": {"retryAfter": 10}
+            })
+        );
+
+        let response = serde_json::to_value(AppsyncResponse::from(AppsyncError::new(
+            "NotFound", "message",
+        )))
+        .unwrap();
+        assert!(response.get("errorInfo").is_none());
+
+        let combined = AppsyncError::new("Error1", "msg1")
+            | AppsyncError::new("Error2", "msg2").with_error_info(json!("info"));
+        assert_eq!(combined.error_info, Some(json!("info")));
+    }
+
     #[test]
     fn test_appsync_error() {
         let error = AppsyncError::new("TestError", "message");
@@ -800,6 +1847,66 @@ mod tests {
         assert!(err.is_err());
     }
 
+    #[test]
+    fn test_is_retryable() {
+        assert!(AppsyncError::new("ProvisionedThroughputExceededException", "").is_retryable());
+        assert!(!AppsyncError::new("ConditionalCheckFailedException", "").is_retryable());
+        let combined =
+            AppsyncError::new("ValidationError", "") | AppsyncError::new("Throttling", "");
+        assert!(combined.is_retryable());
+    }
+
+    #[test]
+    fn test_nullable_arg_from_json() {
+        let mut args = json!({
+            "null": null,
+            "number": 42
+        });
+
+        let n: Nullable<i32> = nullable_arg_from_json(&mut args, "null").unwrap();
+        assert_eq!(n, Nullable::Null);
+
+        let n: Nullable<i32> = nullable_arg_from_json(&mut args, "number").unwrap();
+        assert_eq!(n, Nullable::Value(42));
+
+        let n: Nullable<i32> = nullable_arg_from_json(&mut args, "missing").unwrap();
+        assert_eq!(n, Nullable::Absent);
+
+        let mut args = json!({"number": "42"});
+        let err: Result<Nullable<i32>, _> = nullable_arg_from_json(&mut args, "number");
+        assert_eq!(err.unwrap_err().error_type, "InvalidArgs");
+    }
+
+    #[cfg(feature = "serde_path_to_error")]
+    #[test]
+    fn test_arg_from_json_error_path() {
+        #[derive(Debug, Deserialize)]
+        struct Filter {
+            #[allow(dead_code)]
+            team: String,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Input {
+            #[allow(dead_code)]
+            filters: Vec<Filter>,
+        }
+        let mut args = json!({
+            "input": {"filters": [{"team": "RUST"}, {"team": "JS"}, {"team": 42}]},
+            "ids": ["a", "b", 3],
+            "number": "not a number"
+        });
+
+        let err = arg_from_json::<Input>(&mut args, "input").unwrap_err();
+        assert_eq!(err.error_type, "InvalidArgs");
+        assert!(err.error_message.contains("at `input.filters[2].team`"));
+
+        let err = arg_from_json::<Vec<String>>(&mut args, "ids").unwrap_err();
+        assert!(err.error_message.contains("at `ids[2]`"));
+
+        let err = arg_from_json::<i32>(&mut args, "number").unwrap_err();
+        assert!(err.error_message.contains("at `number`"));
+    }
+
     #[test]
     fn test_res_to_json() {
         #[derive(Serialize)]
@@ -817,4 +1924,41 @@ mod tests {
         assert_eq!(res_to_json(42), json!(42));
         assert_eq!(res_to_json("test"), json!("test"));
     }
+
+    #[test]
+    fn test_cache_key_part_unserializable() {
+        let map = std::collections::HashMap::from([((1, 2), 3)]);
+        assert!(cache_key_part(&map).is_err());
+    }
+
+    #[test]
+    fn test_emf_metrics_record() {
+        let mut record = emf_metrics_record(
+            "Mutation.createPlayer",
+            Some("Unauthorized"),
+            Duration::from_micros(1500),
+        );
+        assert!(record["_aws"]["Timestamp"].as_u64().unwrap() > 0);
+        record["_aws"]["Timestamp"] = json!(0);
+        assert_eq!(
+            record,
+            json!({
+                "_aws": {
+                    "Timestamp": 0,
+                    "CloudWatchMetrics": [{
+                        "Namespace": "LambdaAppsync",
+                        "Dimensions": [["Operation"], ["Operation", "ErrorType"]],
+                        "Metrics": [
+                            {"Name": "Latency", "Unit": "Milliseconds"},
+                            {"Name": "Count", "Unit": "Count"}
+                        ]
+                    }]
+                },
+                "Operation": "Mutation.createPlayer",
+                "ErrorType": "Unauthorized",
+                "Latency": 1.5,
+                "Count": 1
+            })
+        );
+    }
 }
@@ -0,0 +1,59 @@
+//! Middleware wrapped around every operation
+//!
+//! When given `middleware = fn_name`, [appsync_lambda_main](crate::appsync_lambda_main) calls
+//! an `async fn(AppsyncEvent<Operation>, Next<Operation>) -> AppsyncResponse` with every event
+//! instead of executing its operation directly. The middleware executes the operation by calling
+//! [Next::run] with the event, which lets it run code before and after the operation (timing,
+//! logging, tenant scoping...), post-process the response, or short-circuit the operation by
+//! returning a response without calling [Next::run].
+//!
+//! The response passed to the middleware already went through the `on_error` observer and the
+//! `operation_timeout_ms` of the macro, if any.
+//!
+//! # Example
+//! ```
+//! # lambda_appsync::appsync_lambda_main!(
+//! #    "schema.graphql",
+//! #    exclude_lambda_handler = true,
+//! #    middleware = timing,
+//! # );
+//! use lambda_appsync::{middleware::Next, AppsyncEvent, AppsyncResponse};
+//!
+//! async fn timing(event: AppsyncEvent<Operation>, next: Next<Operation>) -> AppsyncResponse {
+//!     let operation = event.info.operation;
+//!     let start = std::time::Instant::now();
+//!     let response = next.run(event).await;
+//!     println!("{operation:?} took {:?}", start.elapsed());
+//!     response
+//! }
+//! # fn main() {}
+//! ```
+use std::{fmt, future::Future, pin::Pin};
+
+use crate::{AppsyncEvent, AppsyncResponse};
+
+type BoxedOperation<O> =
+    fn(AppsyncEvent<O>) -> Pin<Box<dyn Future<Output = AppsyncResponse> + Send>>;
+
+/// The continuation executing the operation of an event, given to the middleware
+pub struct Next<O> {
+    operation: BoxedOperation<O>,
+}
+
+impl<O> Next<O> {
+    #[doc(hidden)]
+    pub fn new(operation: BoxedOperation<O>) -> Self {
+        Self { operation }
+    }
+
+    /// Executes the operation of `event` and returns its response
+    pub async fn run(self, event: AppsyncEvent<O>) -> AppsyncResponse {
+        (self.operation)(event).await
+    }
+}
+
+impl<O> fmt::Debug for Next<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next").finish_non_exhaustive()
+    }
+}
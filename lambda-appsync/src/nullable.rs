@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+/// An optional value distinguishing an absent value from an explicit `null`.
+///
+/// GraphQL makes a difference between an optional argument that is not provided and one that is
+/// explicitly set to `null`, which matters for partial updates: "don't touch" vs "set to null".
+/// An `Option<T>` cannot tell them apart, a [Nullable] can.
+///
+/// Operation arguments are extracted as [Nullable] when selected with the `nullable_arg` option of
+/// [appsync_lambda_main](crate::appsync_lambda_main). In a struct, use it with `#[serde(default)]`
+/// so that a missing field deserializes as [Nullable::Absent].
+///
+/// # Example
+/// ```
+/// use lambda_appsync::Nullable;
+///
+/// #[derive(serde::Deserialize)]
+/// struct PlayerPatch {
+///     #[serde(default)]
+///     nickname: Nullable<String>,
+/// }
+///
+/// let patch: PlayerPatch = serde_json::from_str("{}").unwrap();
+/// assert!(patch.nickname.is_absent());
+/// let patch: PlayerPatch = serde_json::from_str(r#"{"nickname": null}"#).unwrap();
+/// assert!(patch.nickname.is_null());
+/// let patch: PlayerPatch = serde_json::from_str(r#"{"nickname": "Ferris"}"#).unwrap();
+/// assert_eq!(patch.nickname, Nullable::Value("Ferris".to_owned()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Nullable<T> {
+    /// The value was not provided
+    #[default]
+    Absent,
+    /// The value was explicitly set to `null`
+    Null,
+    /// The value was provided
+    Value(T),
+}
+
+impl<T> Nullable<T> {
+    /// Returns `true` if the value was not provided
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    /// Returns `true` if the value was explicitly set to `null`
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns `true` if the value was provided
+    pub fn is_value(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    /// Converts from `&Nullable<T>` to `Nullable<&T>`
+    pub fn as_ref(&self) -> Nullable<&T> {
+        match self {
+            Self::Absent => Nullable::Absent,
+            Self::Null => Nullable::Null,
+            Self::Value(value) => Nullable::Value(value),
+        }
+    }
+
+    /// Returns the value, if provided, losing the distinction between absent and `null`
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::Nullable;
+    /// assert_eq!(Nullable::Value(1).into_option(), Some(1));
+    /// assert_eq!(Nullable::<i32>::Null.into_option(), None);
+    /// assert_eq!(Nullable::<i32>::Absent.into_option(), None);
+    /// ```
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::Absent | Self::Null => None,
+        }
+    }
+
+    /// Converts to the `Option<Option<T>>` representation of a partial update, where the outer
+    /// `None` means "don't touch" and `Some(None)` means "set to null"
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::Nullable;
+    /// assert_eq!(Nullable::Value(1).into_patch(), Some(Some(1)));
+    /// assert_eq!(Nullable::<i32>::Null.into_patch(), Some(None));
+    /// assert_eq!(Nullable::<i32>::Absent.into_patch(), None);
+    /// ```
+    pub fn into_patch(self) -> Option<Option<T>> {
+        match self {
+            Self::Absent => None,
+            Self::Null => Some(None),
+            Self::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Nullable<T> {
+    /// `None` becomes [Nullable::Null]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Value(value),
+            None => Self::Null,
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for Nullable<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.choose_index(3)? {
+            0 => Self::Absent,
+            1 => Self::Null,
+            _ => Self::Value(T::arbitrary(u)?),
+        })
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<T> proptest::arbitrary::Arbitrary for Nullable<T>
+where
+    T: proptest::arbitrary::Arbitrary + Clone + 'static,
+{
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::{Just, Strategy};
+        proptest::prop_oneof![
+            Just(Self::Absent),
+            Just(Self::Null),
+            proptest::arbitrary::any::<T>().prop_map(Self::Value),
+        ]
+        .boxed()
+    }
+}
+
+/// Both [Nullable::Absent] and [Nullable::Null] serialize to `null`
+impl<T: Serialize> Serialize for Nullable<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Value(value) => serializer.serialize_some(value),
+            Self::Absent | Self::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Deserializes `null` as [Nullable::Null]; a missing value is handled by `#[serde(default)]`
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Nullable<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize() {
+        let value: Nullable<i32> = serde_json::from_value(json!(null)).unwrap();
+        assert_eq!(value, Nullable::Null);
+        let value: Nullable<i32> = serde_json::from_value(json!(3)).unwrap();
+        assert_eq!(value, Nullable::Value(3));
+        assert!(serde_json::from_value::<Nullable<i32>>(json!("3")).is_err());
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(serde_json::to_value(Nullable::Value(3)).unwrap(), json!(3));
+        assert_eq!(
+            serde_json::to_value(Nullable::<i32>::Null).unwrap(),
+            json!(null)
+        );
+        assert_eq!(
+            serde_json::to_value(Nullable::<i32>::Absent).unwrap(),
+            json!(null)
+        );
+    }
+
+    #[test]
+    fn test_accessors() {
+        let absent = Nullable::<i32>::default();
+        assert!(absent.is_absent() && !absent.is_null() && !absent.is_value());
+        let value = Nullable::Value(String::from("a"));
+        assert_eq!(value.as_ref(), Nullable::Value(&String::from("a")));
+        assert_eq!(Nullable::from(None::<i32>), Nullable::Null);
+    }
+}
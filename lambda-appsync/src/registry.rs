@@ -0,0 +1,101 @@
+//! Runtime description of the operations of a GraphQL schema
+//!
+//! The types of this module describe the operations generated by
+//! [appsync_lambda_main](crate::appsync_lambda_main). When given `emit_registry = true`, the macro
+//! generates a `pub fn operation_registry() -> &'static [OperationMeta]` function listing every
+//! operation of the schema along with its arguments.
+//!
+//! # Example
+//! ```
+//! # lambda_appsync::appsync_lambda_main!(
+//! #    "schema.graphql",
+//! #    exclude_lambda_handler = true,
+//! #    emit_registry = true,
+//! # );
+//! use lambda_appsync::registry::OperationKind;
+//!
+//! # fn main() {
+//! let player = operation_registry()
+//!     .iter()
+//!     .find(|op| op.kind == OperationKind::Query && op.name == "player")
+//!     .unwrap();
+//! assert_eq!(player.args.len(), 1);
+//! assert_eq!(player.args[0].name, "id");
+//! assert!(player.args[0].required);
+//! # }
+//! ```
+use serde::Serialize;
+
+/// The kind of a GraphQL operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum OperationKind {
+    /// A `Query` operation
+    Query,
+    /// A `Mutation` operation
+    Mutation,
+    /// A `Subscription` operation
+    Subscription,
+}
+
+impl std::fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperationKind::Query => write!(f, "Query"),
+            OperationKind::Mutation => write!(f, "Mutation"),
+            OperationKind::Subscription => write!(f, "Subscription"),
+        }
+    }
+}
+
+/// Description of a GraphQL operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OperationMeta {
+    /// The kind of the operation
+    pub kind: OperationKind,
+    /// The name of the operation, as written in the GraphQL schema
+    pub name: &'static str,
+    /// The arguments of the operation, in the order of the GraphQL schema
+    pub args: &'static [ArgMeta],
+}
+
+/// Description of an argument of a GraphQL operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ArgMeta {
+    /// The name of the argument, as written in the GraphQL schema
+    pub name: &'static str,
+    /// `true` if the argument is non-nullable in the GraphQL schema
+    pub required: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operation_kind_display() {
+        assert_eq!(OperationKind::Query.to_string(), "Query");
+        assert_eq!(OperationKind::Mutation.to_string(), "Mutation");
+        assert_eq!(OperationKind::Subscription.to_string(), "Subscription");
+    }
+
+    #[test]
+    fn test_operation_meta_serialize() {
+        let meta = OperationMeta {
+            kind: OperationKind::Query,
+            name: "player",
+            args: &[ArgMeta {
+                name: "id",
+                required: true,
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(meta).unwrap(),
+            json!({
+                "kind": "Query",
+                "name": "player",
+                "args": [{"name": "id", "required": true}]
+            })
+        );
+    }
+}
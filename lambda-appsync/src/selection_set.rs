@@ -0,0 +1,137 @@
+//! Parsing of the `selectionSetGraphQL` of an AppSync event, see
+//! [AppsyncEventInfo::selected_fields](crate::AppsyncEventInfo::selected_fields)
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use graphql_parser::query::{
+    parse_query, Definition, OperationDefinition, Selection, SelectionSet,
+};
+
+use crate::AppsyncError;
+
+type Fragments<'a> = HashMap<&'a str, &'a SelectionSet<'a, &'a str>>;
+
+/// Returns the names of the top-level fields of a selection set, following the fragments
+pub(crate) fn top_level_fields(
+    selection_set_graphql: &str,
+) -> Result<BTreeSet<String>, AppsyncError> {
+    let mut fields = BTreeSet::new();
+    // AppSync sends an empty selection set for operations returning a scalar
+    if selection_set_graphql.trim().is_empty() {
+        return Ok(fields);
+    }
+    let document = parse_query::<&str>(selection_set_graphql).map_err(|e| {
+        AppsyncError::new(
+            "InvalidSelectionSet",
+            format!("Could not parse the selection set ({e})"),
+        )
+    })?;
+
+    let mut fragments = Fragments::new();
+    let mut selection_sets = vec![];
+    for definition in &document.definitions {
+        match definition {
+            Definition::Fragment(fragment) => {
+                fragments.insert(fragment.name, &fragment.selection_set);
+            }
+            Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                selection_sets.push(selection_set)
+            }
+            Definition::Operation(OperationDefinition::Query(query)) => {
+                selection_sets.push(&query.selection_set)
+            }
+            Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+                selection_sets.push(&mutation.selection_set)
+            }
+            Definition::Operation(OperationDefinition::Subscription(subscription)) => {
+                selection_sets.push(&subscription.selection_set)
+            }
+        }
+    }
+
+    let mut visited_fragments = HashSet::new();
+    for selection_set in selection_sets {
+        collect_fields(
+            selection_set,
+            &fragments,
+            &mut visited_fragments,
+            &mut fields,
+        );
+    }
+    Ok(fields)
+}
+
+// Inline fragments and fragment spreads are flattened, each fragment is visited once so that an
+// (invalid) cycle of fragments does not loop forever
+fn collect_fields<'a>(
+    selection_set: &'a SelectionSet<'a, &'a str>,
+    fragments: &Fragments<'a>,
+    visited_fragments: &mut HashSet<&'a str>,
+    fields: &mut BTreeSet<String>,
+) {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                fields.insert(field.name.to_owned());
+            }
+            Selection::InlineFragment(fragment) => collect_fields(
+                &fragment.selection_set,
+                fragments,
+                visited_fragments,
+                fields,
+            ),
+            Selection::FragmentSpread(spread) => {
+                if !visited_fragments.insert(spread.fragment_name) {
+                    continue;
+                }
+                if let Some(selection_set) = fragments.get(spread.fragment_name) {
+                    collect_fields(selection_set, fragments, visited_fragments, fields)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(selection_set_graphql: &str) -> Vec<String> {
+        top_level_fields(selection_set_graphql)
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_top_level_fields() {
+        assert_eq!(fields("{id name team}"), ["id", "name", "team"]);
+        assert_eq!(
+            fields("{\n  id\n  owner {\n    id\n    name\n  }\n}"),
+            ["id", "owner"]
+        );
+        assert_eq!(fields("{ playerName: name }"), ["name"]);
+        assert!(fields("").is_empty());
+    }
+
+    #[test]
+    fn test_top_level_fields_fragments() {
+        assert_eq!(
+            fields(
+                "{\n  id\n  ... on Player {\n    team\n  }\n  ...PlayerName\n}\n\n\
+                 fragment PlayerName on Player {\n  name\n}"
+            ),
+            ["id", "name", "team"]
+        );
+        // Unknown and cyclic fragments are ignored
+        assert_eq!(
+            fields("{ id ...Unknown ...A }\nfragment A on Player { name ...A }"),
+            ["id", "name"]
+        );
+    }
+
+    #[test]
+    fn test_top_level_fields_invalid() {
+        let err = top_level_fields("{id").unwrap_err();
+        assert_eq!(err.error_type, "InvalidSelectionSet");
+    }
+}
@@ -63,6 +63,44 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # JSON format
+//!
+//! A [FilterGroup] serializes to the JSON object expected by `$extensions.setSubscriptionFilter`
+//! in the AppSync response mapping template. [FilterGroup::to_value] returns it directly:
+//! ```
+//! # use lambda_appsync::{subscription_filters::{FieldPath, Filter, FilterGroup}, AppsyncError};
+//! # use serde_json::json;
+//! # fn example() -> Result<(), AppsyncError> {
+//! let group = FilterGroup::from([
+//!     Filter::from([
+//!         FieldPath::new("severity")?.ge(3),
+//!         FieldPath::new("service")?.begins_with("AWS"),
+//!     ]),
+//!     Filter::from(FieldPath::new("region")?.in_values(["us-east-1", "eu-west-1"])),
+//! ]);
+//! assert_eq!(
+//!     group.to_value(),
+//!     json!({
+//!         // OR between the filters of the group
+//!         "filterGroup": [
+//!             // AND between the field filters of a filter
+//!             {"filters": [
+//!                 {"fieldName": "severity", "operator": "ge", "value": 3},
+//!                 {"fieldName": "service", "operator": "beginsWith", "value": "AWS"}
+//!             ]},
+//!             {"filters": [
+//!                 {"fieldName": "region", "operator": "in", "value": ["us-east-1", "eu-west-1"]}
+//!             ]}
+//!         ]
+//!     })
+//! );
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use std::marker::PhantomData;
 
 use serde::Serialize;
 
@@ -173,6 +211,33 @@ impl<T: IFSValueMarker, const N: usize> FixedVec<T, N> {
     }
 }
 
+impl<T, const N: usize> FixedVec<T, N> {
+    /// Collects an iterator, failing if it yields more than `N` elements
+    fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, AppsyncError> {
+        let mut slice = std::array::from_fn(|_| None);
+        let mut iter = iter.into_iter();
+        for slot in slice.iter_mut() {
+            match iter.next() {
+                Some(v) => *slot = Some(v),
+                None => break,
+            }
+        }
+        if iter.next().is_some() {
+            return Err(AppsyncError::new(
+                "ValidationError",
+                format!("Filter operator accepts at most {N} values"),
+            ));
+        }
+        Ok(Self(slice))
+    }
+}
+
+impl<T, const N: usize> FixedVec<T, N> {
+    fn map<U>(self, mut f: impl FnMut(T) -> U) -> FixedVec<U, N> {
+        FixedVec(self.0.map(|v| v.map(&mut f)))
+    }
+}
+
 /// A vector limited to 5 elements for In/NotIn operators
 type InVec<T> = FixedVec<T, 5>;
 
@@ -495,6 +560,54 @@ impl FieldPath {
         FieldFilter::new(self, serde_json::to_value(in_vec).unwrap(), FilterOp::NotIn)
     }
 
+    /// Creates an IN filter from any iterator of up to 5 values
+    ///
+    /// Unlike [FieldPath::in_values], the number of values is only known at runtime.
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the iterator yields more than 5 values
+    ///
+    /// # Examples
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, subscription_filters::FieldPath};
+    /// # fn example() -> Result<(), AppsyncError> {
+    /// let teams = vec!["red".to_owned(), "blue".to_owned()];
+    /// let filter = FieldPath::new("team")?.in_(teams)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn in_<IFS: IFSValueMarker>(
+        self,
+        values: impl IntoIterator<Item = IFS>,
+    ) -> Result<FieldFilter, AppsyncError> {
+        let in_vec = InVec::try_from_iter(values)?;
+        Ok(FieldFilter::new(self, in_vec.to_value(), FilterOp::In))
+    }
+
+    /// Creates a NOT IN filter from any iterator of up to 5 values
+    ///
+    /// Unlike [FieldPath::not_in], the number of values is only known at runtime.
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the iterator yields more than 5 values
+    ///
+    /// # Examples
+    /// ```
+    /// # use lambda_appsync::{AppsyncError, subscription_filters::FieldPath};
+    /// # fn example() -> Result<(), AppsyncError> {
+    /// let banned = [3, 7, 9].into_iter().filter(|n| n % 3 == 0);
+    /// let filter = FieldPath::new("user.level")?.not_in_(banned)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn not_in_<IFS: IFSValueMarker>(
+        self,
+        values: impl IntoIterator<Item = IFS>,
+    ) -> Result<FieldFilter, AppsyncError> {
+        let in_vec = InVec::try_from_iter(values)?;
+        Ok(FieldFilter::new(self, in_vec.to_value(), FilterOp::NotIn))
+    }
+
     /// Creates a BETWEEN filter that matches values in a range
     pub fn between<IFS: IFSValueMarker>(self, start: IFS, end: IFS) -> FieldFilter {
         FieldFilter::new(
@@ -556,6 +669,245 @@ impl FieldPath {
     }
 }
 
+/// Marker trait for the types a [TypedFieldPath] can compare for (in)equality
+///
+/// Implemented for every scalar accepted by [FieldPath::eq], and by
+/// [appsync_lambda_main](crate::appsync_lambda_main) for the generated enums.
+///
+/// Implementors must serialize to a JSON string, number or boolean, otherwise
+/// AppSync will reject the resulting filter.
+pub trait FilterScalar: Serialize {}
+
+macro_rules! impl_filter_scalar {
+    ($($t:ty),+) => {
+        $(impl FilterScalar for $t {})+
+    };
+}
+impl_filter_scalar!(
+    u8,
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64,
+    u128,
+    i128,
+    f32,
+    f64,
+    bool,
+    String,
+    ID,
+    AWSEmail,
+    AWSUrl,
+    AWSDate,
+    AWSTime,
+    AWSPhone,
+    AWSDateTime,
+    AWSTimestamp
+);
+
+// Values built from FilterScalar types (or FixedVec of them) always serialize to JSON
+fn filter_scalar_value(value: impl Serialize) -> serde_json::Value {
+    serde_json::to_value(value).expect("FilterScalar types serialize to JSON scalars")
+}
+
+/// A [FieldPath] bound to the type `T` of the field it points to
+///
+/// The operators only accept values of the field type, so that comparing a `String` field
+/// to a number is a compile-time error. The operators available depend on `T`:
+/// - Any [FilterScalar]: `eq`, `ne`, `in_values`, `not_in`, `in_` and `not_in_`
+/// - Any [IFSValueMarker] (numbers and strings): `le`, `lt`, `ge`, `gt` and `between`
+/// - `String`: `contains`, `not_contains` and `begins_with`
+/// - `Vec<E>`: `contains`, `not_contains` and `contains_any` with values of type `E`
+///
+/// With the `typed_filters = true` option, [appsync_lambda_main](crate::appsync_lambda_main)
+/// generates a `subscription_fields` module with a constructor for each field of each subscription
+/// return type.
+///
+/// # Examples
+/// ```
+/// # use lambda_appsync::{AppsyncError, subscription_filters::{Filter, TypedFieldPath}};
+/// # fn example() -> Result<Filter, AppsyncError> {
+/// let name = TypedFieldPath::<String>::new("name")?;
+/// let level = TypedFieldPath::<i32>::new("level")?;
+/// let filter = Filter::from([name.begins_with("John"), level.ge(10)]);
+/// # Ok(filter)
+/// # }
+/// ```
+///
+/// Comparing a field to a value of the wrong type does not compile:
+/// ```compile_fail
+/// # use lambda_appsync::{AppsyncError, subscription_filters::{FieldFilter, TypedFieldPath}};
+/// # fn example() -> Result<FieldFilter, AppsyncError> {
+/// let filter = TypedFieldPath::<String>::new("name")?.eq(42);
+/// # Ok(filter)
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedFieldPath<T> {
+    path: FieldPath,
+    _field_type: PhantomData<fn() -> T>,
+}
+
+impl<T> TypedFieldPath<T> {
+    /// Creates a new typed field path, with the same validation as [FieldPath::new]
+    pub fn new(path: impl Into<String>) -> Result<Self, AppsyncError> {
+        FieldPath::new(path).map(Self::from)
+    }
+
+    /// Returns the untyped [FieldPath]
+    pub fn into_field_path(self) -> FieldPath {
+        self.path
+    }
+}
+
+impl<T> From<FieldPath> for TypedFieldPath<T> {
+    fn from(path: FieldPath) -> Self {
+        Self {
+            path,
+            _field_type: PhantomData,
+        }
+    }
+}
+
+impl<T> From<TypedFieldPath<T>> for FieldPath {
+    fn from(value: TypedFieldPath<T>) -> Self {
+        value.path
+    }
+}
+
+impl<T: FilterScalar> TypedFieldPath<T> {
+    /// Creates an equality filter
+    pub fn eq(self, value: impl Into<T>) -> FieldFilter {
+        FieldFilter::new(self.path, filter_scalar_value(value.into()), FilterOp::Eq)
+    }
+
+    /// Creates a not equal filter
+    pub fn ne(self, value: impl Into<T>) -> FieldFilter {
+        FieldFilter::new(self.path, filter_scalar_value(value.into()), FilterOp::Ne)
+    }
+
+    /// Creates an IN filter accepting up to 5 values
+    pub fn in_values<V: Into<T>>(self, values: impl Into<InVec<V>>) -> FieldFilter {
+        let in_vec = values.into().map(Into::into);
+        FieldFilter::new(self.path, filter_scalar_value(in_vec), FilterOp::In)
+    }
+
+    /// Creates a NOT IN filter accepting up to 5 values
+    pub fn not_in<V: Into<T>>(self, values: impl Into<InVec<V>>) -> FieldFilter {
+        let in_vec = values.into().map(Into::into);
+        FieldFilter::new(self.path, filter_scalar_value(in_vec), FilterOp::NotIn)
+    }
+
+    /// Creates an IN filter from any iterator of up to 5 values
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the iterator yields more than 5 values
+    pub fn in_<V: Into<T>>(
+        self,
+        values: impl IntoIterator<Item = V>,
+    ) -> Result<FieldFilter, AppsyncError> {
+        let in_vec = InVec::try_from_iter(values.into_iter().map(Into::into))?;
+        Ok(FieldFilter::new(
+            self.path,
+            filter_scalar_value(in_vec),
+            FilterOp::In,
+        ))
+    }
+
+    /// Creates a NOT IN filter from any iterator of up to 5 values
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the iterator yields more than 5 values
+    pub fn not_in_<V: Into<T>>(
+        self,
+        values: impl IntoIterator<Item = V>,
+    ) -> Result<FieldFilter, AppsyncError> {
+        let in_vec = InVec::try_from_iter(values.into_iter().map(Into::into))?;
+        Ok(FieldFilter::new(
+            self.path,
+            filter_scalar_value(in_vec),
+            FilterOp::NotIn,
+        ))
+    }
+}
+
+impl<T: IFSValueMarker> TypedFieldPath<T> {
+    /// Creates a less than or equal filter
+    pub fn le(self, value: impl Into<T>) -> FieldFilter {
+        self.path.le(value.into())
+    }
+
+    /// Creates a less than filter
+    pub fn lt(self, value: impl Into<T>) -> FieldFilter {
+        self.path.lt(value.into())
+    }
+
+    /// Creates a greater than or equal filter
+    pub fn ge(self, value: impl Into<T>) -> FieldFilter {
+        self.path.ge(value.into())
+    }
+
+    /// Creates a greater than filter
+    pub fn gt(self, value: impl Into<T>) -> FieldFilter {
+        self.path.gt(value.into())
+    }
+
+    /// Creates a BETWEEN filter that matches values in a range
+    pub fn between(self, start: impl Into<T>, end: impl Into<T>) -> FieldFilter {
+        self.path.between(start.into(), end.into())
+    }
+}
+
+impl TypedFieldPath<String> {
+    /// Creates a contains filter matching strings containing `value`
+    pub fn contains(self, value: impl Into<String>) -> FieldFilter {
+        self.path.contains(value.into())
+    }
+
+    /// Creates a not contains filter matching strings not containing `value`
+    pub fn not_contains(self, value: impl Into<String>) -> FieldFilter {
+        self.path.not_contains(value.into())
+    }
+
+    /// Creates a begins with filter
+    pub fn begins_with(self, value: impl Into<String>) -> FieldFilter {
+        self.path.begins_with(value)
+    }
+}
+
+impl<E: FilterScalar> TypedFieldPath<Vec<E>> {
+    /// Creates a contains filter matching lists containing `value`
+    pub fn contains(self, value: impl Into<E>) -> FieldFilter {
+        FieldFilter::new(
+            self.path,
+            filter_scalar_value(value.into()),
+            FilterOp::Contains,
+        )
+    }
+
+    /// Creates a not contains filter matching lists not containing `value`
+    pub fn not_contains(self, value: impl Into<E>) -> FieldFilter {
+        FieldFilter::new(
+            self.path,
+            filter_scalar_value(value.into()),
+            FilterOp::NotContains,
+        )
+    }
+
+    /// Creates a contains any filter accepting up to 20 values
+    pub fn contains_any<V: Into<E>>(self, values: impl Into<ContainsAnyVec<V>>) -> FieldFilter {
+        let contains_vec = values.into().map(Into::into);
+        FieldFilter::new(
+            self.path,
+            filter_scalar_value(contains_vec),
+            FilterOp::ContainsAny,
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 enum FilterOp {
@@ -642,6 +994,39 @@ impl From<FieldFilter> for Filter {
     }
 }
 
+impl Filter {
+    /// Creates a filter from any iterator of up to 5 [FieldFilter]s combined with AND logic
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the iterator yields more than 5 field filters
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{subscription_filters::{FieldPath, Filter}, AppsyncError};
+    /// # fn example() -> Result<Filter, AppsyncError> {
+    /// let required_flags = vec!["verified", "active"];
+    /// let filter = Filter::try_from_iter(
+    ///     required_flags
+    ///         .into_iter()
+    ///         .map(|flag| Ok::<_, AppsyncError>(FieldPath::new(format!("user.{flag}"))?.eq(true)))
+    ///         .collect::<Result<Vec<_>, _>>()?,
+    /// )?;
+    /// # Ok(filter)
+    /// # }
+    /// ```
+    pub fn try_from_iter(
+        filters: impl IntoIterator<Item = FieldFilter>,
+    ) -> Result<Self, AppsyncError> {
+        let filters = FixedVec::try_from_iter(filters).map_err(|_| {
+            AppsyncError::new(
+                "ValidationError",
+                "A filter accepts at most 5 field filters",
+            )
+        })?;
+        Ok(Self { filters })
+    }
+}
+
 /// A filter group limited to 10 filters combined with OR logic
 ///
 /// Can be created from an arrays of up to 10 [Filter] elements.
@@ -693,6 +1078,156 @@ impl From<Filter> for FilterGroup {
     }
 }
 
+impl FilterGroup {
+    /// Creates a filter group from any iterator of up to 10 filters combined with OR logic
+    ///
+    /// The items can be [Filter]s or single [FieldFilter]s, which allows OR-ing a number of conditions
+    /// only known at runtime.
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the iterator yields more than 10 filters
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{subscription_filters::{FieldPath, FilterGroup}, AppsyncError};
+    /// # fn example() -> Result<FilterGroup, AppsyncError> {
+    /// // e.g. fetched at runtime
+    /// let allowed_teams = vec!["red".to_owned(), "blue".to_owned()];
+    /// let team = FieldPath::new("team")?;
+    /// let group = FilterGroup::try_from_iter(
+    ///     allowed_teams.into_iter().map(|t| team.clone().eq(t)),
+    /// )?;
+    /// # Ok(group)
+    /// # }
+    /// ```
+    pub fn try_from_iter(
+        filters: impl IntoIterator<Item = impl Into<Filter>>,
+    ) -> Result<Self, AppsyncError> {
+        let filters =
+            FixedVec::try_from_iter(filters.into_iter().map(Into::into)).map_err(|_| {
+                AppsyncError::new(
+                    "ValidationError",
+                    "A filter group accepts at most 10 filters",
+                )
+            })?;
+        Ok(Self { filters })
+    }
+
+    /// Serializes the filter group to the JSON value expected by `$extensions.setSubscriptionFilter`
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("filter groups always serialize to JSON")
+    }
+}
+
+/// Several [FilterGroup]s combined with OR logic
+///
+/// AppSync expects a single `filterGroup` of up to 10 filters. [SubscriptionFilters] allows to build
+/// the filters of a subscription from independent groups (e.g. one per authorization rule): the filters
+/// of all the groups are merged in a single `filterGroup` when serialized, which preserves the OR logic.
+///
+/// A subscription operation can return either `Option<FilterGroup>` or `Option<SubscriptionFilters>`.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::{subscription_filters::{FieldPath, Filter, FilterGroup, SubscriptionFilters}, AppsyncError};
+/// # fn example() -> Result<SubscriptionFilters, AppsyncError> {
+/// let owner_rule = FilterGroup::from(FieldPath::new("owner")?.eq("user-1"));
+/// let team_rules = FilterGroup::from([
+///     Filter::from(FieldPath::new("team")?.eq("red")),
+///     Filter::from(FieldPath::new("team")?.eq("blue")),
+/// ]);
+/// let filters = SubscriptionFilters::new([owner_rule, team_rules])?;
+/// # Ok(filters)
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubscriptionFilters {
+    groups: Vec<FilterGroup>,
+}
+
+impl SubscriptionFilters {
+    /// Combines filter groups with OR logic
+    ///
+    /// # Errors
+    /// Returns a `ValidationError` if the groups contain more than 10 filters in total, the limit
+    /// of an AppSync `filterGroup`
+    pub fn new(groups: impl IntoIterator<Item = FilterGroup>) -> Result<Self, AppsyncError> {
+        let groups = groups.into_iter().collect::<Vec<_>>();
+        let filter_count = groups
+            .iter()
+            .map(|group| group.filters.0.iter().flatten().count())
+            .sum::<usize>();
+        if filter_count > 10 {
+            return Err(AppsyncError::new(
+                "ValidationError",
+                format!("Subscription filters accept at most 10 filters, got {filter_count}"),
+            ));
+        }
+        Ok(Self { groups })
+    }
+
+    /// Serializes the filters to the JSON value expected by `$extensions.setSubscriptionFilter`
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("subscription filters always serialize to JSON")
+    }
+}
+
+impl Serialize for SubscriptionFilters {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        struct MergedFilters<'a>(&'a [FilterGroup]);
+        impl Serialize for MergedFilters<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_seq(
+                    self.0
+                        .iter()
+                        .flat_map(|group| group.filters.0.iter().flatten()),
+                )
+            }
+        }
+        let mut state = serializer.serialize_struct("SubscriptionFilters", 1)?;
+        state.serialize_field("filterGroup", &MergedFilters(&self.groups))?;
+        state.end()
+    }
+}
+
+impl From<FilterGroup> for SubscriptionFilters {
+    fn from(value: FilterGroup) -> Self {
+        Self {
+            groups: vec![value],
+        }
+    }
+}
+impl From<Filter> for SubscriptionFilters {
+    fn from(value: Filter) -> Self {
+        FilterGroup::from(value).into()
+    }
+}
+impl From<FieldFilter> for SubscriptionFilters {
+    fn from(value: FieldFilter) -> Self {
+        FilterGroup::from(value).into()
+    }
+}
+
+/// Types that a subscription operation can return
+///
+/// Implemented for `Option<FilterGroup>` and `Option<SubscriptionFilters>`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be returned by a subscription operation",
+    label = "subscription operations must return `Option<FilterGroup>` or `Option<SubscriptionFilters>`"
+)]
+pub trait SubscriptionFilterResult: private::Sealed + Serialize {}
+impl private::Sealed for Option<FilterGroup> {}
+impl SubscriptionFilterResult for Option<FilterGroup> {}
+impl private::Sealed for Option<SubscriptionFilters> {}
+impl SubscriptionFilterResult for Option<SubscriptionFilters> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -914,6 +1449,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_in_operators_from_iter() {
+        let path = FieldPath::new("team").unwrap();
+
+        let teams = vec!["red".to_owned(), "blue".to_owned(), "green".to_owned()];
+        let filter = path.clone().in_(teams).unwrap();
+        assert_eq!(
+            filter_value(&filter),
+            json!({
+                "fieldName": "team",
+                "operator": "in",
+                "value": ["red", "blue", "green"]
+            })
+        );
+
+        let filter = path.clone().not_in_(Vec::<i32>::new()).unwrap();
+        assert_eq!(
+            filter_value(&filter),
+            json!({
+                "fieldName": "team",
+                "operator": "notIn",
+                "value": []
+            })
+        );
+
+        let filter = path.clone().in_(Vec::<String>::new()).unwrap();
+        assert_eq!(filter_value(&filter)["value"], json!([]));
+
+        let filter = path.clone().not_in_(1..=5).unwrap();
+        assert_eq!(filter_value(&filter)["value"], json!([1, 2, 3, 4, 5]));
+
+        let err = path.clone().in_(1..=6).unwrap_err();
+        assert_eq!(err.error_type, "ValidationError");
+        assert!(path.not_in_(1..=6).is_err());
+    }
+
     #[test]
     fn test_between() {
         let filter = FieldPath::new("severity").unwrap().between(1, 5);
@@ -1023,4 +1594,259 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_typed_field_path() {
+        let name = TypedFieldPath::<String>::new("name").unwrap();
+        assert_eq!(
+            filter_value(&name.clone().eq("Alice")),
+            json!({
+                "fieldName": "name",
+                "operator": "eq",
+                "value": "Alice"
+            })
+        );
+        assert_eq!(
+            filter_value(&name.clone().in_values(["a", "b"]))["value"],
+            json!(["a", "b"])
+        );
+        assert_eq!(
+            filter_value(&name.clone().begins_with("Al"))["operator"],
+            json!("beginsWith")
+        );
+        assert!(name.not_in_(["a", "b", "c", "d", "e", "f"]).is_err());
+
+        let level = TypedFieldPath::<i32>::new("level").unwrap();
+        assert_eq!(
+            filter_value(&level.clone().between(1, 10u8)),
+            json!({
+                "fieldName": "level",
+                "operator": "between",
+                "value": [1, 10]
+            })
+        );
+        assert_eq!(
+            filter_value(&level.ne(3)),
+            json!({
+                "fieldName": "level",
+                "operator": "ne",
+                "value": 3
+            })
+        );
+
+        let active = TypedFieldPath::<bool>::new("active").unwrap();
+        assert_eq!(filter_value(&active.eq(true))["value"], json!(true));
+
+        let tags = TypedFieldPath::<Vec<String>>::new("tags").unwrap();
+        assert_eq!(
+            filter_value(&tags.clone().contains("rust")),
+            json!({
+                "fieldName": "tags",
+                "operator": "contains",
+                "value": "rust"
+            })
+        );
+        assert_eq!(
+            filter_value(&tags.contains_any(["a", "b"]))["value"],
+            json!(["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_subscription_filters() {
+        let groups = [
+            FilterGroup::from(FieldPath::new("owner").unwrap().eq("user-1")),
+            FilterGroup::from([
+                Filter::from(FieldPath::new("team").unwrap().eq("red")),
+                Filter::from(FieldPath::new("team").unwrap().eq("blue")),
+            ]),
+        ];
+        let filters = SubscriptionFilters::new(groups).unwrap();
+        assert_eq!(
+            serde_json::to_value(&filters).unwrap(),
+            json!({
+                "filterGroup": [
+                    {"filters": [{"fieldName": "owner", "operator": "eq", "value": "user-1"}]},
+                    {"filters": [{"fieldName": "team", "operator": "eq", "value": "red"}]},
+                    {"filters": [{"fieldName": "team", "operator": "eq", "value": "blue"}]}
+                ]
+            })
+        );
+
+        // A single group serializes exactly like the group itself
+        let group = FilterGroup::from(FieldPath::new("owner").unwrap().eq("user-1"));
+        assert_eq!(
+            serde_json::to_value(SubscriptionFilters::from(group.clone())).unwrap(),
+            serde_json::to_value(group).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_subscription_filters_limit() {
+        let group = || {
+            FilterGroup::from([
+                Filter::from(FieldPath::new("a").unwrap().eq(1)),
+                Filter::from(FieldPath::new("b").unwrap().eq(2)),
+                Filter::from(FieldPath::new("c").unwrap().eq(3)),
+                Filter::from(FieldPath::new("d").unwrap().eq(4)),
+            ])
+        };
+        assert!(SubscriptionFilters::new([group(), group()]).is_ok());
+        let err = SubscriptionFilters::new([group(), group(), group()]).unwrap_err();
+        assert_eq!(err.error_type, "ValidationError");
+    }
+
+    #[test]
+    fn test_filter_group_to_value_operators() {
+        let path = || FieldPath::new("a.b").unwrap();
+        let cases = [
+            (path().eq("x"), "eq", json!("x")),
+            (path().eq(true), "eq", json!(true)),
+            (path().ne(1.5), "ne", json!(1.5)),
+            (path().le(1), "le", json!(1)),
+            (path().lt(2), "lt", json!(2)),
+            (path().ge(3), "ge", json!(3)),
+            (path().gt(4), "gt", json!(4)),
+            (path().contains("x"), "contains", json!("x")),
+            (path().not_contains(5), "notContains", json!(5)),
+            (path().begins_with("x"), "beginsWith", json!("x")),
+            (path().in_values(["x", "y"]), "in", json!(["x", "y"])),
+            (path().not_in([1]), "notIn", json!([1])),
+            (path().between(1, 9), "between", json!([1, 9])),
+            (path().contains_any(["x"]), "containsAny", json!(["x"])),
+        ];
+        for (field_filter, operator, value) in cases {
+            assert_eq!(
+                FilterGroup::from(field_filter).to_value(),
+                json!({
+                    "filterGroup": [
+                        {
+                            "filters": [
+                                {
+                                    "fieldName": "a.b",
+                                    "operator": operator,
+                                    "value": value
+                                }
+                            ]
+                        }
+                    ]
+                }),
+                "operator `{operator}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_group_to_value_and_or() {
+        // (a = 1 AND b = 2 AND c = 3) OR (d = 4) OR (e = 5 AND f = 6)
+        let group = FilterGroup::from([
+            Filter::from([
+                FieldPath::new("a").unwrap().eq(1),
+                FieldPath::new("b").unwrap().eq(2),
+                FieldPath::new("c").unwrap().eq(3),
+            ]),
+            Filter::from(FieldPath::new("d").unwrap().eq(4)),
+            Filter::from([
+                FieldPath::new("e").unwrap().eq(5),
+                FieldPath::new("f").unwrap().eq(6),
+            ]),
+        ]);
+        let eq =
+            |field: &str, value: i32| json!({"fieldName": field, "operator": "eq", "value": value});
+        assert_eq!(
+            group.to_value(),
+            json!({
+                "filterGroup": [
+                    {"filters": [eq("a", 1), eq("b", 2), eq("c", 3)]},
+                    {"filters": [eq("d", 4)]},
+                    {"filters": [eq("e", 5), eq("f", 6)]}
+                ]
+            })
+        );
+        assert_eq!(group.to_value(), serde_json::to_value(&group).unwrap());
+    }
+
+    #[test]
+    fn test_filter_try_from_iter() {
+        let filter =
+            Filter::try_from_iter(["a", "b"].map(|name| FieldPath::new(name).unwrap().eq(true)))
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            json!({"filters": [
+                {"fieldName": "a", "operator": "eq", "value": true},
+                {"fieldName": "b", "operator": "eq", "value": true}
+            ]})
+        );
+
+        let err =
+            Filter::try_from_iter((0..6).map(|i| FieldPath::new("a").unwrap().eq(i))).unwrap_err();
+        assert_eq!(err.error_type, "ValidationError");
+        assert_eq!(
+            err.error_message,
+            "A filter accepts at most 5 field filters"
+        );
+    }
+
+    #[test]
+    fn test_filter_group_try_from_iter() {
+        let teams = vec!["red".to_owned(), "blue".to_owned(), "green".to_owned()];
+        let group = FilterGroup::try_from_iter(
+            teams
+                .into_iter()
+                .map(|team| FieldPath::new("team").unwrap().eq(team)),
+        )
+        .unwrap();
+        assert_eq!(
+            group.to_value(),
+            json!({"filterGroup": [
+                {"filters": [{"fieldName": "team", "operator": "eq", "value": "red"}]},
+                {"filters": [{"fieldName": "team", "operator": "eq", "value": "blue"}]},
+                {"filters": [{"fieldName": "team", "operator": "eq", "value": "green"}]}
+            ]})
+        );
+
+        let group = FilterGroup::try_from_iter(Vec::<Filter>::new()).unwrap();
+        assert_eq!(group.to_value(), json!({"filterGroup": []}));
+
+        let err = FilterGroup::try_from_iter((0..11).map(|i| FieldPath::new("a").unwrap().eq(i)))
+            .unwrap_err();
+        assert_eq!(err.error_type, "ValidationError");
+        assert_eq!(
+            err.error_message,
+            "A filter group accepts at most 10 filters"
+        );
+    }
+
+    #[test]
+    fn test_filter_group_to_value_limits() {
+        // 10 filters of 5 field filters each is the largest accepted group
+        let filter = || {
+            Filter::from([
+                FieldPath::new("a").unwrap().eq(1),
+                FieldPath::new("b").unwrap().eq(2),
+                FieldPath::new("c").unwrap().eq(3),
+                FieldPath::new("d").unwrap().eq(4),
+                FieldPath::new("e").unwrap().eq(5),
+            ])
+        };
+        let group = FilterGroup::from([
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+            filter(),
+        ]);
+        let value = group.to_value();
+        let filters = value["filterGroup"].as_array().unwrap();
+        assert_eq!(filters.len(), 10);
+        assert!(filters
+            .iter()
+            .all(|f| f["filters"].as_array().unwrap().len() == 5));
+    }
 }
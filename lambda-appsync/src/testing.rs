@@ -0,0 +1,102 @@
+use serde_json::Value;
+
+use crate::{AppsyncAuthStrategy, AppsyncIdentityCognito};
+
+impl AppsyncIdentityCognito {
+    /// Creates a Cognito identity for tests, with sensible defaults for the fields AppSync
+    /// always provides: an `issuer` pointing to a fake user pool, empty `claims`, a single
+    /// `sourceIp` and the [AppsyncAuthStrategy::Allow] default strategy.
+    ///
+    /// The returned identity can be serialized into the `identity` field of a test event.
+    /// The fields are public, adjust them as needed.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncIdentityCognito;
+    /// let identity = AppsyncIdentityCognito::test("user123", "testuser", ["admin"]);
+    /// let event = serde_json::json!({
+    ///     "identity": identity,
+    ///     // ...
+    /// });
+    /// assert_eq!(event["identity"]["groups"][0], "admin");
+    /// assert_eq!(event["identity"]["defaultAuthStrategy"], "ALLOW");
+    /// ```
+    pub fn test(
+        sub: impl Into<String>,
+        username: impl Into<String>,
+        groups: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            sub: sub.into(),
+            username: username.into(),
+            issuer: "https://cognito-idp.us-east-1.amazonaws.com/us-east-1_test".to_owned(),
+            default_auth_strategy: AppsyncAuthStrategy::Allow,
+            source_ip: vec!["127.0.0.1".to_owned()],
+            groups: Some(groups.into_iter().map(Into::into).collect()),
+            claims: Value::Object(Default::default()),
+        }
+    }
+}
+
+/// Asserts that a client getter returns a cached client rather than building a new one on each call.
+///
+/// Two calls to the getter must return clones of the same client, sharing the same configuration.
+/// A getter that creates its client on each call loads a new configuration and opens new connections
+/// on every invocation; this macro makes such a getter fail a test instead of slowing down production.
+///
+/// Works with any AWS SDK client, as they all expose their configuration through `config()`.
+///
+/// # Panics
+/// Panics when the two calls return clients that do not share their configuration.
+///
+/// # Example
+/// ```
+/// use lambda_appsync::assert_client_cached;
+/// use std::sync::OnceLock;
+///
+/// fn dynamodb() -> aws_sdk_dynamodb::Client {
+///     static CLIENT: OnceLock<aws_sdk_dynamodb::Client> = OnceLock::new();
+///     CLIENT
+///         .get_or_init(|| {
+///             let config = aws_sdk_dynamodb::Config::builder()
+///                 .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
+///                 .build();
+///             aws_sdk_dynamodb::Client::from_conf(config)
+///         })
+///         .clone()
+/// }
+///
+/// assert_client_cached!(dynamodb);
+/// ```
+#[macro_export]
+macro_rules! assert_client_cached {
+    ($getter:path) => {{
+        let (first, second) = ($getter(), $getter());
+        ::core::assert!(
+            ::core::ptr::eq(first.config(), second.config()),
+            "`{}` builds a new client on each call, cache it in a `static OnceLock`",
+            ::core::stringify!($getter)
+        );
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppsyncIdentity;
+
+    #[test]
+    fn test_cognito_identity_roundtrip() {
+        let identity = AppsyncIdentityCognito::test("user123", "testuser", ["admin"]);
+        let value = serde_json::to_value(&identity).unwrap();
+        match serde_json::from_value::<AppsyncIdentity>(value).unwrap() {
+            AppsyncIdentity::Cognito(cognito) => {
+                assert_eq!(cognito.sub, "user123");
+                assert_eq!(cognito.username, "testuser");
+                assert_eq!(cognito.groups, Some(vec!["admin".to_owned()]));
+                assert_eq!(cognito.default_auth_strategy, AppsyncAuthStrategy::Allow);
+            }
+            other => panic!("Expected a Cognito identity, got {other:?}"),
+        }
+    }
+}
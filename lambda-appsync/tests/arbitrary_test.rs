@@ -0,0 +1,73 @@
+#![cfg(feature = "arbitrary")]
+
+use lambda_appsync::arbitrary::{Arbitrary, Unstructured};
+
+lambda_appsync::appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/arbitrary.graphql",
+    only_appsync_types = true,
+    arbitrary = true,
+);
+
+// Deterministic pseudo-random bytes
+fn bytes(seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..4096)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_arbitrary_enum() {
+    let mut seen = vec![];
+    for seed in 0..64 {
+        let data = bytes(seed);
+        let team = Team::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        if !seen.contains(&team) {
+            seen.push(team);
+        }
+    }
+    assert_eq!(seen.len(), Team::COUNT);
+}
+
+#[test]
+fn test_arbitrary_type_round_trip() {
+    for seed in 0..64 {
+        let data = bytes(seed);
+        let player = Player::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(lambda_appsync::AWSDateTime::is_valid(&player.created_at));
+        if let Some(birth_date) = &player.birth_date {
+            assert!(lambda_appsync::AWSDate::is_valid(birth_date));
+        }
+        if let Some(wake_up_time) = &player.wake_up_time {
+            assert!(lambda_appsync::AWSTime::is_valid(wake_up_time));
+        }
+
+        let json = serde_json::to_value(&player).unwrap();
+        let back: Player = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    }
+}
+
+#[test]
+fn test_arbitrary_input_round_trip() {
+    for seed in 0..64 {
+        let data = bytes(seed);
+        let input = PlayerInput::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let json = serde_json::to_value(&input).unwrap();
+        let back: PlayerInput = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    }
+}
+
+#[test]
+fn test_arbitrary_from_empty_data() {
+    let data = [];
+    let input = PlayerInput::arbitrary(&mut Unstructured::new(&data)).unwrap();
+    assert!(input.tags.is_empty());
+    assert!(input.settings.is_null());
+}
@@ -0,0 +1,45 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", args_may_be_string = true);
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team: Team::Rust,
+    })
+}
+
+fn create_player_event(arguments: Value) -> Value {
+    json!({
+        "info": {
+            "fieldName": "createPlayer",
+            "parentTypeName": "Mutation",
+            "variables": {},
+            "selectionSetList": ["name"],
+            "selectionSetGraphQL": "{name}"
+        },
+        "arguments": arguments,
+        "identity": null,
+        "request": null,
+        "source": null
+    })
+}
+
+#[tokio::test]
+async fn test_args_may_be_string() {
+    let event = json!([
+        // Double-encoded by a VTL template
+        create_player_event(json!(r#"{"name": "Ferris"}"#)),
+        create_player_event(json!({"name": "Corro"})),
+        create_player_event(json!("not JSON")),
+    ]);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap();
+
+    assert_eq!(response[0]["data"]["name"], "Ferris");
+    assert_eq!(response[1]["data"]["name"], "Corro");
+    assert_eq!(response[2]["errorType"], "InvalidArgs");
+}
@@ -0,0 +1,44 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", args_source = prev_result);
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team: Team::Rust,
+    })
+}
+
+fn create_player_event(arguments: Value, prev: Value) -> Value {
+    json!({
+        "info": {
+            "fieldName": "createPlayer",
+            "parentTypeName": "Mutation",
+            "variables": {},
+            "selectionSetList": ["name"],
+            "selectionSetGraphQL": "{name}"
+        },
+        "arguments": arguments,
+        "prev": prev,
+        "identity": null,
+        "request": null,
+        "source": null
+    })
+}
+
+#[tokio::test]
+async fn test_args_source_prev_result() {
+    let event = json!([
+        create_player_event(json!({}), json!({"result": {"name": "Ferris"}})),
+        // The standard arguments are ignored
+        create_player_event(json!({"name": "Corro"}), Value::Null),
+    ]);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap();
+
+    assert_eq!(response[0]["data"]["name"], "Ferris");
+    assert_eq!(response[1]["errorType"], "InvalidArgs");
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false);
+
+use operation_args::{mutations::CreatePlayerArgs, queries::PlayerArgs};
+
+fn non_empty(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        Err("The name cannot be empty")
+    } else {
+        Ok(())
+    }
+}
+
+#[appsync_operation(
+    mutation(createPlayer),
+    args_struct,
+    validate(name = non_empty),
+    retry = { max: 2, base_ms: 1 },
+    cache_key(name)
+)]
+async fn create_player(args: CreatePlayerArgs) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name: args.name,
+        team: Team::Rust,
+    })
+}
+
+#[appsync_operation(query(player), args_struct, with_appsync_event)]
+async fn get_player(
+    args: PlayerArgs,
+    event: &AppsyncEvent<Operation>,
+) -> Result<Option<Player>, AppsyncError> {
+    Ok(Some(Player {
+        id: args.id,
+        name: format!("{:?}", event.info.operation),
+        team: Team::Python,
+    }))
+}
+
+async fn call(parent_type_name: &str, field_name: &str, arguments: Value) -> Value {
+    let event = common::event(parent_type_name, field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_args_struct() {
+    let response = call("Mutation", "createPlayer", json!({"name": "Ferris"})).await;
+    assert_eq!(response["data"]["name"], "Ferris");
+}
+
+#[tokio::test]
+async fn test_args_struct_with_event() {
+    let id = ID::new();
+    let response = call("Query", "player", json!({"id": id})).await;
+    assert_eq!(response["data"]["id"], json!(id));
+    assert_eq!(response["data"]["name"], "Query(Player)");
+}
+
+#[tokio::test]
+async fn test_args_struct_invalid_args() {
+    let response = call("Mutation", "createPlayer", json!({})).await;
+    assert_eq!(response["errorType"], "InvalidArgs");
+}
+
+#[tokio::test]
+async fn test_args_struct_validate() {
+    let response = call("Mutation", "createPlayer", json!({"name": ""})).await;
+    assert_eq!(response["errorType"], "BadRequest");
+}
+
+#[test]
+fn test_args_struct_cache_key() {
+    let args = CreatePlayerArgs {
+        name: "Ferris".to_owned(),
+    };
+    assert_eq!(
+        Operation::mutation_create_player_cache_key(&args.clone()).unwrap(),
+        "Mutation.createPlayer:Ferris"
+    );
+}
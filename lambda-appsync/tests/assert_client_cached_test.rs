@@ -0,0 +1,28 @@
+use std::sync::OnceLock;
+
+use aws_sdk_dynamodb::config::{BehaviorVersion, Region};
+use lambda_appsync::assert_client_cached;
+
+fn new_client() -> aws_sdk_dynamodb::Client {
+    let config = aws_sdk_dynamodb::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new("eu-west-1"))
+        .build();
+    aws_sdk_dynamodb::Client::from_conf(config)
+}
+
+fn cached_client() -> aws_sdk_dynamodb::Client {
+    static CLIENT: OnceLock<aws_sdk_dynamodb::Client> = OnceLock::new();
+    CLIENT.get_or_init(new_client).clone()
+}
+
+#[test]
+fn test_cached_client_passes() {
+    assert_client_cached!(cached_client);
+}
+
+#[test]
+#[should_panic(expected = "`new_client` builds a new client on each call")]
+fn test_fresh_client_panics() {
+    assert_client_cached!(new_client);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+use serde_json::{json, Value};
+
+// The `testing` feature makes the handlers public
+appsync_lambda_main!("schema.graphql");
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    // The first events finish last
+    if id.to_string().ends_with('0') {
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    Ok(Some(Player {
+        id,
+        name: "Ferris".to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+fn event(field_name: &str, arguments: Value) -> AppsyncEvent<Operation> {
+    serde_json::from_value(common::event("Query", field_name, arguments)).unwrap()
+}
+
+#[tokio::test]
+async fn test_batch_handler_keeps_order() {
+    let ids = [
+        "00000000-0000-0000-0000-000000000000",
+        "00000000-0000-0000-0000-000000000001",
+        "00000000-0000-0000-0000-000000000002",
+    ];
+    let events = ids
+        .iter()
+        .map(|id| event("player", json!({"id": id})))
+        .collect();
+
+    let responses = appsync_batch_handler(events).await;
+
+    let response_ids = responses
+        .iter()
+        .map(|response| response.data().unwrap()["id"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(response_ids, ids);
+}
+
+#[tokio::test]
+async fn test_single_handler() {
+    let response = appsync_handler(event("player", json!({}))).await;
+    assert_eq!(response.error_type(), Some("InvalidArgs"));
+}
@@ -0,0 +1,51 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+appsync_lambda_main!("schema.graphql");
+
+#[appsync_operation(query(player), cache_key(id))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(Some(Player {
+        id,
+        name: "Ferris".to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+#[appsync_operation(mutation(createPlayer), cache_key(name))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team: Team::Rust,
+    })
+}
+
+#[test]
+fn test_cache_key_from_argument() {
+    let id = ID::new();
+    assert_eq!(
+        Operation::query_player_cache_key(&id).unwrap(),
+        format!("Query.player:{id}")
+    );
+}
+
+#[test]
+fn test_cache_key_is_stable() {
+    let id = ID::new();
+    assert_eq!(
+        Operation::query_player_cache_key(&id).unwrap(),
+        Operation::query_player_cache_key(&id.clone()).unwrap()
+    );
+    assert_ne!(
+        Operation::query_player_cache_key(&id).unwrap(),
+        Operation::query_player_cache_key(&ID::new()).unwrap()
+    );
+}
+
+#[test]
+fn test_cache_key_escapes_separator() {
+    assert_eq!(
+        Operation::mutation_create_player_cache_key(&"a:b%c".to_owned()).unwrap(),
+        "Mutation.createPlayer:a%3Ab%25c"
+    );
+}
@@ -0,0 +1,29 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+
+/// Payload of the AppSync event of the `parent_type_name.field_name` operation, with an empty
+/// selection set and no identity, request or source
+pub fn event(parent_type_name: &str, field_name: &str, arguments: Value) -> Value {
+    json!({
+        "info": {
+            "fieldName": field_name,
+            "parentTypeName": parent_type_name,
+            "variables": {},
+            "selectionSetList": [],
+            "selectionSetGraphQL": ""
+        },
+        "arguments": arguments,
+        "identity": null,
+        "request": null,
+        "source": null
+    })
+}
+
+/// Sets the selection set of an [event] to the given top-level fields
+pub fn with_selection_set(mut event: Value, fields: &[&str]) -> Value {
+    event["info"]["selectionSetList"] = json!(fields);
+    event["info"]["selectionSetGraphQL"] = json!(format!("{{{}}}", fields.join(" ")));
+    event
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent};
+use lambda_runtime::Context;
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false);
+
+// Stops early when the invocation is about to time out
+#[appsync_operation(query(players), with_appsync_event)]
+async fn get_players(event: &AppsyncEvent<Operation>) -> Result<Vec<Player>, AppsyncError> {
+    let remaining = event
+        .remaining_time()
+        .expect("the handler sets the context");
+    if remaining < Duration::from_secs(1) {
+        return Err(AppsyncError::new("Timeout", "Not enough time left"));
+    }
+    assert_eq!(event.context.as_ref().unwrap().request_id, "request-id");
+    Ok(vec![])
+}
+
+async fn query(field_name: &str, context: Context) -> Value {
+    let event = common::event("Query", field_name, json!({}));
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, context);
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_remaining_time() {
+    let mut context = Context::default();
+    context.request_id = "request-id".to_owned();
+
+    // The default deadline is long gone
+    let response = query("players", context.clone()).await;
+    assert_eq!(response["errorType"], "Timeout");
+
+    let in_a_minute = SystemTime::now() + Duration::from_secs(60);
+    context.deadline = in_a_minute.duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+    let response = query("players", context).await;
+    assert_eq!(response["data"], json!([]));
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, correlation::correlation_id, AppsyncError, ID,
+};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", correlation_header = "x-correlation-id");
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    // Yield, so that the events of the batch are interleaved
+    tokio::task::yield_now().await;
+    Ok(Player {
+        id: ID::new(),
+        name: correlation_id().unwrap_or(name),
+        team: Team::Rust,
+    })
+}
+
+fn create_player_event(name: &str, headers: Value) -> Value {
+    let mut event = common::with_selection_set(
+        common::event("Mutation", "createPlayer", json!({"name": name})),
+        &["name"],
+    );
+    event["request"] = json!({"headers": headers, "domainName": null});
+    event
+}
+
+#[tokio::test]
+async fn test_correlation_id_of_each_event() {
+    let events = json!([
+        create_player_event("first", json!({"x-correlation-id": "corr-1"})),
+        create_player_event("second", json!({"X-Correlation-Id": "corr-2"})),
+        create_player_event("third", json!({"host": "api.example.com"})),
+    ]);
+    let lambda_event = lambda_runtime::LambdaEvent::new(events, Default::default());
+    let response = serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap();
+    assert_eq!(response[0]["data"]["name"], "corr-1");
+    assert_eq!(response[1]["data"]["name"], "corr-2");
+    assert_eq!(response[2]["data"]["name"], "third");
+
+    // Outside of the handling of a request
+    assert_eq!(correlation_id(), None);
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent};
+use serde_json::json;
+
+// The `testing` feature makes the handlers public
+appsync_lambda_main!("schema.graphql", emf_metrics = true);
+
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+fn event(field_name: &str) -> AppsyncEvent<Operation> {
+    serde_json::from_value(common::event("Query", field_name, json!({}))).unwrap()
+}
+
+#[tokio::test]
+async fn test_emf_metrics_keep_response() {
+    // The EMF records go to the standard output, the responses are unchanged
+    let response = appsync_handler(event("gameStatus")).await;
+    assert_eq!(response.data(), Some(&json!("STARTED")));
+
+    let response = appsync_handler(event("players")).await;
+    assert_eq!(response.error_type(), Some("Unimplemented"));
+}
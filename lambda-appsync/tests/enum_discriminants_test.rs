@@ -0,0 +1,55 @@
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    only_appsync_types = true,
+    enum_discriminants = true,
+);
+
+#[test]
+fn test_discriminants_follow_declaration_order() {
+    assert_eq!(Team::Rust as i32, 0);
+    assert_eq!(Team::Python as i32, 1);
+    assert_eq!(Team::Js as i32, 2);
+    assert_eq!(Team::MultiWordsTeam as i32, 3);
+    assert_eq!(i32::from(GameStatus::Stopped), 1);
+}
+
+#[test]
+fn test_int_round_trip() {
+    for team in Team::all() {
+        assert_eq!(Team::try_from(i32::from(team)).unwrap(), team);
+    }
+    for status in GameStatus::all() {
+        assert_eq!(GameStatus::try_from(status as i32).unwrap(), status);
+    }
+}
+
+#[test]
+fn test_invalid_discriminant() {
+    let err = Team::try_from(4).unwrap_err();
+    assert_eq!(err.error_type, "InvalidDiscriminant");
+    assert_eq!(
+        err.error_message,
+        "`4` is an invalid discriminant for enum Team"
+    );
+    assert!(Team::try_from(-1).is_err());
+}
+
+#[test]
+fn test_ordinal_round_trip() {
+    for (i, team) in Team::all().into_iter().enumerate() {
+        assert_eq!(team.to_ordinal() as usize, i);
+        assert_eq!(Team::from_ordinal(team.to_ordinal()), Some(team));
+    }
+    assert_eq!(GameStatus::Stopped.to_ordinal(), 1);
+    assert_eq!(Team::from_ordinal(4), None);
+    assert_eq!(Team::from_ordinal(u32::MAX), None);
+}
+
+#[test]
+fn test_serde_stays_string_based() {
+    assert_eq!(serde_json::to_value(Team::Python).unwrap(), "PYTHON");
+    assert_eq!(
+        serde_json::from_value::<Team>(serde_json::json!("JS")).unwrap(),
+        Team::Js
+    );
+}
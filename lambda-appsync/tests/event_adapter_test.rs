@@ -0,0 +1,48 @@
+mod common;
+
+use lambda_appsync::{appsync_operation, AppsyncError};
+use serde_json::{json, Value};
+
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    event_adapter = unwrap_envelope,
+    capture_raw_event = true
+);
+
+// A proxy wrapping the AppSync events in an envelope of its own
+fn unwrap_envelope(mut payload: Value) -> Result<Value, lambda_runtime::Error> {
+    match payload.get_mut("events") {
+        Some(events) => Ok(events.take()),
+        None => Err("Missing `events` in the envelope".into()),
+    }
+}
+
+#[appsync_operation(query(gameStatus), with_appsync_event)]
+async fn get_game_status(
+    event: &lambda_appsync::AppsyncEvent<Operation>,
+) -> Result<GameStatus, AppsyncError> {
+    // The raw event is the adapted one
+    assert!(event.raw.as_ref().unwrap().get("events").is_none());
+    Ok(GameStatus::Started)
+}
+
+fn event() -> Value {
+    common::event("Query", "gameStatus", json!({}))
+}
+
+#[tokio::test]
+async fn test_event_adapter() {
+    let payload = json!({"proxy": "v1", "events": [event(), event()]});
+    let lambda_event = lambda_runtime::LambdaEvent::new(payload, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    let response = serde_json::to_value(response).unwrap();
+    assert_eq!(response, json!([{"data": "STARTED"}, {"data": "STARTED"}]));
+}
+
+#[tokio::test]
+async fn test_event_adapter_error() {
+    let payload = json!([event()]);
+    let lambda_event = lambda_runtime::LambdaEvent::new(payload, Default::default());
+    let err = function_handler(lambda_event).await.unwrap_err();
+    assert_eq!(err.to_string(), "Missing `events` in the envelope");
+}
@@ -0,0 +1,26 @@
+use lambda_appsync::ID;
+
+lambda_appsync::appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/federation_keys.graphql",
+    only_appsync_types = true,
+    federation_keys = true,
+);
+
+#[test]
+fn test_single_field_key() {
+    let player = Player {
+        id: ID::new(),
+        name: "Ferris".to_owned(),
+    };
+    assert_eq!(player.key(), &player.id);
+}
+
+#[test]
+fn test_compound_key() {
+    let score = Score {
+        game_id: ID::new(),
+        player_id: ID::new(),
+        points: 42,
+    };
+    assert_eq!(score.key(), (&score.game_id, &score.player_id));
+}
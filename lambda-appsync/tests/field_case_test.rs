@@ -0,0 +1,28 @@
+use lambda_appsync::serde_json::json;
+
+// Generate the types from our test schema, keeping the GraphQL casing of the fields
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    only_appsync_types = true,
+    field_case = preserve,
+);
+
+#[test]
+fn test_preserved_field_names() {
+    let stats = PlayerStats {
+        gamesPlayed: 12,
+        winRate: Some(0.5),
+    };
+    let json = serde_json::to_value(&stats).unwrap();
+    assert_eq!(json, json!({"gamesPlayed": 12, "winRate": 0.5}));
+
+    let stats: PlayerStats = serde_json::from_value(json!({"gamesPlayed": 3})).unwrap();
+    assert_eq!(stats.gamesPlayed, 3);
+    assert!(stats.winRate.is_none());
+}
+
+// Keywords are still escaped when the GraphQL casing is preserved
+#[allow(dead_code)]
+fn preserved_keywords_are_escaped(weird: &WeirdFieldNames) -> [bool; 3] {
+    [weird.r#type, weird.r_self, weird.r_crate]
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use lambda_appsync::{appsync_operation, AppsyncError};
+use serde_json::{json, Value};
+
+lambda_appsync::appsync_lambda_main!("schema.graphql", health_check_op = "__health");
+
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+async fn invoke(payload: Value) -> Value {
+    let lambda_event = lambda_runtime::LambdaEvent::new(payload, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+// A single test, as the cold start is tracked for the whole process
+#[tokio::test]
+async fn test_health_check() {
+    // A probe sent as an object is answered with an object, even in batch mode
+    let health = invoke(json!({"fieldName": "__health"})).await;
+    assert_eq!(health["data"]["status"], "healthy");
+    assert_eq!(health["data"]["coldStart"], true);
+    let schema_hash = health["data"]["schemaHash"].as_str().unwrap();
+    assert_eq!(schema_hash.len(), 16);
+    assert!(schema_hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+    // A probe sent as a single-element list is answered with a list
+    let health = invoke(json!([{"fieldName": "__health"}])).await;
+    assert_eq!(health.as_array().unwrap().len(), 1);
+    assert_eq!(health[0]["data"]["status"], "healthy");
+    assert_eq!(health[0]["data"]["coldStart"], false);
+    assert_eq!(health[0]["data"]["schemaHash"], schema_hash);
+
+    // Regular events are still dispatched
+    let response = invoke(json!([common::event("Query", "gameStatus", json!({}))])).await;
+    assert_eq!(response, json!([{"data": "STARTED"}]));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false);
+
+use operation_args::queries::PlayerArgs;
+
+#[appsync_operation(mutation(createPlayer), with_appsync_event, keep_raw_args)]
+async fn create_player(
+    name: String,
+    event: &AppsyncEvent<Operation>,
+) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name: format!("{name} {}", event.args),
+        team: Team::Rust,
+    })
+}
+
+#[appsync_operation(query(player), args_struct, with_appsync_event, keep_raw_args)]
+async fn get_player(
+    args: PlayerArgs,
+    event: &AppsyncEvent<Operation>,
+) -> Result<Option<Player>, AppsyncError> {
+    assert_eq!(event.args["id"], json!(args.id));
+    Ok(Some(Player {
+        id: args.id,
+        name: event.args.to_string(),
+        team: Team::Python,
+    }))
+}
+
+#[appsync_operation(query(gameStatus), with_appsync_event)]
+async fn get_game_status(event: &AppsyncEvent<Operation>) -> Result<GameStatus, AppsyncError> {
+    // Without `keep_raw_args`, the arguments are taken
+    assert_eq!(event.args, Value::Null);
+    Ok(GameStatus::Started)
+}
+
+async fn call(parent_type_name: &str, field_name: &str, arguments: Value) -> Value {
+    let event = common::event(parent_type_name, field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_keep_raw_args() {
+    let response = call("Mutation", "createPlayer", json!({"name": "Ferris"})).await;
+    assert_eq!(response["data"]["name"], r#"Ferris {"name":"Ferris"}"#);
+}
+
+#[tokio::test]
+async fn test_keep_raw_args_struct() {
+    let id = ID::new();
+    let response = call("Query", "player", json!({"id": id})).await;
+    assert_eq!(response["data"]["id"], json!(id));
+    assert_eq!(response["data"]["name"], json!({"id": id}).to_string());
+}
+
+#[tokio::test]
+async fn test_args_taken_by_default() {
+    let response = call("Query", "gameStatus", json!({})).await;
+    assert_eq!(response["data"], "STARTED");
+}
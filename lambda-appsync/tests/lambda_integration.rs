@@ -56,12 +56,17 @@ appsync_lambda_main!(
 
 // Auth hook for testing group-based access
 async fn verify_request(event: &AppsyncEvent<Operation>) -> Option<AppsyncResponse> {
-    if let AppsyncIdentity::Cognito(AppsyncIdentityCognito { groups, .. }) = &event.identity {
-        // Require "admin" group for deletePlayer operation
-        if let Operation::Mutation(MutationField::DeletePlayer) = event.info.operation {
-            if !groups.iter().flatten().any(|g| g == "admin") {
-                return Some(AppsyncResponse::unauthorized());
+    // Require "admin" group for deletePlayer operation, unauthenticated requests are rejected
+    if let Operation::Mutation(MutationField::DeletePlayer) = event.info.operation {
+        let authorized = match &event.identity {
+            AppsyncIdentity::Cognito(AppsyncIdentityCognito { groups, .. }) => {
+                groups.iter().flatten().any(|g| g == "admin")
             }
+            AppsyncIdentity::Iam(_) | AppsyncIdentity::Oidc(_) | AppsyncIdentity::Lambda(_) => true,
+            AppsyncIdentity::Unauthenticated => false,
+        };
+        if !authorized {
+            return Some(AppsyncResponse::unauthorized());
         }
     }
     None
@@ -213,7 +218,7 @@ async fn test_cognito_auth_no_groups() {
 }
 
 #[tokio::test]
-async fn test_cognito_auth_with_admin_group() {
+async fn test_cognito_auth_null_groups() {
     let player = Player {
         id: ID::new(),
         name: "Test Player".to_string(),
@@ -237,9 +242,9 @@ async fn test_cognito_auth_with_admin_group() {
             "issuer": "cognito",
             "username": "testuser",
             "claims": {},
-            "groups": ["admin"],
             "sourceIp": ["1.1.1.1"],
-            "defaultAuthStrategy": "ALLOW"
+            "defaultAuthStrategy": "ALLOW",
+            "groups": null
         },
         "request": null,
         "source": null
@@ -248,10 +253,76 @@ async fn test_cognito_auth_with_admin_group() {
     let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
     let response = function_handler(lambda_event).await.unwrap();
 
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value[0]["errorType"], "Unauthorized");
+}
+
+#[tokio::test]
+async fn test_cognito_auth_with_admin_group() {
+    let player = Player {
+        id: ID::new(),
+        name: "Test Player".to_string(),
+        team: Team::Rust,
+    };
+    Database.insert(player.id, player.clone());
+
+    let event = json!([{
+        "info": {
+            "fieldName": "deletePlayer",
+            "parentTypeName": "Mutation",
+            "variables": {},
+            "selectionSetList": ["id", "name", "team"],
+            "selectionSetGraphQL": "{id name team}"
+        },
+        "arguments": {
+            "id": player.id.to_string()
+        },
+        "identity": AppsyncIdentityCognito::test("user123", "testuser", ["admin"]),
+        "request": null,
+        "source": null
+    }]);
+
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
     let response_value = serde_json::to_value(response).unwrap();
     assert_eq!(response_value[0]["data"]["id"], player.id.to_string());
 }
 
+#[tokio::test]
+async fn test_unauthenticated_delete_player() {
+    let player = Player {
+        id: ID::new(),
+        name: "Test Player".to_string(),
+        team: Team::Rust,
+    };
+    Database.insert(player.id, player.clone());
+
+    let event = json!([{
+        "info": {
+            "fieldName": "deletePlayer",
+            "parentTypeName": "Mutation",
+            "variables": {},
+            "selectionSetList": ["id", "name", "team"],
+            "selectionSetGraphQL": "{id name team}"
+        },
+        "arguments": {
+            "id": player.id.to_string()
+        },
+        "identity": null,
+        "request": null,
+        "source": null
+    }]);
+
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value[0]["errorType"], "Unauthorized");
+    // The guarded operation did not run
+    assert!(Database.get(&player.id).is_some());
+}
+
 #[tokio::test]
 async fn test_iam_auth() {
     let event = json!([{
@@ -280,6 +351,38 @@ async fn test_iam_auth() {
     assert!(response_value[0].get("data").is_some_and(|v| v.is_array()));
 }
 
+#[tokio::test]
+async fn test_iam_auth_cognito_federated() {
+    let event = json!([{
+        "info": {
+            "fieldName": "players",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["id", "name", "team"],
+            "selectionSetGraphQL": "{id name team}"
+        },
+        "arguments": {},
+        "identity": {
+            "accountId": "123456789012",
+            "sourceIp": ["1.1.1.1"],
+            "username": "AROAEXAMPLE:CognitoIdentityCredentials",
+            "userArn": "arn:aws:sts::123456789012:assumed-role/Auth_Role/CognitoIdentityCredentials",
+            "cognitoIdentityId": "us-east-1:11111111-2222-3333-4444-555555555555",
+            "cognitoIdentityPoolId": "us-east-1:66666666-7777-8888-9999-000000000000",
+            "cognitoIdentityAuthType": "authenticated",
+            "cognitoIdentityAuthProvider": "cognito-idp.us-east-1.amazonaws.com/us-east-1_example,cognito-idp.us-east-1.amazonaws.com/us-east-1_example:CognitoSignIn:user"
+        },
+        "request": null,
+        "source": null
+    }]);
+
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert!(response_value[0].get("data").is_some_and(|v| v.is_array()));
+}
+
 #[tokio::test]
 async fn test_unimplemented_operation() {
     // Test setGameStatus mutation
@@ -483,3 +586,41 @@ async fn test_delete_player_twice() {
     let response_value = serde_json::to_value(response).unwrap();
     assert_eq!(response_value[0]["errorType"], "NotFound");
 }
+
+#[test]
+fn test_operation_from_names() {
+    assert!(matches!(
+        Operation::from_names("Query", "players"),
+        Some(Operation::Query(QueryField::Players))
+    ));
+    assert!(matches!(
+        Operation::from_names("Mutation", "deletePlayer"),
+        Some(Operation::Mutation(MutationField::DeletePlayer))
+    ));
+    assert!(Operation::from_names("Query", "deletePlayer").is_none());
+    assert!(Operation::from_names("Unknown", "players").is_none());
+}
+
+#[test]
+fn test_operation_kind_predicates() {
+    let query = Operation::Query(QueryField::Players);
+    assert!(query.is_query() && !query.is_mutation() && !query.is_subscription());
+    let mutation = Operation::Mutation(MutationField::DeletePlayer);
+    assert!(!mutation.is_query() && mutation.is_mutation() && !mutation.is_subscription());
+    let subscription = Operation::Subscription(SubscriptionField::OnGameStatusChange);
+    assert!(
+        !subscription.is_query() && !subscription.is_mutation() && subscription.is_subscription()
+    );
+}
+
+#[test]
+fn test_operation_deserialize_unknown() {
+    let err = serde_json::from_value::<Operation>(json!({
+        "parentTypeName": "Query",
+        "fieldName": "unknownField"
+    }))
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown operation `Query.unknownField`"));
+}
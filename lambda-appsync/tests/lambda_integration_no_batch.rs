@@ -233,15 +233,7 @@ async fn test_cognito_auth_with_admin_group() {
         "arguments": {
             "id": player.id.to_string()
         },
-        "identity": {
-            "sub": "user123",
-            "issuer": "cognito",
-            "username": "testuser",
-            "claims": {},
-            "groups": ["admin"],
-            "sourceIp": ["1.1.1.1"],
-            "defaultAuthStrategy": "ALLOW"
-        },
+        "identity": AppsyncIdentityCognito::test("user123", "testuser", ["admin"]),
         "request": null,
         "source": null
     });
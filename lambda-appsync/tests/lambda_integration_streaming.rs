@@ -0,0 +1,65 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::json;
+
+// Generate AppSync types and a streaming runtime from schema
+appsync_lambda_main!("schema.graphql", batch = false, streaming = true);
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(Some(Player {
+        id,
+        name: "Streamed Player".to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+async fn call(event: serde_json::Value) -> serde_json::Value {
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    let bytes = response.stream.collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_streamed_response() {
+    let id = ID::new();
+    let response = call(json!({
+        "info": {
+            "fieldName": "player",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["id", "name", "team"],
+            "selectionSetGraphQL": "{id name team}"
+        },
+        "arguments": {
+            "id": id
+        },
+        "identity": null,
+        "request": null,
+        "source": null
+    }))
+    .await;
+    assert_eq!(
+        response,
+        json!({"data": {"id": id, "name": "Streamed Player", "team": "RUST"}})
+    );
+}
+
+#[tokio::test]
+async fn test_streamed_error() {
+    let response = call(json!({
+        "info": {
+            "fieldName": "players",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["id"],
+            "selectionSetGraphQL": "{id}"
+        },
+        "arguments": {},
+        "identity": null,
+        "request": null,
+        "source": null
+    }))
+    .await;
+    assert_eq!(response["errorType"], "Unimplemented");
+}
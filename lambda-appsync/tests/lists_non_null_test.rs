@@ -0,0 +1,57 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::json;
+
+appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/lists_non_null.graphql",
+    batch = false,
+    lists_non_null = true,
+);
+
+#[appsync_operation(query(players))]
+async fn get_players(ids: Vec<ID>) -> Result<Vec<Player>, AppsyncError> {
+    Ok(ids
+        .into_iter()
+        .map(|id| Player {
+            id,
+            name: "Ferris".to_owned(),
+        })
+        .collect())
+}
+
+#[test]
+fn test_types_skip_null_elements() {
+    let team: Team = serde_json::from_value(json!({
+        "members": [null, {"id": "00000000-0000-0000-0000-000000000000", "name": "Ferris"}],
+        "tags": ["rust", null],
+        "grid": [[1, 2], null, [3]]
+    }))
+    .unwrap();
+    assert_eq!(team.members.len(), 1);
+    assert_eq!(team.tags, Some(vec!["rust".to_owned()]));
+    let grid: Vec<Vec<i32>> = team.grid.unwrap();
+    assert_eq!(grid, [vec![1, 2], vec![3]]);
+
+    let team: Team = serde_json::from_value(json!({"members": []})).unwrap();
+    assert_eq!(team.tags, None);
+}
+
+#[tokio::test]
+async fn test_list_args_skip_null_elements() {
+    let event = json!({
+        "info": {
+            "fieldName": "players",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["id"],
+            "selectionSetGraphQL": "{id}"
+        },
+        "arguments": {"ids": ["00000000-0000-0000-0000-000000000000", null]},
+        "identity": null,
+        "request": null,
+        "source": null
+    });
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap();
+
+    assert_eq!(response["data"].as_array().unwrap().len(), 1);
+}
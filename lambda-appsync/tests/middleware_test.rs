@@ -0,0 +1,62 @@
+mod common;
+
+use std::sync::Mutex;
+
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, middleware::Next, AppsyncError, AppsyncEvent,
+    AppsyncResponse,
+};
+use serde_json::{json, Value};
+
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Short-circuits `players`, records the other operations and their outcome
+async fn record(event: AppsyncEvent<Operation>, next: Next<Operation>) -> AppsyncResponse {
+    let operation = event.info.operation;
+    if let Operation::Query(QueryField::Players) = operation {
+        return AppsyncResponse::unauthorized();
+    }
+    let response = next.run(event).await;
+    CALLS
+        .lock()
+        .unwrap()
+        .push(format!("{operation:?}: {:?}", response.error_type()));
+    response
+}
+
+appsync_lambda_main!("schema.graphql", middleware = record);
+
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    unreachable!("short-circuited by the middleware")
+}
+
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+fn query(field_name: &str) -> Value {
+    common::event("Query", field_name, json!({}))
+}
+
+#[tokio::test]
+async fn test_middleware() {
+    let event = json!([query("gameStatus"), query("players"), query("player")]);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap();
+
+    assert_eq!(response[0]["data"], "STARTED");
+    assert_eq!(response[1]["errorType"], "Unauthorized");
+    assert_eq!(response[2]["errorType"], "Unimplemented");
+
+    let mut calls = CALLS.lock().unwrap().clone();
+    calls.sort();
+    assert_eq!(
+        calls,
+        [
+            "Query(GameStatus): None",
+            "Query(Player): Some(\"Unimplemented\")"
+        ]
+    );
+}
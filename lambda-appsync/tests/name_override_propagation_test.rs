@@ -0,0 +1,64 @@
+mod common;
+
+use lambda_appsync::{appsync_operation, serde_json::json, AppsyncError, ID};
+
+// Only the types are renamed, the operations and fields using them follow
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    batch = false,
+    name_override = Player: GamePlayer,
+    name_override = Team: Squad,
+    // An explicit `type_override` still wins
+    type_override = Mutation.deletePlayer: String,
+);
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<GamePlayer>, AppsyncError> {
+    Ok(Some(GamePlayer {
+        id,
+        name: "Ferris".to_owned(),
+        team: Squad::Rust,
+    }))
+}
+
+#[appsync_operation(mutation(deletePlayer))]
+async fn delete_player(id: ID) -> Result<String, AppsyncError> {
+    Ok(id.to_string())
+}
+
+#[test]
+fn test_renamed_field_types() {
+    let optional_team = OptionalTeam {
+        team: Some(Squad::Python),
+    };
+    assert_eq!(
+        serde_json::to_value(&optional_team).unwrap(),
+        json!({"team": "PYTHON"})
+    );
+}
+
+#[tokio::test]
+async fn test_renamed_return_type() {
+    let id = ID::new();
+    let event = common::with_selection_set(
+        common::event("Query", "player", json!({"id": id})),
+        &["id", "name", "team"],
+    );
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(
+        response_value["data"],
+        json!({"id": id, "name": "Ferris", "team": "RUST"})
+    );
+}
+
+#[tokio::test]
+async fn test_type_override_wins() {
+    let id = ID::new();
+    let event = common::event("Mutation", "deletePlayer", json!({"id": id}));
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["data"], json!(id.to_string()));
+}
@@ -0,0 +1,63 @@
+use lambda_appsync::{appsync_operation, serde_json::json, AppsyncError, ID};
+
+// Two schemas declaring the same `Player` and `Team` types in the same crate
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    only_appsync_types = true,
+    namespace = admin,
+);
+lambda_appsync::appsync_lambda_main!("schema_public.graphql", batch = false, namespace = public,);
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<PublicPlayer>, AppsyncError> {
+    Ok(Some(PublicPlayer {
+        id,
+        nickname: "Ferris".to_owned(),
+        team: Some(PublicTeam::Rust),
+    }))
+}
+
+#[test]
+fn test_namespaced_types() {
+    let id = ID::new();
+    let admin_player = AdminPlayer {
+        id,
+        name: "Ferris".to_owned(),
+        team: AdminTeam::MultiWordsTeam,
+    };
+    assert_eq!(
+        serde_json::to_value(&admin_player).unwrap(),
+        json!({"id": id, "name": "Ferris", "team": "MULTI_WORDS_TEAM"})
+    );
+
+    let public_player: PublicPlayer =
+        serde_json::from_value(json!({"id": id, "nickname": "Ferris", "team": "GO"})).unwrap();
+    assert_eq!(public_player.team, Some(PublicTeam::Go));
+    assert_eq!(PublicTeam::COUNT, 2);
+    assert_eq!(AdminTeam::COUNT, 4);
+}
+
+#[tokio::test]
+async fn test_namespaced_operation() {
+    let id = ID::new();
+    let event = json!({
+        "info": {
+            "fieldName": "player",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["id", "nickname"],
+            "selectionSetGraphQL": "{id nickname}"
+        },
+        "arguments": {"id": id},
+        "identity": null,
+        "request": null,
+        "source": null
+    });
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(
+        response_value["data"],
+        json!({"id": id, "nickname": "Ferris", "team": "RUST"})
+    );
+}
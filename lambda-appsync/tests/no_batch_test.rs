@@ -0,0 +1,61 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+use serde_json::{json, Value};
+
+// The `testing` feature makes the handlers public
+appsync_lambda_main!("schema.graphql");
+
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+static MAX_RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+#[appsync_operation(query(players), no_batch)]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+    MAX_RUNNING.fetch_max(running, Ordering::SeqCst);
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    RUNNING.fetch_sub(1, Ordering::SeqCst);
+    Ok(vec![])
+}
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(Some(Player {
+        id,
+        name: "Ferris".to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+fn event(field_name: &str, arguments: Value) -> AppsyncEvent<Operation> {
+    serde_json::from_value(common::event("Query", field_name, arguments)).unwrap()
+}
+
+#[test]
+fn test_is_no_batch() {
+    assert!(Operation::Query(QueryField::Players).is_no_batch());
+    assert!(!Operation::Query(QueryField::Player).is_no_batch());
+    assert!(!Operation::Query(QueryField::GameStatus).is_no_batch());
+}
+
+#[tokio::test]
+async fn test_no_batch_operations_run_sequentially() {
+    let id = ID::new();
+    let events = vec![
+        event("players", json!({})),
+        event("player", json!({"id": id})),
+        event("players", json!({})),
+        event("players", json!({})),
+    ];
+
+    let responses = appsync_batch_handler(events).await;
+
+    assert_eq!(MAX_RUNNING.load(Ordering::SeqCst), 1);
+    assert_eq!(responses.len(), 4);
+    assert_eq!(responses[0].data(), Some(&json!([])));
+    assert_eq!(responses[1].data().unwrap()["id"], json!(id));
+    assert_eq!(responses[2].data(), Some(&json!([])));
+    assert_eq!(responses[3].data(), Some(&json!([])));
+}
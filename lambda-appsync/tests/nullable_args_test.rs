@@ -0,0 +1,68 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, Nullable, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/partial_update.graphql",
+    batch = false,
+    nullable_arg = Mutation.updatePlayer.nickname,
+);
+
+#[appsync_operation(mutation(updatePlayer))]
+async fn update_player(
+    id: ID,
+    nickname: Nullable<String>,
+    level: Option<i32>,
+) -> Result<Player, AppsyncError> {
+    // Absent nickname: keep the stored one, null: remove it
+    let nickname = match nickname {
+        Nullable::Absent => Some("stored".to_owned()),
+        Nullable::Null => None,
+        Nullable::Value(nickname) => Some(nickname),
+    };
+    Ok(Player {
+        id,
+        nickname,
+        level,
+    })
+}
+
+async fn update(arguments: Value) -> Value {
+    let event = json!({
+        "info": {
+            "fieldName": "updatePlayer",
+            "parentTypeName": "Mutation",
+            "variables": {},
+            "selectionSetList": ["id", "nickname", "level"],
+            "selectionSetGraphQL": "{id nickname level}"
+        },
+        "arguments": arguments,
+        "identity": null,
+        "request": null,
+        "source": null
+    });
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    serde_json::to_value(response).unwrap()
+}
+
+#[tokio::test]
+async fn test_nullable_arg_absent() {
+    let id = ID::new();
+    let response = update(json!({"id": id})).await;
+    assert_eq!(response["data"]["nickname"], "stored");
+}
+
+#[tokio::test]
+async fn test_nullable_arg_null() {
+    let id = ID::new();
+    let response = update(json!({"id": id, "nickname": null})).await;
+    assert!(response["data"].get("nickname").is_none());
+}
+
+#[tokio::test]
+async fn test_nullable_arg_value() {
+    let id = ID::new();
+    let response = update(json!({"id": id, "nickname": "Ferris", "level": 3})).await;
+    assert_eq!(response["data"]["nickname"], "Ferris");
+    assert_eq!(response["data"]["level"], 3);
+}
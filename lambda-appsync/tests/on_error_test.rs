@@ -0,0 +1,57 @@
+mod common;
+
+use std::sync::Mutex;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+static REPORTED: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+fn report_error(e: &AppsyncError, operation: &Operation) {
+    REPORTED
+        .lock()
+        .unwrap()
+        .push((e.error_type.clone(), format!("{operation:?}")));
+}
+
+appsync_lambda_main!("schema.graphql", batch = false, on_error = report_error);
+
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Err(AppsyncError::new("NotFound", "No such player"))
+}
+
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+async fn query(field_name: &str, arguments: Value) -> Value {
+    let event = common::event("Query", field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_on_error() {
+    // Successful operations are not reported
+    let response = query("gameStatus", json!({})).await;
+    assert_eq!(response["data"], "STARTED");
+    assert!(REPORTED.lock().unwrap().is_empty());
+
+    // Errors are reported before being converted into the response
+    let response = query("player", json!({"id": ID::new()})).await;
+    assert_eq!(response["errorType"], "NotFound");
+    // Invalid arguments go through the same path
+    let response = query("player", json!({})).await;
+    assert_eq!(response["errorType"], "InvalidArgs");
+
+    let reported = REPORTED.lock().unwrap();
+    assert_eq!(
+        *reported,
+        vec![
+            ("NotFound".to_owned(), "Query(Player)".to_owned()),
+            ("InvalidArgs".to_owned(), "Query(Player)".to_owned()),
+        ]
+    );
+}
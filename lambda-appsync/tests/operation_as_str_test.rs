@@ -0,0 +1,17 @@
+use std::collections::HashSet;
+
+lambda_appsync::appsync_lambda_main!("schema.graphql", emit_registry = true,);
+
+#[test]
+fn test_operation_as_str() {
+    let mut names = HashSet::new();
+    for meta in operation_registry() {
+        let operation = Operation::from_names(&meta.kind.to_string(), meta.name).unwrap();
+        let name = operation.as_str();
+        assert_eq!(name, format!("{}.{}", meta.kind, meta.name));
+        assert_eq!(<&'static str>::from(operation), name);
+        assert!(names.insert(name), "duplicate operation name `{name}`");
+    }
+    assert_eq!(names.len(), operation_registry().len());
+    assert!(names.contains("Mutation.createPlayer"));
+}
@@ -0,0 +1,58 @@
+use lambda_appsync::{serde_json::json, Nullable, ID};
+
+lambda_appsync::appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/partial_update.graphql",
+    only_appsync_types = true,
+    generate_patch = Player,
+);
+
+fn player() -> Player {
+    Player {
+        id: ID::new(),
+        nickname: Some("Ferris".to_owned()),
+        level: Some(3),
+    }
+}
+
+#[test]
+fn test_empty_patch() {
+    let mut player = player();
+    let id = player.id;
+    player.apply_patch(PlayerPatch::default());
+    assert_eq!(player.id, id);
+    assert_eq!(player.nickname.as_deref(), Some("Ferris"));
+    assert_eq!(player.level, Some(3));
+}
+
+#[test]
+fn test_patch_from_json() {
+    let mut player = player();
+    let patch: PlayerPatch = serde_json::from_value(json!({"nickname": null, "level": 4})).unwrap();
+    assert!(patch.id.is_none());
+    assert!(patch.nickname.is_null());
+    assert_eq!(patch.level, Nullable::Value(4));
+
+    player.apply_patch(patch);
+    assert_eq!(player.nickname, None);
+    assert_eq!(player.level, Some(4));
+}
+
+#[test]
+fn test_patch_required_field() {
+    let mut player = player();
+    let id = ID::new();
+    player.apply_patch(PlayerPatch {
+        id: Some(id),
+        ..Default::default()
+    });
+    assert_eq!(player.id, id);
+}
+
+#[test]
+fn test_patch_serialization() {
+    let patch = PlayerPatch {
+        level: Nullable::Null,
+        ..Default::default()
+    };
+    assert_eq!(serde_json::to_value(patch).unwrap(), json!({"level": null}));
+}
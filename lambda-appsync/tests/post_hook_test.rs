@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::Mutex;
+
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, AppsyncEventInfo,
+    AppsyncIdentity, AppsyncResponse,
+};
+use serde_json::{json, Value};
+
+static AUDIT: Mutex<Vec<(String, Option<String>)>> = Mutex::new(Vec::new());
+
+// Rejects `players`, the post hook still sees the response
+async fn verify_request(event: &AppsyncEvent<Operation>) -> Option<AppsyncResponse> {
+    if let Operation::Query(QueryField::Players) = event.info.operation {
+        return Some(AppsyncResponse::unauthorized());
+    }
+    None
+}
+
+// Audits the outcome of every request and scrubs the errors
+fn finalize(
+    info: &AppsyncEventInfo<Operation>,
+    _identity: &AppsyncIdentity,
+    response: AppsyncResponse,
+) -> AppsyncResponse {
+    AUDIT.lock().unwrap().push((
+        format!("{:?}", info.operation),
+        response.error_type().map(ToOwned::to_owned),
+    ));
+    if response.is_error() {
+        AppsyncError::new("Error", "Something went wrong").into()
+    } else {
+        response
+    }
+}
+
+appsync_lambda_main!(
+    "schema.graphql",
+    hook = verify_request,
+    post_hook = finalize
+);
+
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+fn query(field_name: &str) -> Value {
+    common::event("Query", field_name, json!({}))
+}
+
+#[tokio::test]
+async fn test_post_hook() {
+    let event = json!([query("gameStatus"), query("players"), query("player")]);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap();
+
+    assert_eq!(response[0]["data"], "STARTED");
+    assert_eq!(response[1]["errorType"], "Error");
+    assert_eq!(response[2]["errorType"], "Error");
+
+    let mut audit = AUDIT.lock().unwrap().clone();
+    audit.sort();
+    assert_eq!(
+        audit,
+        [
+            ("Query(GameStatus)".to_owned(), None),
+            ("Query(Player)".to_owned(), Some("Unimplemented".to_owned())),
+            ("Query(Players)".to_owned(), Some("Unauthorized".to_owned())),
+        ]
+    );
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "proptest")]
+
+use lambda_appsync::proptest::{prop_assert, prop_assert_eq, proptest};
+
+lambda_appsync::appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/arbitrary.graphql",
+    only_appsync_types = true,
+    proptest = true,
+);
+
+proptest! {
+    #[test]
+    fn test_enum_strategy(team in team_strategy()) {
+        prop_assert!(Team::all().contains(&team));
+    }
+
+    #[test]
+    fn test_type_strategy_round_trip(player in player_strategy()) {
+        prop_assert!(lambda_appsync::AWSDateTime::is_valid(&player.created_at));
+        prop_assert!(player.email.iter().all(|email| email.contains('@')));
+
+        let json = serde_json::to_value(&player).unwrap();
+        let back: Player = serde_json::from_value(json.clone()).unwrap();
+        prop_assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    }
+
+    #[test]
+    fn test_input_strategy_round_trip(input in player_input_strategy()) {
+        let json = serde_json::to_value(&input).unwrap();
+        let back: PlayerInput = serde_json::from_value(json.clone()).unwrap();
+        prop_assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    }
+
+    #[test]
+    fn test_recursive_type_strategy(guild in guild_strategy_with_depth(1)) {
+        // Lists of types are nested at most once
+        for sub_guild in guild.sub_guilds.iter().flatten() {
+            prop_assert!(sub_guild.members.is_empty());
+            prop_assert!(sub_guild.sub_guilds.iter().flatten().next().is_none());
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+use serde_json::json;
+
+appsync_lambda_main!("schema.graphql", capture_raw_event = true);
+
+// Uses a field that `AppsyncEvent` does not model
+#[appsync_operation(query(player), with_appsync_event)]
+async fn get_player(
+    id: ID,
+    event: &AppsyncEvent<Operation>,
+) -> Result<Option<Player>, AppsyncError> {
+    let raw = event.raw.as_ref().expect("capture_raw_event is set");
+    // Arguments are still available in the raw event, even if taken from `event.args`
+    assert_eq!(raw["arguments"]["id"], id.to_string());
+    Ok(Some(Player {
+        id,
+        name: raw["stash"]["playerName"]
+            .as_str()
+            .unwrap_or_default()
+            .to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+#[tokio::test]
+async fn test_raw_event_in_batch() {
+    let id1 = ID::new();
+    let id2 = ID::new();
+    let event = |id: ID, name: &str| {
+        json!({
+            "info": {
+                "fieldName": "player",
+                "parentTypeName": "Query",
+                "variables": {},
+                "selectionSetList": ["id", "name", "team"],
+                "selectionSetGraphQL": "{id name team}"
+            },
+            "arguments": {"id": id},
+            "identity": null,
+            "request": null,
+            "source": null,
+            "stash": {"playerName": name}
+        })
+    };
+    let lambda_event = lambda_runtime::LambdaEvent::new(
+        json!([event(id1, "First"), event(id2, "Second")]),
+        Default::default(),
+    );
+    let response = function_handler(lambda_event).await.unwrap();
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value[0]["data"]["name"], "First");
+    assert_eq!(response_value[1]["data"]["name"], "Second");
+}
@@ -14,7 +14,7 @@ lambda_appsync::appsync_lambda_main!("schema.graphql",
     name_override = WeirdFieldNames.crate: no_crate,
     name_override = WeirdFieldNames.u8: no_u8,
 
-    // MUST also override ALL the operation return type !!!
+    // Explicit overrides of the renamed type, which is otherwise propagated
     type_override = Query.players: NewPlayer,
     type_override = Query.player: NewPlayer,
     type_override = Mutation.createPlayer: NewPlayer,
@@ -71,6 +71,19 @@ fn test_team_enum() {
     }
 }
 
+#[test]
+fn test_renamed_variant_keeps_graphql_name() {
+    assert_eq!(serde_json::to_value(Team::Snake).unwrap(), json!("PYTHON"));
+    assert_eq!(
+        serde_json::from_value::<Team>(json!("PYTHON")).unwrap(),
+        Team::Snake
+    );
+    assert_eq!(Team::Snake.to_string(), "PYTHON");
+    assert_eq!("PYTHON".parse::<Team>().unwrap(), Team::Snake);
+    assert_eq!(Team::MultiWordsTeam.to_string(), "MULTI_WORDS_TEAM");
+    assert!("Snake".parse::<Team>().is_err());
+}
+
 #[test]
 fn test_weird_field_names() {
     // Test that Rust keywords are properly escaped in field names
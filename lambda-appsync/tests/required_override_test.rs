@@ -0,0 +1,42 @@
+use lambda_appsync::serde_json::{self, json};
+
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    only_appsync_types = true,
+    required_override = OptionalTeam.team,
+    required_override = PlayerStats.winRate: default,
+);
+
+#[test]
+fn test_required_field_is_not_optional() {
+    let optional_team = OptionalTeam { team: Team::Rust };
+    let value = serde_json::to_value(&optional_team).unwrap();
+    assert_eq!(value, json!({"team": "RUST"}));
+
+    let optional_team: OptionalTeam = serde_json::from_value(value).unwrap();
+    assert_eq!(optional_team.team, Team::Rust);
+}
+
+#[test]
+fn test_required_field_rejects_null_or_absent() {
+    assert!(serde_json::from_value::<OptionalTeam>(json!({"team": null})).is_err());
+    assert!(serde_json::from_value::<OptionalTeam>(json!({})).is_err());
+}
+
+#[test]
+fn test_required_field_with_default() {
+    let stats: PlayerStats =
+        serde_json::from_value(json!({"gamesPlayed": 3, "winRate": 0.5})).unwrap();
+    assert_eq!(stats.win_rate, 0.5);
+
+    let stats: PlayerStats =
+        serde_json::from_value(json!({"gamesPlayed": 3, "winRate": null})).unwrap();
+    assert_eq!(stats.win_rate, 0.0);
+
+    let stats: PlayerStats = serde_json::from_value(json!({"gamesPlayed": 3})).unwrap();
+    assert_eq!(stats.games_played, 3);
+    assert_eq!(stats.win_rate, 0.0);
+
+    let value = serde_json::to_value(&stats).unwrap();
+    assert_eq!(value, json!({"gamesPlayed": 3, "winRate": 0.0}));
+}
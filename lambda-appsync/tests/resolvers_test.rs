@@ -0,0 +1,96 @@
+mod common;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false, resolvers = Resolvers);
+
+#[derive(Default)]
+struct Resolvers;
+
+impl QueryResolvers for Resolvers {
+    async fn game_status(&self) -> Result<GameStatus, AppsyncError> {
+        Ok(GameStatus::Started)
+    }
+    async fn player(&self, id: ID) -> Result<Option<Player>, AppsyncError> {
+        Ok(Some(Player {
+            id,
+            name: "Ferris".to_owned(),
+            team: Team::Rust,
+        }))
+    }
+}
+
+impl MutationResolvers for Resolvers {
+    async fn create_player(&self, name: String) -> Result<Player, AppsyncError> {
+        Ok(Player {
+            id: ID::new(),
+            name,
+            team: Team::Rust,
+        })
+    }
+    async fn delete_player(&self, _id: ID) -> Result<Player, AppsyncError> {
+        Err(AppsyncError::new("NotFound", "No such player"))
+    }
+}
+
+// Takes precedence over the resolver
+#[appsync_operation(mutation(setGameStatus))]
+async fn set_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Stopped)
+}
+
+// Business logic written against the trait can be tested with a mock
+async fn player_name<R: QueryResolvers>(resolvers: &R, id: ID) -> Option<String> {
+    Some(resolvers.player(id).await.ok()??.name)
+}
+
+struct MockResolvers;
+
+impl QueryResolvers for MockResolvers {
+    async fn player(&self, _id: ID) -> Result<Option<Player>, AppsyncError> {
+        Ok(None)
+    }
+}
+
+async fn call(parent_type_name: &str, field_name: &str, arguments: Value) -> Value {
+    let event = common::event(parent_type_name, field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_dispatch_to_resolvers() {
+    let id = ID::new();
+    let response = call("Query", "player", json!({"id": id})).await;
+    assert_eq!(response["data"]["id"], id.to_string());
+    assert_eq!(response["data"]["team"], "RUST");
+
+    let response = call("Mutation", "createPlayer", json!({"name": "Ferris"})).await;
+    assert_eq!(response["data"]["name"], "Ferris");
+
+    let response = call("Mutation", "deletePlayer", json!({"id": id})).await;
+    assert_eq!(response["errorType"], "NotFound");
+
+    let response = call("Mutation", "setGameStatus", json!({})).await;
+    assert_eq!(response["data"], "STOPPED");
+
+    // Not overridden by `Resolvers`
+    let response = call("Query", "players", json!({})).await;
+    assert_eq!(response["errorType"], "Unimplemented");
+}
+
+// Users may have their own `resolvers` item next to the generated code
+#[allow(dead_code)]
+fn resolvers() -> &'static str {
+    "user defined"
+}
+
+#[tokio::test]
+async fn test_mock_resolvers() {
+    assert_eq!(player_name(&MockResolvers, ID::new()).await, None);
+    assert_eq!(
+        player_name(&Resolvers, ID::new()).await.as_deref(),
+        Some("Ferris")
+    );
+}
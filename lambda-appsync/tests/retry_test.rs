@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false);
+
+static PLAYER_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static PLAYERS_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static GAME_STATUS_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+// Throttled twice, then succeeds
+#[appsync_operation(query(player), retry = { max: 3, base_ms: 1 })]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    if PLAYER_ATTEMPTS.fetch_add(1, Ordering::SeqCst) < 2 {
+        return Err(AppsyncError::new("ThrottlingException", "Rate exceeded"));
+    }
+    Ok(Some(Player {
+        id,
+        name: "Ferris".to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+// Always throttled, gives up after 2 retries
+#[appsync_operation(query(players), retry = { max: 2, base_ms: 1 }, keep_original_function_name)]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    PLAYERS_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+    Err(AppsyncError::new("ThrottlingException", "Rate exceeded"))
+}
+
+fn is_busy(e: &AppsyncError) -> bool {
+    e.error_type == "Busy"
+}
+
+// Custom predicate, a non-retryable error is returned right away
+#[appsync_operation(query(gameStatus), retry = { max: 5, retryable: is_busy })]
+fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    match GAME_STATUS_ATTEMPTS.fetch_add(1, Ordering::SeqCst) {
+        0 => Err(AppsyncError::new("Busy", "Try again")),
+        _ => Err(AppsyncError::new("ThrottlingException", "Not retried")),
+    }
+}
+
+async fn query(field_name: &str, arguments: Value) -> Value {
+    let event = common::event("Query", field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_retry_until_success() {
+    let response = query("player", json!({"id": ID::new()})).await;
+    assert_eq!(response["data"]["name"], "Ferris");
+    assert_eq!(PLAYER_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retry_gives_up() {
+    let response = query("players", json!({})).await;
+    assert_eq!(response["errorType"], "ThrottlingException");
+    // First attempt + 2 retries
+    assert_eq!(PLAYERS_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retry_custom_predicate() {
+    let response = query("gameStatus", json!({})).await;
+    assert_eq!(response["errorType"], "ThrottlingException");
+    assert_eq!(GAME_STATUS_ATTEMPTS.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,41 @@
+// Generated by lambda-appsync from the GraphQL schema, do not edit manually.
+
+export type AWSDateTime = string;
+export type AWSEmail = string;
+export type AWSJSON = string;
+export type AWSTimestamp = number;
+
+/** A game between several players */
+export interface Game {
+  id: string;
+  /** When the game started */
+  startedAt: AWSTimestamp | null;
+  status: GameStatus;
+  players: Array<Player>;
+  scores: Array<number | null> | null;
+  settings: AWSJSON | null;
+}
+
+export interface Player extends Named {
+  name: string;
+  email: AWSEmail | null;
+  winRate: number | null;
+  active: boolean;
+}
+
+/** Anything with a name */
+export interface Named {
+  name: string;
+}
+
+export type Participant = Player | Game;
+
+export type Color = unknown;
+
+export interface GameInput {
+  status: GameStatus;
+  playerNames?: Array<string> | null;
+  startedAt?: AWSDateTime | null;
+}
+
+export type GameStatus = "STARTED" | "STOPPED";
@@ -0,0 +1,89 @@
+mod common;
+
+use aws_sdk_dynamodb::{
+    config::{retry::RetryConfig, BehaviorVersion, Credentials, Region},
+    error::ErrorMetadata,
+    operation::get_item::GetItemError,
+    types::{error::ResourceNotFoundException, AttributeValue},
+};
+use lambda_appsync::{appsync_operation, serde_json::json, AppsyncError, ID};
+
+lambda_appsync::appsync_lambda_main!("schema.graphql", batch = false);
+
+// A client whose requests fail right away, as nothing listens on this port
+fn dynamodb() -> aws_sdk_dynamodb::Client {
+    let config = aws_sdk_dynamodb::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new("eu-west-1"))
+        .credentials_provider(Credentials::new("key", "secret", None, None, "test"))
+        .endpoint_url("http://127.0.0.1:1")
+        .retry_config(RetryConfig::disabled())
+        .build();
+    aws_sdk_dynamodb::Client::from_conf(config)
+}
+
+// `?` converts the SDK error into an `AppsyncError`
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    let output = dynamodb()
+        .get_item()
+        .table_name("players")
+        .key("id", AttributeValue::S(id.to_string()))
+        .send()
+        .await?;
+    Ok(output.item.map(|_| Player {
+        id,
+        name: "Ferris".to_owned(),
+        team: Team::Rust,
+    }))
+}
+
+// Like the SDK operations, returns its large error type
+#[allow(clippy::result_large_err)]
+fn check_table(exists: bool) -> Result<(), GetItemError> {
+    if exists {
+        Ok(())
+    } else {
+        Err(GetItemError::ResourceNotFoundException(
+            ResourceNotFoundException::builder()
+                .message("Requested resource not found")
+                .meta(
+                    ErrorMetadata::builder()
+                        .code("ResourceNotFoundException")
+                        .message("Requested resource not found")
+                        .build(),
+                )
+                .build(),
+        ))
+    }
+}
+
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    check_table(false)?;
+    Ok(vec![])
+}
+
+fn event(field_name: &str, arguments: lambda_appsync::serde_json::Value) -> serde_json::Value {
+    common::with_selection_set(common::event("Query", field_name, arguments), &["id"])
+}
+
+#[tokio::test]
+async fn test_sdk_call_error() {
+    let event = event("player", json!({"id": ID::new()}));
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    // A dispatch failure carries no error code
+    assert_eq!(response.error_type(), Some("Unknown"));
+    assert_eq!(response.data(), None);
+}
+
+#[tokio::test]
+async fn test_sdk_service_error() {
+    let lambda_event =
+        lambda_runtime::LambdaEvent::new(event("players", json!({})), Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+    let response = serde_json::to_value(response).unwrap();
+    assert_eq!(response["errorType"], "ResourceNotFoundException");
+    assert_eq!(response["errorMessage"], "Requested resource not found");
+}
@@ -0,0 +1,61 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/sdl.graphql",
+    only_appsync_types = true,
+    // Overrides change the Rust types, not the emitted schema
+    type_override = Game.settings: String,
+    name_override = Player: GamePlayer,
+    emit_sdl = "lambda-appsync/tests/schemas/sdl.emitted.graphql",
+);
+
+#[test]
+fn test_emit_sdl() {
+    let sdl = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/schemas/sdl.emitted.graphql"
+    ))
+    .unwrap();
+    assert_eq!(
+        sdl,
+        r#"# Generated by lambda-appsync from the parsed GraphQL schema, do not edit manually.
+
+type Query {
+  game(id: ID!): Game
+  games(status: GameStatus, first: Int): [Game!]!
+}
+
+type Mutation {
+  createGame(input: GameInput!): Game!
+}
+
+type Subscription {
+  onCreateGame(status: GameStatus): Game
+}
+
+type Game {
+  id: ID!
+  status: GameStatus!
+  players: [Player!]!
+  scores: [Int]
+  settings: AWSJSON
+}
+
+type Player {
+  name: String!
+  email: AWSEmail
+}
+
+input GameInput {
+  status: GameStatus!
+  playerNames: [String!]
+}
+
+enum GameStatus {
+  WAITING
+  STARTED
+  FINISHED
+}
+"#
+    );
+}
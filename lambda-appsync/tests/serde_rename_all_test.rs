@@ -0,0 +1,53 @@
+use lambda_appsync::{serde_json::json, ID};
+
+lambda_appsync::appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/serde_rename_all.graphql",
+    only_appsync_types = true,
+    serde_rename_all = Player: snake_case,
+    serde_rename_all = PlayerInput: "SCREAMING-KEBAB-CASE",
+    // Overridden fields keep their GraphQL name
+    name_override = Player.createdAt: creation_date,
+);
+
+#[test]
+fn test_rename_all_snake_case() {
+    let id = ID::new();
+    let player = Player {
+        id,
+        display_name: "Ferris".to_owned(),
+        team_name: Some("Rust".to_owned()),
+        creation_date: "2025-01-01T00:00:00Z".to_owned(),
+    };
+    let json = serde_json::to_value(&player).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "id": id,
+            "display_name": "Ferris",
+            "team_name": "Rust",
+            "createdAt": "2025-01-01T00:00:00Z"
+        })
+    );
+    let player: Player = serde_json::from_value(json).unwrap();
+    assert_eq!(player.display_name, "Ferris");
+    assert_eq!(player.creation_date, "2025-01-01T00:00:00Z");
+
+    // The GraphQL names are no longer accepted
+    assert!(serde_json::from_value::<Player>(json!({
+        "id": id,
+        "displayName": "Ferris",
+        "createdAt": "2025-01-01T00:00:00Z"
+    }))
+    .is_err());
+}
+
+#[test]
+fn test_rename_all_kebab_case() {
+    let input: PlayerInput = serde_json::from_value(json!({"DISPLAY-NAME": "Ferris"})).unwrap();
+    assert_eq!(input.display_name, "Ferris");
+    assert_eq!(input.team_name, None);
+    assert_eq!(
+        serde_json::to_value(&input).unwrap(),
+        json!({"DISPLAY-NAME": "Ferris"})
+    );
+}
@@ -53,6 +53,43 @@ fn test_team_enum() {
     }
 }
 
+#[test]
+fn test_enum_serialize_vs_display() {
+    assert_eq!(serde_json::to_string(&Team::Rust).unwrap(), "\"RUST\"");
+    assert_eq!(Team::Rust.to_string(), "RUST");
+    assert_eq!(
+        serde_json::to_string(&Team::MultiWordsTeam).unwrap(),
+        "\"MULTI_WORDS_TEAM\""
+    );
+    assert_eq!(Team::MultiWordsTeam.to_string(), "MULTI_WORDS_TEAM");
+
+    // Apart from the quotes, both always use the GraphQL name
+    for team in Team::all() {
+        assert_eq!(serde_json::to_string(&team).unwrap(), format!("\"{team}\""));
+    }
+}
+
+#[test]
+fn test_multi_words_enum_round_trip() {
+    let json = serde_json::to_value(Team::MultiWordsTeam).unwrap();
+    assert_eq!(json, json!("MULTI_WORDS_TEAM"));
+    assert_eq!(
+        serde_json::from_value::<Team>(json).unwrap(),
+        Team::MultiWordsTeam
+    );
+    assert_eq!(Team::MultiWordsTeam.to_string(), "MULTI_WORDS_TEAM");
+    assert_eq!(
+        "MULTI_WORDS_TEAM".parse::<Team>().unwrap(),
+        Team::MultiWordsTeam
+    );
+
+    // Only the GraphQL name is accepted
+    for name in ["MultiWordsTeam", "multi_words_team", "MULTIWORDSTEAM"] {
+        assert!(serde_json::from_value::<Team>(json!(name)).is_err());
+        assert!(name.parse::<Team>().is_err());
+    }
+}
+
 #[test]
 fn test_game_status_enum() {
     let statuses = vec![GameStatus::Started, GameStatus::Stopped];
@@ -170,6 +207,23 @@ fn test_invalid_deserialization() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_enum_try_from_str() {
+    assert_eq!(Team::try_from("RUST").unwrap(), Team::Rust);
+    assert_eq!(
+        GameStatus::try_from("STOPPED".to_owned()).unwrap(),
+        GameStatus::Stopped
+    );
+
+    let err = Team::try_from("Rust").unwrap_err();
+    assert_eq!(err.error_type, "InvalidStr");
+    assert_eq!(
+        err.error_message,
+        "`Rust` is an invalid value for enum Team"
+    );
+    assert!(Team::try_from(String::from("INVALID")).is_err());
+}
+
 #[test]
 fn test_null_handling() {
     // Test that optional team accepts null
@@ -192,3 +246,17 @@ fn test_null_handling() {
     let optional_team = OptionalTeam { team: None };
     assert_eq!(serde_json::to_value(optional_team).unwrap(), json!({}));
 }
+
+#[test]
+fn test_camel_case_fields_serialization() {
+    let stats = PlayerStats {
+        games_played: 12,
+        win_rate: Some(0.5),
+    };
+    let json = serde_json::to_value(&stats).unwrap();
+    assert_eq!(json, json!({"gamesPlayed": 12, "winRate": 0.5}));
+
+    let stats: PlayerStats = serde_json::from_value(json!({"gamesPlayed": 3})).unwrap();
+    assert_eq!(stats.games_played, 3);
+    assert!(stats.win_rate.is_none());
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncResponse};
+use serde_json::json;
+
+appsync_lambda_main!(
+    "schema.graphql",
+    batch = false,
+    serialize_response = serialize_pretty
+);
+
+fn serialize_pretty(response: &AppsyncResponse) -> Result<Vec<u8>, lambda_runtime::Error> {
+    if response.error_type() == Some("Corrupted") {
+        return Ok(b"{not json".to_vec());
+    }
+    Ok(serde_json::to_vec_pretty(response)?)
+}
+
+#[appsync_operation(query(gameStatus))]
+async fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+#[appsync_operation(mutation(setGameStatus))]
+async fn set_game_status() -> Result<GameStatus, AppsyncError> {
+    Err(AppsyncError::new("Corrupted", "Cannot be serialized"))
+}
+
+fn event(parent_type_name: &str, field_name: &str) -> serde_json::Value {
+    common::event(parent_type_name, field_name, json!({}))
+}
+
+#[tokio::test]
+async fn test_custom_serialization() {
+    let lambda_event =
+        lambda_runtime::LambdaEvent::new(event("Query", "gameStatus"), Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    // The response is returned as serialized by the function
+    assert_eq!(response.get(), "{\n  \"data\": \"STARTED\"\n}");
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        "{\n  \"data\": \"STARTED\"\n}"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_json_is_an_error() {
+    let lambda_event =
+        lambda_runtime::LambdaEvent::new(event("Mutation", "setGameStatus"), Default::default());
+    assert!(function_handler(lambda_event).await.is_err());
+}
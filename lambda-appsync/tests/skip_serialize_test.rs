@@ -0,0 +1,42 @@
+use lambda_appsync::serde_json::{self, json};
+
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    only_appsync_types = true,
+    skip_serialize = PlayerStats.winRate,
+    skip_serialize = OptionalTeam.team,
+);
+
+#[test]
+fn test_skipped_field_is_deserialized() {
+    let stats: PlayerStats =
+        serde_json::from_value(json!({"gamesPlayed": 3, "winRate": 0.5})).unwrap();
+    assert_eq!(stats.games_played, 3);
+    assert_eq!(stats.win_rate, Some(0.5));
+
+    let optional_team: OptionalTeam = serde_json::from_value(json!({"team": "RUST"})).unwrap();
+    assert_eq!(optional_team.team, Some(Team::Rust));
+}
+
+#[test]
+fn test_skipped_field_is_not_serialized() {
+    let stats = PlayerStats {
+        games_played: 3,
+        win_rate: Some(0.5),
+    };
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap(),
+        json!({"gamesPlayed": 3})
+    );
+
+    let optional_team = OptionalTeam {
+        team: Some(Team::Rust),
+    };
+    assert_eq!(serde_json::to_value(&optional_team).unwrap(), json!({}));
+}
+
+#[test]
+fn test_skipped_nullable_field_may_be_absent() {
+    let optional_team: OptionalTeam = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(optional_team.team, None);
+}
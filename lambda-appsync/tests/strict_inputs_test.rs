@@ -0,0 +1,28 @@
+use lambda_appsync::serde_json::json;
+
+lambda_appsync::appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/strict_inputs.graphql",
+    only_appsync_types = true,
+    strict_inputs = true,
+);
+
+#[test]
+fn test_input_rejects_unknown_fields() {
+    let input: PlayerInput = serde_json::from_value(json!({"name": "Ferris"})).unwrap();
+    assert_eq!(input.name, "Ferris");
+    assert!(input.nickname.is_none());
+
+    let err = serde_json::from_value::<PlayerInput>(json!({"name": "Ferris", "nickame": "Crab"}))
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown field `nickame`"));
+}
+
+#[test]
+fn test_type_accepts_unknown_fields() {
+    // Only inputs are strict
+    let player: Player = serde_json::from_value(
+        json!({"id": "00000000-0000-0000-0000-000000000000", "name": "Ferris", "level": 3}),
+    )
+    .unwrap();
+    assert_eq!(player.name, "Ferris");
+}
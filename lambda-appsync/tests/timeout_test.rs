@@ -0,0 +1,60 @@
+mod common;
+
+use std::time::Duration;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false, operation_timeout_ms = 100);
+
+// The per-operation timeout applies before the global one
+#[appsync_operation(query(player), timeout_ms = 10)]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    Ok(None)
+}
+
+// Only the global timeout applies
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    Ok(vec![])
+}
+
+// Synchronous operations can have a timeout too, although it cannot interrupt them
+#[appsync_operation(query(gameStatus), timeout_ms = 1000)]
+fn get_game_status() -> Result<GameStatus, AppsyncError> {
+    Ok(GameStatus::Started)
+}
+
+async fn query(field_name: &str, arguments: Value) -> Value {
+    let event = common::event("Query", field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_operation_timeout() {
+    let response = query("player", json!({"id": ID::new()})).await;
+    assert_eq!(response["errorType"], "Timeout");
+    assert_eq!(
+        response["errorMessage"],
+        "`Query.player` timed out after 10 ms"
+    );
+}
+
+#[tokio::test]
+async fn test_global_timeout() {
+    let response = query("players", json!({})).await;
+    assert_eq!(response["errorType"], "Timeout");
+    assert_eq!(
+        response["errorMessage"],
+        "`Query.players` timed out after 100 ms"
+    );
+}
+
+#[tokio::test]
+async fn test_no_timeout() {
+    let response = query("gameStatus", json!({})).await;
+    assert_eq!(response["data"], "STARTED");
+}
@@ -0,0 +1,56 @@
+use lambda_appsync::{
+    serde_json::{self, json},
+    subscription_filters::{Filter, FilterGroup},
+    ID,
+};
+
+lambda_appsync::appsync_lambda_main!(
+    "schema.graphql",
+    only_appsync_types = true,
+    typed_filters = true,
+);
+
+#[test]
+fn test_typed_subscription_fields() {
+    let id = ID::new();
+    let group = FilterGroup::from(Filter::from([
+        subscription_fields::on_create_player::name().begins_with("Fer"),
+        subscription_fields::on_create_player::team().in_values([Team::Rust, Team::Python]),
+        subscription_fields::on_delete_player::id().ne(id),
+    ]));
+    assert_eq!(
+        serde_json::to_value(&group).unwrap(),
+        json!({
+            "filterGroup": [{
+                "filters": [
+                    {"fieldName": "name", "operator": "beginsWith", "value": "Fer"},
+                    {"fieldName": "team", "operator": "in", "value": ["RUST", "PYTHON"]},
+                    {"fieldName": "id", "operator": "ne", "value": id},
+                ]
+            }]
+        })
+    );
+}
+
+#[test]
+fn test_typed_subscription_enum_field() {
+    let filter = subscription_fields::on_create_player::team().eq(Team::MultiWordsTeam);
+    assert_eq!(
+        serde_json::to_value(&filter).unwrap(),
+        json!({"fieldName": "team", "operator": "eq", "value": "MULTI_WORDS_TEAM"})
+    );
+}
+
+#[test]
+fn test_subscription_payload_type() {
+    let player: subscription_fields::on_create_player::PayloadType = Player {
+        id: ID::new(),
+        name: "Ferris".to_owned(),
+        team: Team::Rust,
+    };
+    assert_eq!(player.name, "Ferris");
+    assert_eq!(
+        subscription_fields::on_create_player::FIELDS,
+        ["id", "name", "team"]
+    );
+}
@@ -0,0 +1,17 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "lambda-appsync/tests/schemas/typescript.graphql",
+    only_appsync_types = true,
+    emit_typescript = "lambda-appsync/tests/schemas/typescript.emitted.d.ts",
+);
+
+#[test]
+fn test_emit_typescript() {
+    let ts = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/schemas/typescript.emitted.d.ts"
+    ))
+    .unwrap();
+    assert_eq!(ts, include_str!("schemas/typescript.expected.d.ts"));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::{json, Value};
+
+appsync_lambda_main!("schema.graphql", batch = false);
+
+static CREATE_PLAYER_CALLED: AtomicBool = AtomicBool::new(false);
+
+fn non_empty(value: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        Err("The name cannot be empty".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+fn short(value: &str) -> Result<(), &'static str> {
+    if value.len() > 16 {
+        Err("The name is too long")
+    } else {
+        Ok(())
+    }
+}
+
+#[appsync_operation(mutation(createPlayer), validate(name = non_empty, name = short))]
+async fn create_player(name: String) -> Result<Player, AppsyncError> {
+    CREATE_PLAYER_CALLED.store(true, Ordering::SeqCst);
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team: Team::Rust,
+    })
+}
+
+async fn mutation(field_name: &str, arguments: Value) -> Value {
+    let event = common::event("Mutation", field_name, arguments);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    serde_json::to_value(function_handler(lambda_event).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_validation() {
+    let response = mutation("createPlayer", json!({"name": "  "})).await;
+    assert_eq!(response["errorType"], "BadRequest");
+    assert_eq!(response["errorMessage"], "The name cannot be empty");
+
+    let response = mutation("createPlayer", json!({"name": "A very long player name"})).await;
+    assert_eq!(response["errorType"], "BadRequest");
+    assert_eq!(response["errorMessage"], "The name is too long");
+    assert!(!CREATE_PLAYER_CALLED.load(Ordering::SeqCst));
+
+    let response = mutation("createPlayer", json!({"name": "Ferris"})).await;
+    assert_eq!(response["data"]["name"], "Ferris");
+    assert!(CREATE_PLAYER_CALLED.load(Ordering::SeqCst));
+}